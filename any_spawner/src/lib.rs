@@ -32,6 +32,9 @@
 use std::{future::Future, pin::Pin, sync::OnceLock};
 use thiserror::Error;
 
+mod local_sender;
+pub use local_sender::*;
+
 /// A future that has been pinned.
 pub type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
 /// A future that has been pinned.
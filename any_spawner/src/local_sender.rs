@@ -0,0 +1,115 @@
+use crate::{Executor, PinnedLocalFuture};
+use futures::{channel::mpsc, StreamExt};
+use std::fmt::Debug;
+use thiserror::Error;
+
+type Job<T> = Box<dyn FnOnce(&mut T) + Send>;
+
+/// An error returned when sending to a [`LocalSender`] whose loop has stopped.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("The local loop has stopped.")]
+pub struct LoopClosed;
+
+/// A thread-safe handle for sending work to a loop that owns a value on a single thread.
+///
+/// The value is moved into a task that is spawned on the thread that created the loop, and never
+/// leaves it, so it does not need to be `Send`. Each function sent with
+/// [`send`](LocalSender::send), from any thread, is run with a mutable reference to the value,
+/// in the order in which it was sent.
+///
+/// The loop stops, dropping the value, once every `LocalSender` for it has been dropped.
+///
+/// ```rust
+/// use any_spawner::LocalSender;
+/// use futures::{executor::LocalPool, task::LocalSpawnExt};
+/// use std::{rc::Rc, sync::mpsc};
+///
+/// let mut pool = LocalPool::new();
+/// let spawner = pool.spawner();
+///
+/// // `Rc` is not `Send`, but the sender can still be moved to another thread
+/// let sender = LocalSender::spawn_with(Rc::new(0), |fut| {
+///     spawner.spawn_local(fut).unwrap()
+/// });
+/// std::thread::spawn({
+///     let sender = sender.clone();
+///     move || sender.send(|value| *value = Rc::new(1)).unwrap()
+/// })
+/// .join()
+/// .unwrap();
+///
+/// let (tx, rx) = mpsc::channel();
+/// sender.send(move |value| tx.send(**value).unwrap()).unwrap();
+/// // the functions run when the loop is polled on this thread
+/// pool.run_until_stalled();
+/// assert_eq!(rx.recv().unwrap(), 1);
+/// ```
+pub struct LocalSender<T> {
+    tx: mpsc::UnboundedSender<Job<T>>,
+}
+
+impl<T> Clone for LocalSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+        }
+    }
+}
+
+impl<T> Debug for LocalSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalSender")
+            .field("type", &std::any::type_name::<T>())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl<T: 'static> LocalSender<T> {
+    /// Moves `value` into a loop that is spawned on the current thread with
+    /// [`Executor::spawn_local`].
+    ///
+    /// With executors that need to be polled, functions that have been sent only run when the
+    /// executor is polled, for example with [`Executor::poll_local`].
+    #[track_caller]
+    pub fn spawn(value: T) -> Self {
+        Self::spawn_with(value, |fut| Executor::spawn_local(fut))
+    }
+
+    /// Moves `value` into a loop that is spawned with the given function, which can be used to
+    /// run it on an executor other than the global [`Executor`], like the event loop of a
+    /// desktop application.
+    pub fn spawn_with(
+        mut value: T,
+        spawn: impl FnOnce(PinnedLocalFuture<()>),
+    ) -> Self {
+        let (tx, mut rx) = mpsc::unbounded::<Job<T>>();
+        spawn(Box::pin(async move {
+            while let Some(job) = rx.next().await {
+                job(&mut value);
+            }
+        }));
+        Self { tx }
+    }
+}
+
+impl<T> LocalSender<T> {
+    /// Queues a function to be run with a mutable reference to the value, on the thread that owns
+    /// it.
+    ///
+    /// Returns an error if the loop has stopped, for example because the executor it was spawned
+    /// on has shut down.
+    pub fn send(
+        &self,
+        fun: impl FnOnce(&mut T) + Send + 'static,
+    ) -> Result<(), LoopClosed> {
+        self.tx
+            .unbounded_send(Box::new(fun))
+            .map_err(|_| LoopClosed)
+    }
+
+    /// Returns `true` if the loop has stopped, so that nothing more can be sent to it.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
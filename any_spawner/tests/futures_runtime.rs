@@ -36,3 +36,32 @@ fn can_make_local_progress() {
     Executor::poll_local();
     assert_eq!(counter.load(Ordering::Acquire), 1);
 }
+
+#[cfg(feature = "futures-executor")]
+#[test]
+fn local_sender_runs_jobs_from_other_threads() {
+    use any_spawner::LocalSender;
+    use std::{cell::Cell, rc::Rc};
+
+    let _ = Executor::init_futures_executor();
+    let seen = Rc::new(Cell::new(0));
+    let sender = LocalSender::spawn(Rc::clone(&seen));
+
+    std::thread::scope(|s| {
+        for n in 1..=3 {
+            let sender = sender.clone();
+            s.spawn(move || {
+                sender.send(move |seen| seen.set(seen.get() + n)).unwrap();
+            });
+        }
+    });
+    // nothing runs until the local executor is polled
+    assert_eq!(seen.get(), 0);
+    Executor::poll_local();
+    assert_eq!(seen.get(), 6);
+
+    drop(sender);
+    Executor::poll_local();
+    // the loop has stopped and dropped its value
+    assert_eq!(Rc::strong_count(&seen), 1);
+}
@@ -24,7 +24,10 @@ use leptos::{
     config::LeptosOptions,
     context::{provide_context, use_context},
     prelude::expect_context,
-    reactive::{computed::ScopedFuture, owner::Owner},
+    reactive::{
+        computed::ScopedFuture,
+        owner::{ArenaLimitExceeded, Owner},
+    },
     IntoView,
 };
 use leptos_integration_utils::{
@@ -193,6 +196,12 @@ impl ExtendResponse for ActixResponse {
             );
         }
     }
+
+    fn from_render_error(error: ArenaLimitExceeded) -> Self {
+        ActixResponse(
+            HttpResponse::InternalServerError().body(error.to_string()),
+        )
+    }
 }
 
 /// Provides an easy way to redirect the user from within a server function.
@@ -1151,7 +1160,11 @@ impl StaticRouteGenerator {
                         let options = options.clone();
                         let path = path.to_owned();
                         let response_options = owner.with(use_context);
+                        let exceeded = owner.arena_limit_exceeded();
                         async move {
+                            if let Some(error) = exceeded {
+                                return Err(std::io::Error::other(error));
+                            }
                             write_static_route(
                                 &options,
                                 response_options,
@@ -1257,7 +1270,13 @@ where
                                 let options = options.clone();
                                 let path = path.to_owned();
                                 let response_options = owner.with(use_context);
+                                let exceeded = owner.arena_limit_exceeded();
                                 async move {
+                                    if let Some(error) = exceeded {
+                                        return Err(std::io::Error::other(
+                                            error,
+                                        ));
+                                    }
                                     write_static_route(
                                         &options,
                                         response_options,
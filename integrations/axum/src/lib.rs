@@ -54,7 +54,10 @@ use leptos::{
     config::LeptosOptions,
     context::{provide_context, use_context},
     prelude::*,
-    reactive::{computed::ScopedFuture, owner::Owner},
+    reactive::{
+        computed::ScopedFuture,
+        owner::{ArenaLimitExceeded, Owner},
+    },
     IntoView,
 };
 use leptos_integration_utils::{
@@ -185,6 +188,13 @@ impl ExtendResponse for AxumResponse {
             );
         }
     }
+
+    fn from_render_error(error: ArenaLimitExceeded) -> Self {
+        AxumResponse(
+            (StatusCode::INTERNAL_SERVER_ERROR, error.to_string())
+                .into_response(),
+        )
+    }
 }
 
 /// Provides an easy way to redirect the user from within a server function.
@@ -1447,7 +1457,11 @@ impl StaticRouteGenerator {
                             let options = options.clone();
                             let path = path.to_owned();
                             let response_options = owner.with(use_context);
+                            let exceeded = owner.arena_limit_exceeded();
                             async move {
+                                if let Some(error) = exceeded {
+                                    return Err(std::io::Error::other(error));
+                                }
                                 write_static_route(
                                     &options,
                                     response_options,
@@ -1581,7 +1595,11 @@ where
                             let options = options.clone();
                             let path = path.to_owned();
                             let response_options = owner.with(use_context);
+                            let exceeded = owner.arena_limit_exceeded();
                             async move {
+                                if let Some(error) = exceeded {
+                                    return Err(std::io::Error::other(error));
+                                }
                                 write_static_route(
                                     &options,
                                     response_options,
@@ -1,13 +1,19 @@
-use futures::{stream::once, Stream, StreamExt};
+use futures::{stream::once, FutureExt, Stream, StreamExt};
 use hydration_context::{SharedContext, SsrSharedContext};
 use leptos::{
     nonce::use_nonce,
-    reactive::owner::{Owner, Sandboxed},
+    reactive::owner::{ArenaLimitExceeded, Owner, Sandboxed},
     IntoView,
 };
 use leptos_config::LeptosOptions;
 use leptos_meta::ServerMetaContextOutput;
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{
+    any::Any,
+    future::{ready, Future},
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::Arc,
+};
 
 pub type PinnedStream<T> = Pin<Box<dyn Stream<Item = T> + Send>>;
 pub type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
@@ -23,6 +29,18 @@ pub trait ExtendResponse: Sized {
 
     fn set_default_content_type(&mut self, content_type: &str);
 
+    /// Creates the response for a render that failed because it reached the limit set with
+    /// [`Owner::set_arena_limit`].
+    ///
+    /// By default, this is a plain-text response describing the error. Integrations that can
+    /// set the status of a response should override this to return a
+    /// `500 Internal Server Error`, as the built-in integrations do.
+    fn from_render_error(error: ArenaLimitExceeded) -> Self {
+        let mut res = Self::from_stream(once(ready(error.to_string())));
+        res.set_default_content_type("text/plain; charset=utf-8");
+        res
+    }
+
     fn from_app<IV>(
         app_fn: impl FnOnce() -> IV + Send + 'static,
         meta_context: ServerMetaContextOutput,
@@ -37,6 +55,18 @@ pub trait ExtendResponse: Sized {
         IV: IntoView + 'static,
     {
         async move {
+            // the arena's usage is recorded on this span, which is created within the request's
+            // span
+            #[cfg(feature = "tracing")]
+            let span = tracing::info_span!(
+                "render_app",
+                arena.live = tracing::field::Empty,
+                arena.peak = tracing::field::Empty,
+                arena.created = tracing::field::Empty,
+                arena.bytes = tracing::field::Empty,
+                arena.limit_exceeded = tracing::field::Empty,
+            );
+
             let (owner, stream) =
                 build_response(app_fn, additional_context, stream_builder);
 
@@ -66,12 +96,23 @@ pub trait ExtendResponse: Sized {
             // wait for the first chunk of the stream, then set the status and headers
             let first_chunk = stream.next().await.unwrap_or_default();
 
+            // if the render has already failed, nothing has been sent yet, so the whole response
+            // can fail instead
+            if let Some(error) = owner.arena_limit_exceeded() {
+                #[cfg(feature = "tracing")]
+                record_arena_stats(&span, &owner);
+                owner.unset();
+                return Self::from_render_error(error);
+            }
+
             let mut res = Self::from_stream(Sandboxed::new(
                 once(async move { first_chunk })
                     .chain(stream)
                     // drop the owner, cleaning up the reactive runtime,
                     // once the stream is over
                     .chain(once(async move {
+                        #[cfg(feature = "tracing")]
+                        record_arena_stats(&span, &owner);
                         owner.unset();
                         Default::default()
                     })),
@@ -88,6 +129,30 @@ pub trait ExtendResponse: Sized {
     }
 }
 
+/// Records the usage of the request's reactive arena on its render span.
+#[cfg(feature = "tracing")]
+fn record_arena_stats(span: &tracing::Span, owner: &Owner) {
+    let stats = owner.arena_stats();
+    span.record("arena.live", stats.live);
+    span.record("arena.peak", stats.peak);
+    span.record("arena.created", stats.created);
+    span.record("arena.bytes", stats.bytes);
+    if let Some(error) = owner.arena_limit_exceeded() {
+        span.record("arena.limit_exceeded", tracing::field::display(&error));
+        tracing::error!(parent: span, "{error}");
+    }
+}
+
+/// Resumes a panic, unless it was caused by reaching the limit of the reactive arena.
+///
+/// That error is kept by the arena, so the render stops where it failed and the error is
+/// returned by [`Owner::arena_limit_exceeded`] instead.
+fn resume_unless_arena_limit(payload: Box<dyn Any + Send>) {
+    if !payload.is::<ArenaLimitExceeded>() {
+        std::panic::resume_unwind(payload);
+    }
+}
+
 pub fn build_response<IV>(
     app_fn: impl FnOnce() -> IV + Send + 'static,
     additional_context: impl FnOnce() + Send + 'static,
@@ -105,7 +170,7 @@ where
     let stream = Box::pin(Sandboxed::new({
         let owner = owner.clone();
         async move {
-            let stream = owner.with(|| {
+            let stream = owner.try_with(|| {
                 additional_context();
 
                 // run app
@@ -141,7 +206,25 @@ where
                 stream_builder(app, chunks)
             });
 
-            stream.await
+            let stream =
+                match stream {
+                    Ok(stream) => AssertUnwindSafe(stream).catch_unwind().await,
+                    Err(_) => Ok(Box::pin(futures::stream::empty())
+                        as PinnedStream<String>),
+                };
+            match stream {
+                Ok(stream) => Box::pin(
+                    AssertUnwindSafe(stream).catch_unwind().filter_map(
+                        |chunk| {
+                            ready(chunk.map_err(resume_unless_arena_limit).ok())
+                        },
+                    ),
+                ) as PinnedStream<String>,
+                Err(payload) => {
+                    resume_unless_arena_limit(payload);
+                    Box::pin(futures::stream::empty())
+                }
+            }
         }
     }));
     (owner, stream)
@@ -6,7 +6,10 @@ use reactive_graph::{
     traits::Set,
 };
 use std::hash::Hash;
-use tachys::{reactive_graph::OwnedView, view::keyed::keyed};
+use tachys::{
+    reactive_graph::OwnedView,
+    view::keyed::{keyed, KeyedItems},
+};
 
 /// Iterates over children and displays them, keyed by the `key` function given.
 ///
@@ -105,6 +108,27 @@ use tachys::{reactive_graph::OwnedView, view::keyed::keyed};
 ///   }
 /// # }
 /// ```
+///
+/// When the items are stored in an [`ArcSignalVec`](reactive_graph::collections::ArcSignalVec),
+/// [`vec_changes`](tachys::view::keyed::vec_changes) can be used for `each`. Each change made to
+/// the list is then applied to the rows directly, rather than comparing the whole list with its
+/// previous value.
+///
+/// ```
+/// # use leptos::prelude::*;
+/// use leptos::{reactive::collections::ArcSignalVec, tachys::view::keyed::vec_changes};
+///
+/// #[component]
+/// fn Rows(rows: ArcSignalVec<(usize, String)>) -> impl IntoView {
+///   view! {
+///     <ul>
+///         <For each=vec_changes(rows) key=|(id, _)| *id let((_, name))>
+///             <li>{name}</li>
+///         </For>
+///     </ul>
+///   }
+/// }
+/// ```
 #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
 #[component]
 pub fn For<IF, I, T, EF, N, KF, K>(
@@ -117,7 +141,7 @@ pub fn For<IF, I, T, EF, N, KF, K>(
 ) -> impl IntoView
 where
     IF: Fn() -> I + Send + 'static,
-    I: IntoIterator<Item = T> + Send + 'static,
+    I: KeyedItems<Item = T> + Send + 'static,
     EF: Fn(T) -> N + Send + Clone + 'static,
     N: IntoView + 'static,
     KF: Fn(&T) -> K + Send + Clone + 'static,
@@ -191,7 +215,7 @@ pub fn ForEnumerate<IF, I, T, EF, N, KF, K>(
 ) -> impl IntoView
 where
     IF: Fn() -> I + Send + 'static,
-    I: IntoIterator<Item = T> + Send + 'static,
+    I: KeyedItems<Item = T> + Send + 'static,
     EF: Fn(ReadSignal<usize>, T) -> N + Send + Clone + 'static,
     N: IntoView + 'static,
     KF: Fn(&T) -> K + Send + Clone + 'static,
@@ -15,6 +15,8 @@ pub use once_resource::*;
 mod resource;
 pub use resource::*;
 mod shared;
+mod stream_signal;
+pub use stream_signal::*;
 
 use base64::{engine::general_purpose::STANDARD_NO_PAD, DecodeError, Engine};
 pub use shared::*;
@@ -0,0 +1,404 @@
+use crate::{
+    initial_value, FromEncodedStr, IntoEncodedString,
+    IS_SUPPRESSING_RESOURCE_LOAD,
+};
+use codee::{string::JsonSerdeCodec, Decoder, Encoder};
+use core::{fmt::Debug, marker::PhantomData};
+use futures::{
+    future::{BoxFuture, Shared},
+    stream::BoxStream,
+    FutureExt, Stream, StreamExt,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::suspense::{SuspenseContext, TaskHandle},
+    graph::{AnySource, ToAnySource},
+    owner::{use_context, Owner},
+    signal::{
+        guards::{Plain, ReadGuard},
+        ArcReadSignal, ArcRwSignal,
+    },
+    traits::{
+        DefinedAt, IsDisposed, ReadUntracked, Set, Track, Update, With,
+        WithUntracked,
+    },
+};
+use std::{
+    future::Future,
+    panic::Location,
+    sync::{atomic::Ordering, Arc, Mutex},
+};
+
+/// The items that have been loaded from the stream, and whether it has ended.
+type LoadedItems<T> = (Vec<T>, bool);
+
+type ItemStream<T, E> = BoxStream<'static, Result<T, E>>;
+
+type StreamFn<T, E> = dyn Fn(usize) -> ItemStream<T, E> + Send + Sync;
+
+/// The loading state of an [`ArcStreamSignal`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamStatus<E> {
+    /// No page is loading, and more items may be available.
+    Idle,
+    /// A page of items is loading.
+    Loading,
+    /// The stream has ended, and no more items will be loaded.
+    Done,
+    /// The stream returned an error while loading the last page.
+    ///
+    /// Calling [`load_more`](ArcStreamSignal::load_more) again will restart the stream after the
+    /// items that have already been loaded.
+    Error(E),
+}
+
+/// A reference-counted signal that accumulates the items of a [`Stream`], one page at a time.
+///
+/// This is useful for infinite-scroll feeds and other paginated data. Reading the signal returns
+/// all the items that have been loaded so far. The stream is only polled when a page is requested
+/// with [`load_more`](ArcStreamSignal::load_more), so items are never fetched faster than they are
+/// needed.
+///
+/// The stream is created by a function that takes a cursor: the number of items that have already
+/// been loaded. The stream it returns should begin with the item after those.
+///
+/// The first page is loaded as soon as the signal is created. When server rendering, the items that
+/// have been loaded are serialized to the client with the encoding `Ser`. While hydrating, the
+/// client starts with those items and creates its own stream from the same cursor once more items
+/// are requested.
+///
+/// ```rust
+/// # use leptos_server::{ArcStreamSignal, StreamStatus};
+/// # use reactive_graph::prelude::*;
+/// # use futures::stream;
+/// # any_spawner::Executor::init_futures_executor().unwrap();
+/// # futures::executor::block_on(async move {
+/// // loads the numbers from 0 to 4, two at a time
+/// let numbers = ArcStreamSignal::<u32, ()>::new(2, |cursor| {
+///     stream::iter((cursor as u32..5).map(Ok))
+/// });
+/// numbers.ready().await;
+/// assert_eq!(numbers.get(), vec![0, 1]);
+///
+/// numbers.load_more();
+/// numbers.ready().await;
+/// assert_eq!(numbers.get(), vec![0, 1, 2, 3]);
+///
+/// numbers.load_more();
+/// numbers.ready().await;
+/// assert_eq!(numbers.get(), vec![0, 1, 2, 3, 4]);
+/// assert_eq!(numbers.status().get(), StreamStatus::Done);
+/// # });
+/// ```
+pub struct ArcStreamSignal<T, E, Ser = JsonSerdeCodec> {
+    items: ArcRwSignal<Vec<T>>,
+    status: ArcRwSignal<StreamStatus<E>>,
+    make_stream: Arc<StreamFn<T, E>>,
+    stream: Arc<Mutex<Option<ItemStream<T, E>>>>,
+    loading: Arc<Mutex<Option<Shared<BoxFuture<'static, ()>>>>>,
+    // the `Suspense` boundaries that are waiting for the first page, each of which is only
+    // registered once
+    suspended: Arc<Mutex<Vec<(SuspenseContext, TaskHandle)>>>,
+    page_size: usize,
+    ser: PhantomData<fn() -> Ser>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+}
+
+impl<T, E, Ser> Clone for ArcStreamSignal<T, E, Ser> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            status: self.status.clone(),
+            make_stream: Arc::clone(&self.make_stream),
+            stream: Arc::clone(&self.stream),
+            loading: Arc::clone(&self.loading),
+            suspended: Arc::clone(&self.suspended),
+            page_size: self.page_size,
+            ser: self.ser,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+        }
+    }
+}
+
+impl<T, E, Ser> Debug for ArcStreamSignal<T, E, Ser> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcStreamSignal")
+            .field("type", &std::any::type_name::<T>())
+            .field("items", &self.items)
+            .field("page_size", &self.page_size)
+            .finish()
+    }
+}
+
+impl<T, E, Ser> ArcStreamSignal<T, E, Ser>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Send + Sync + 'static,
+    Ser: Encoder<LoadedItems<T>> + Decoder<LoadedItems<T>>,
+    <Ser as Encoder<LoadedItems<T>>>::Error: Debug,
+    <Ser as Decoder<LoadedItems<T>>>::Error: Debug,
+    <<Ser as Decoder<LoadedItems<T>>>::Encoded as FromEncodedStr>::DecodingError:
+        Debug,
+    <Ser as Encoder<LoadedItems<T>>>::Encoded: IntoEncodedString,
+    <Ser as Decoder<LoadedItems<T>>>::Encoded: FromEncodedStr,
+{
+    /// Creates a new stream signal with the encoding `Ser`, which loads `page_size` items at a
+    /// time from the streams returned by `make_stream`.
+    ///
+    /// `make_stream` is called with the number of items that have already been loaded.
+    #[track_caller]
+    pub fn new_with_codec<S>(
+        page_size: usize,
+        make_stream: impl Fn(usize) -> S + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+    {
+        let shared_context = Owner::current_shared_context();
+        let id = shared_context
+            .as_ref()
+            .map(|sc| sc.next_id())
+            .unwrap_or_default();
+
+        let initial =
+            initial_value::<LoadedItems<T>, Ser>(&id, shared_context.as_ref());
+        let is_hydrated = initial.is_some();
+        let (items, done) = initial.unwrap_or_default();
+
+        let data = Self {
+            items: ArcRwSignal::new(items),
+            status: ArcRwSignal::new(if done {
+                StreamStatus::Done
+            } else {
+                StreamStatus::Idle
+            }),
+            make_stream: Arc::new(move |cursor| make_stream(cursor).boxed()),
+            stream: Default::default(),
+            loading: Default::default(),
+            suspended: Default::default(),
+            page_size: page_size.max(1),
+            ser: PhantomData,
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        };
+
+        if !is_hydrated && !IS_SUPPRESSING_RESOURCE_LOAD.load(Ordering::Relaxed)
+        {
+            data.load_more();
+        }
+
+        #[cfg(feature = "ssr")]
+        if let Some(shared_context) = shared_context {
+            if shared_context.get_is_hydrating() {
+                let data = data.clone();
+                shared_context.write_async(
+                    id,
+                    Box::pin(async move {
+                        data.ready().await;
+                        let loaded = (
+                            data.items.with_untracked(Vec::clone),
+                            data.status.with_untracked(|status| {
+                                matches!(status, StreamStatus::Done)
+                            }),
+                        );
+                        Ser::encode(&loaded).unwrap().into_encoded_string()
+                    }),
+                );
+            }
+        }
+
+        data
+    }
+}
+
+impl<T, E> ArcStreamSignal<T, E, JsonSerdeCodec>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Send + Sync + 'static,
+    JsonSerdeCodec: Encoder<LoadedItems<T>> + Decoder<LoadedItems<T>>,
+    <JsonSerdeCodec as Encoder<LoadedItems<T>>>::Error: Debug,
+    <JsonSerdeCodec as Decoder<LoadedItems<T>>>::Error: Debug,
+    <<JsonSerdeCodec as Decoder<LoadedItems<T>>>::Encoded as FromEncodedStr>::DecodingError:
+        Debug,
+    <JsonSerdeCodec as Encoder<LoadedItems<T>>>::Encoded: IntoEncodedString,
+    <JsonSerdeCodec as Decoder<LoadedItems<T>>>::Encoded: FromEncodedStr,
+{
+    /// Creates a stream signal using [`JsonSerdeCodec`] for encoding/decoding the loaded items.
+    ///
+    /// See [`ArcStreamSignal::new_with_codec`].
+    #[track_caller]
+    pub fn new<S>(
+        page_size: usize,
+        make_stream: impl Fn(usize) -> S + Send + Sync + 'static,
+    ) -> Self
+    where
+        S: Stream<Item = Result<T, E>> + Send + 'static,
+    {
+        ArcStreamSignal::new_with_codec(page_size, make_stream)
+    }
+}
+
+impl<T, E, Ser> ArcStreamSignal<T, E, Ser>
+where
+    T: Send + Sync + 'static,
+    E: Send + Sync + 'static,
+    Ser: 'static,
+{
+    /// Starts loading the next page of items.
+    ///
+    /// This does nothing if a page is already loading, or if the stream has ended.
+    pub fn load_more(&self) {
+        let fut = {
+            let mut loading = self.loading.lock().or_poisoned();
+            let done = self
+                .status
+                .with_untracked(|status| matches!(status, StreamStatus::Done));
+            if loading.is_some() || done {
+                return;
+            }
+            let this = self.clone();
+            let fut = async move { this.load_page().await }.boxed().shared();
+            *loading = Some(fut.clone());
+            fut
+        };
+        self.status.set(StreamStatus::Loading);
+        reactive_graph::spawn(fut);
+    }
+
+    /// Returns a `Future` that is ready once the page that is currently loading (if any) has
+    /// loaded.
+    pub fn ready(&self) -> impl Future<Output = ()> + Send + 'static {
+        let loading = self.loading.lock().or_poisoned().clone();
+        async move {
+            if let Some(loading) = loading {
+                loading.await;
+            }
+        }
+    }
+
+    /// The current loading state, as a signal.
+    pub fn status(&self) -> ArcReadSignal<StreamStatus<E>> {
+        self.status.read_only()
+    }
+
+    /// Whether a page of items is currently loading.
+    ///
+    /// This is reactive.
+    pub fn is_loading(&self) -> bool {
+        self.status
+            .with(|status| matches!(status, StreamStatus::Loading))
+    }
+
+    /// Whether the stream has ended, so that no more items will be loaded.
+    ///
+    /// This is reactive.
+    pub fn is_done(&self) -> bool {
+        self.status
+            .with(|status| matches!(status, StreamStatus::Done))
+    }
+
+    async fn load_page(&self) {
+        // the stream is only recreated once the previous one has failed, or after hydration
+        let stream = self.stream.lock().or_poisoned().take();
+        let mut stream = stream.unwrap_or_else(|| {
+            (self.make_stream)(self.items.with_untracked(Vec::len))
+        });
+
+        let mut page = Vec::with_capacity(self.page_size);
+        let mut status = StreamStatus::Idle;
+        while page.len() < self.page_size {
+            match stream.next().await {
+                Some(Ok(item)) => page.push(item),
+                Some(Err(e)) => {
+                    status = StreamStatus::Error(e);
+                    break;
+                }
+                None => {
+                    status = StreamStatus::Done;
+                    break;
+                }
+            }
+        }
+
+        if matches!(status, StreamStatus::Idle) {
+            *self.stream.lock().or_poisoned() = Some(stream);
+        }
+        if !page.is_empty() {
+            self.items.update(|items| items.extend(page));
+        }
+        let suspended = {
+            let mut loading = self.loading.lock().or_poisoned();
+            loading.take();
+            std::mem::take(&mut *self.suspended.lock().or_poisoned())
+        };
+        self.status.set(status);
+        // releasing the handles lets the `Suspense` boundaries render the loaded items
+        drop(suspended);
+    }
+}
+
+impl<T, E, Ser> DefinedAt for ArcStreamSignal<T, E, Ser> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+    }
+}
+
+impl<T, E, Ser> IsDisposed for ArcStreamSignal<T, E, Ser> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T, E, Ser> ToAnySource for ArcStreamSignal<T, E, Ser>
+where
+    T: Send + Sync + 'static,
+{
+    fn to_any_source(&self) -> AnySource {
+        self.items.to_any_source()
+    }
+}
+
+impl<T, E, Ser> Track for ArcStreamSignal<T, E, Ser>
+where
+    T: Send + Sync + 'static,
+{
+    fn track(&self) {
+        self.items.track();
+    }
+}
+
+impl<T, E, Ser> ReadUntracked for ArcStreamSignal<T, E, Ser>
+where
+    T: 'static,
+{
+    type Value = ReadGuard<Vec<T>, Plain<Vec<T>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        // only the first page suspends: later pages are loaded while the current items are shown
+        if let Some(suspense_context) = use_context::<SuspenseContext>() {
+            // holding the lock means the page cannot finish loading before the boundary is added
+            let loading = self.loading.lock().or_poisoned();
+            if loading.is_some() && self.items.with_untracked(Vec::is_empty) {
+                let mut suspended = self.suspended.lock().or_poisoned();
+                if !suspended
+                    .iter()
+                    .any(|(context, _)| context.tasks == suspense_context.tasks)
+                {
+                    let handle = suspense_context.task_id();
+                    suspended.push((suspense_context, handle));
+                }
+            }
+        }
+        self.items.try_read_untracked()
+    }
+}
@@ -0,0 +1,297 @@
+use any_spawner::Executor;
+use futures::{
+    channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    executor::block_on,
+    stream,
+};
+use leptos_server::{ArcStreamSignal, StreamStatus};
+use reactive_graph::{
+    computed::suspense::SuspenseContext,
+    owner::{provide_context, Owner},
+    prelude::*,
+    signal::ArcRwSignal,
+};
+use slotmap::SlotMap;
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+type Item = Result<u32, String>;
+
+/// Streams whose items are sent by the test, handed out in order each time the signal creates
+/// a stream.
+#[derive(Clone, Default)]
+struct Streams {
+    waiting: Arc<Mutex<VecDeque<UnboundedReceiver<Item>>>>,
+    cursors: Arc<Mutex<Vec<usize>>>,
+}
+
+impl Streams {
+    /// Prepares the next stream the signal will create.
+    fn next(&self) -> UnboundedSender<Item> {
+        let (tx, rx) = unbounded();
+        self.waiting.lock().unwrap().push_back(rx);
+        tx
+    }
+
+    fn signal(&self, page_size: usize) -> ArcStreamSignal<u32, String> {
+        let streams = self.clone();
+        ArcStreamSignal::new(page_size, move |cursor| {
+            streams.cursors.lock().unwrap().push(cursor);
+            streams
+                .waiting
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("no stream was prepared")
+        })
+    }
+
+    fn cursors(&self) -> Vec<usize> {
+        self.cursors.lock().unwrap().clone()
+    }
+}
+
+#[test]
+fn items_are_loaded_one_page_at_a_time() {
+    _ = Executor::init_futures_executor();
+    block_on(async {
+        let numbers = ArcStreamSignal::<u32, ()>::new(2, |cursor| {
+            stream::iter((cursor as u32..5).map(Ok))
+        });
+        numbers.ready().await;
+        assert_eq!(numbers.get_untracked(), [0, 1]);
+        assert_eq!(numbers.status().get_untracked(), StreamStatus::Idle);
+
+        numbers.load_more();
+        numbers.ready().await;
+        assert_eq!(numbers.get_untracked(), [0, 1, 2, 3]);
+
+        numbers.load_more();
+        numbers.ready().await;
+        assert_eq!(numbers.get_untracked(), [0, 1, 2, 3, 4]);
+        assert_eq!(numbers.status().get_untracked(), StreamStatus::Done);
+
+        // once the stream has ended, nothing more is loaded
+        numbers.load_more();
+        assert_eq!(numbers.status().get_untracked(), StreamStatus::Done);
+    });
+}
+
+#[test]
+fn status_follows_each_page() {
+    _ = Executor::init_futures_executor();
+    block_on(async {
+        let streams = Streams::default();
+        let tx = streams.next();
+        let numbers = streams.signal(2);
+        assert_eq!(numbers.status().get_untracked(), StreamStatus::Loading);
+
+        tx.unbounded_send(Ok(1)).unwrap();
+        tx.unbounded_send(Ok(2)).unwrap();
+        numbers.ready().await;
+        assert_eq!(numbers.get_untracked(), [1, 2]);
+        assert_eq!(numbers.status().get_untracked(), StreamStatus::Idle);
+
+        numbers.load_more();
+        assert!(numbers.is_loading());
+        tx.unbounded_send(Ok(3)).unwrap();
+        tx.unbounded_send(Err("oops".to_string())).unwrap();
+        numbers.ready().await;
+        assert_eq!(numbers.get_untracked(), [1, 2, 3]);
+        assert_eq!(
+            numbers.status().get_untracked(),
+            StreamStatus::Error("oops".to_string())
+        );
+
+        // after an error, a new stream starts after the items that have been loaded
+        let tx = streams.next();
+        numbers.load_more();
+        drop(tx);
+        numbers.ready().await;
+        assert_eq!(streams.cursors(), [0, 3]);
+        assert_eq!(numbers.get_untracked(), [1, 2, 3]);
+        assert!(numbers.is_done());
+    });
+}
+
+#[test]
+fn only_the_first_page_suspends() {
+    _ = Executor::init_futures_executor();
+    let owner = Owner::new();
+    owner.set();
+    let tasks = ArcRwSignal::new(SlotMap::new());
+    provide_context(SuspenseContext {
+        tasks: tasks.clone(),
+    });
+
+    block_on(async {
+        let streams = Streams::default();
+        let tx = streams.next();
+        let numbers = streams.signal(1);
+
+        // reading several times while the first page loads only adds one task
+        for _ in 0..3 {
+            assert!(numbers.read_untracked().is_empty());
+        }
+        assert_eq!(tasks.with_untracked(SlotMap::len), 1);
+
+        tx.unbounded_send(Ok(1)).unwrap();
+        numbers.ready().await;
+        assert_eq!(tasks.with_untracked(SlotMap::len), 0);
+
+        // later pages are loaded while the current items are shown
+        numbers.load_more();
+        assert_eq!(numbers.read_untracked().len(), 1);
+        assert_eq!(tasks.with_untracked(SlotMap::len), 0);
+    });
+}
+
+#[cfg(all(feature = "ssr", feature = "hydration"))]
+mod hydration {
+    use super::Streams;
+    use any_spawner::Executor;
+    use futures::{executor::block_on, stream, StreamExt};
+    use hydration_context::{
+        PinnedFuture, PinnedStream, SerializedDataId, SharedContext,
+        SsrSharedContext,
+    };
+    use leptos_server::{ArcStreamSignal, StreamStatus};
+    use reactive_graph::{owner::Owner, prelude::*};
+    use std::sync::Arc;
+    use throw_error::{Error, ErrorId};
+
+    /// Renders a stream signal on the server, and returns the data sent to the client.
+    fn serialize(items: Vec<u32>) -> String {
+        let shared_context = Arc::new(SsrSharedContext::new());
+        let owner = Owner::new_root(Some(shared_context.clone()));
+        owner.set();
+        let numbers = ArcStreamSignal::<u32, ()>::new(2, move |cursor| {
+            stream::iter(items.clone().into_iter().skip(cursor).map(Ok))
+        });
+        block_on(numbers.ready());
+
+        let data = shared_context.pending_data().unwrap();
+        let data = block_on(data.collect::<Vec<_>>()).concat();
+        owner.unset();
+        data
+    }
+
+    #[test]
+    fn loaded_items_are_sent_to_the_client() {
+        _ = Executor::init_futures_executor();
+        let data = serialize(vec![1, 2, 3]);
+        assert!(data.contains("[[1,2],false]"), "{data}");
+        let data = serialize(vec![1]);
+        assert!(data.contains("[[1],true]"), "{data}");
+    }
+
+    #[test]
+    fn hydrated_signal_continues_after_the_server_items() {
+        _ = Executor::init_futures_executor();
+        let shared_context = Arc::new(Hydrating("[[1,2],false]"));
+        let owner = Owner::new_root(Some(shared_context));
+        owner.set();
+
+        let streams = Streams::default();
+        let numbers = streams.signal(2);
+        // the first page is not loaded again
+        assert_eq!(numbers.get_untracked(), [1, 2]);
+        assert_eq!(numbers.status().get_untracked(), StreamStatus::Idle);
+        assert!(streams.cursors().is_empty());
+
+        let tx = streams.next();
+        numbers.load_more();
+        tx.unbounded_send(Ok(3)).unwrap();
+        drop(tx);
+        block_on(numbers.ready());
+        assert_eq!(streams.cursors(), [2]);
+        assert_eq!(numbers.get_untracked(), [1, 2, 3]);
+        assert!(numbers.is_done());
+
+        let owner = Owner::new_root(Some(Arc::new(Hydrating("[[1],true]"))));
+        owner.set();
+        let numbers = streams.signal(2);
+        assert_eq!(numbers.get_untracked(), [1]);
+        assert!(numbers.is_done());
+    }
+
+    /// A client that is hydrating, which reads the same server data for every signal.
+    #[derive(Debug)]
+    struct Hydrating(&'static str);
+
+    impl SharedContext for Hydrating {
+        fn is_browser(&self) -> bool {
+            true
+        }
+
+        fn next_id(&self) -> SerializedDataId {
+            SerializedDataId::new(0)
+        }
+
+        fn write_async(
+            &self,
+            _id: SerializedDataId,
+            _fut: PinnedFuture<String>,
+        ) {
+        }
+
+        fn read_data(&self, _id: &SerializedDataId) -> Option<String> {
+            Some(self.0.to_string())
+        }
+
+        fn await_data(&self, _id: &SerializedDataId) -> Option<String> {
+            None
+        }
+
+        fn pending_data(&self) -> Option<PinnedStream<String>> {
+            None
+        }
+
+        fn during_hydration(&self) -> bool {
+            true
+        }
+
+        fn hydration_complete(&self) {}
+
+        fn get_is_hydrating(&self) -> bool {
+            true
+        }
+
+        fn set_is_hydrating(&self, _is_hydrating: bool) {}
+
+        fn take_errors(&self) -> Vec<(SerializedDataId, ErrorId, Error)> {
+            Vec::new()
+        }
+
+        fn errors(
+            &self,
+            _boundary_id: &SerializedDataId,
+        ) -> Vec<(ErrorId, Error)> {
+            Vec::new()
+        }
+
+        fn seal_errors(&self, _boundary_id: &SerializedDataId) {}
+
+        fn register_error(
+            &self,
+            _error_boundary: SerializedDataId,
+            _error_id: ErrorId,
+            _error: Error,
+        ) {
+        }
+
+        fn defer_stream(&self, _wait_for: PinnedFuture<()>) {}
+
+        fn await_deferred(&self) -> Option<PinnedFuture<()>> {
+            None
+        }
+
+        fn set_incomplete_chunk(&self, _id: SerializedDataId) {}
+
+        fn get_incomplete_chunk(&self, _id: &SerializedDataId) -> bool {
+            false
+        }
+    }
+}
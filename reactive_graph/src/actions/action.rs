@@ -1,3 +1,9 @@
+use super::{
+    concurrency::{Concurrency, ConcurrencyPolicy, Turn},
+    optimistic::{
+        apply_update, rollback_on_failure, settle_update, ActionOutcome,
+    },
+};
 use crate::{
     computed::{ArcMemo, Memo},
     diagnostics::is_suppressing_resource_load,
@@ -6,13 +12,22 @@ use crate::{
         SyncStorage,
     },
     signal::{ArcRwSignal, RwSignal},
-    traits::{DefinedAt, Dispose, Get, GetUntracked, GetValue, Set, Update},
+    traits::{
+        DefinedAt, Dispose, Get, GetUntracked, GetValue, Set, Update,
+        WithUntracked,
+    },
     unwrap_signal,
 };
 use any_spawner::Executor;
-use futures::{channel::oneshot, select, FutureExt};
+use futures::{channel::oneshot, pin_mut, select, FutureExt};
+use or_poisoned::OrPoisoned;
 use send_wrapper::SendWrapper;
-use std::{future::Future, panic::Location, pin::Pin, sync::Arc};
+use std::{
+    future::Future,
+    panic::Location,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
 
 /// An action runs some asynchronous code when you dispatch a new value to it, and gives you
 /// reactive access to the result.
@@ -97,6 +112,7 @@ pub struct ArcAction<I, O> {
     value: ArcRwSignal<Option<O>>,
     version: ArcRwSignal<usize>,
     dispatched: ArcStoredValue<usize>,
+    concurrency: Arc<Mutex<Concurrency>>,
     #[allow(clippy::complexity)]
     action_fn: Arc<
         dyn Fn(&I) -> Pin<Box<dyn Future<Output = O> + Send>> + Send + Sync,
@@ -113,6 +129,7 @@ impl<I, O> Clone for ArcAction<I, O> {
             value: self.value.clone(),
             version: self.version.clone(),
             dispatched: self.dispatched.clone(),
+            concurrency: Arc::clone(&self.concurrency),
             action_fn: self.action_fn.clone(),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: self.defined_at,
@@ -197,6 +214,7 @@ where
             value: ArcRwSignal::new(value),
             version: Default::default(),
             dispatched: Default::default(),
+            concurrency: Default::default(),
             action_fn: Arc::new(move |input| Box::pin(action_fn(input))),
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
@@ -213,6 +231,47 @@ where
     }
 }
 
+impl<I, O> ArcAction<I, O> {
+    /// Sets what happens when the action is dispatched while an earlier dispatch is still in
+    /// flight. By default, dispatches run concurrently.
+    ///
+    /// ```rust
+    /// # use reactive_graph::actions::*;
+    /// # use reactive_graph::prelude::*;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// let save = ArcAction::new(|text: &String| {
+    ///     let text = text.clone();
+    ///     async move { text }
+    /// })
+    /// .with_concurrency(ConcurrencyPolicy::DropNew);
+    ///
+    /// save.dispatch("first".to_string());
+    /// // ignored, because the first save is still in flight
+    /// save.dispatch("second".to_string());
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    ///
+    /// assert_eq!(save.value().get(), Some("first".to_string()));
+    /// # });
+    /// ```
+    pub fn with_concurrency(self, policy: ConcurrencyPolicy) -> Self {
+        self.concurrency.lock().or_poisoned().policy = policy;
+        self
+    }
+
+    /// Decides whether a new dispatch can run.
+    ///
+    /// Returns `None` if loading is currently suppressed, or if the dispatch is dropped because
+    /// of the action's [`ConcurrencyPolicy`].
+    fn admit(&self) -> Option<Turn> {
+        if is_suppressing_resource_load() {
+            return None;
+        }
+        let busy = self.in_flight.get_untracked() > 0;
+        self.concurrency.lock().or_poisoned().admit(busy)
+    }
+}
+
 /// A handle that allows aborting an in-flight action. It is returned from [`Action::dispatch`] or
 /// [`ArcAction::dispatch`].
 #[derive(Debug)]
@@ -239,9 +298,27 @@ where
     /// Calls the `async` function with a reference to the input type as its argument.
     #[track_caller]
     pub fn dispatch(&self, input: I) -> ActionAbortHandle {
+        self.dispatch_and_settle(input, |_| |_| {})
+    }
+
+    /// Dispatches the action, unless it is dropped because of its [`ConcurrencyPolicy`].
+    ///
+    /// If the dispatch goes ahead, `prepare` is called with the input. It returns a callback
+    /// which is called with the result once the action resolves, or with `None` if it is
+    /// aborted or canceled.
+    #[track_caller]
+    fn dispatch_and_settle<F>(
+        &self,
+        input: I,
+        prepare: impl FnOnce(&I) -> F,
+    ) -> ActionAbortHandle
+    where
+        F: FnOnce(Option<&O>) + Send + 'static,
+    {
         let (abort_tx, mut abort_rx) = oneshot::channel();
-        if !is_suppressing_resource_load() {
-            let mut fut = (self.action_fn)(&input).fuse();
+        if let Some(mut turn) = self.admit() {
+            let on_settle = prepare(&input);
+            let fut = (self.action_fn)(&input);
 
             // Update the state before loading
             self.in_flight.update(|n| *n += 1);
@@ -256,24 +333,37 @@ where
                 let value = self.value.clone();
                 let in_flight = self.in_flight.clone();
                 async move {
-                    select! {
-                        // if the abort message has been sent, bail and do nothing
-                        _ = abort_rx => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                        },
-                        // otherwise, update the value
-                        result = fut => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                            let is_latest = dispatched.get_value() <= current_version;
-                            if is_latest {
-                                version.update(|n| *n += 1);
-                                value.update(|n| *n = Some(result));
+                    {
+                        let fut = turn.run(fut).fuse();
+                        pin_mut!(fut);
+                        select! {
+                            // if the abort message has been sent, bail and do nothing
+                            _ = abort_rx => {
+                                in_flight.update(|n| *n = n.saturating_sub(1));
+                                on_settle(None);
+                            },
+                            // otherwise, update the value
+                            result = fut => {
+                                in_flight.update(|n| *n = n.saturating_sub(1));
+                                match result {
+                                    Some(result) => {
+                                        on_settle(Some(&result));
+                                        let is_latest = dispatched.get_value() <= current_version;
+                                        if is_latest {
+                                            version.update(|n| *n += 1);
+                                            value.update(|n| *n = Some(result));
+                                        }
+                                    }
+                                    // canceled by a later dispatch
+                                    None => on_settle(None),
+                                }
                             }
                         }
                     }
                     if in_flight.get_untracked() == 0 {
                         input.update(|inp| *inp = None);
                     }
+                    turn.finish().await;
                 }
             });
         }
@@ -291,9 +381,26 @@ where
     /// ensuring that it is spawned on the current thread.
     #[track_caller]
     pub fn dispatch_local(&self, input: I) -> ActionAbortHandle {
+        self.dispatch_local_and_settle(input, |_| |_| {})
+    }
+
+    /// Dispatches the action on the current thread, unless it is dropped because of its
+    /// [`ConcurrencyPolicy`].
+    ///
+    /// In all other ways, this is identical to `dispatch_and_settle`.
+    #[track_caller]
+    fn dispatch_local_and_settle<F>(
+        &self,
+        input: I,
+        prepare: impl FnOnce(&I) -> F,
+    ) -> ActionAbortHandle
+    where
+        F: FnOnce(Option<&O>) + 'static,
+    {
         let (abort_tx, mut abort_rx) = oneshot::channel();
-        if !is_suppressing_resource_load() {
-            let mut fut = (self.action_fn)(&input).fuse();
+        if let Some(mut turn) = self.admit() {
+            let on_settle = prepare(&input);
+            let fut = (self.action_fn)(&input);
 
             // Update the state before loading
             self.in_flight.update(|n| *n += 1);
@@ -308,24 +415,37 @@ where
                 let dispatched = self.dispatched.clone();
                 let in_flight = self.in_flight.clone();
                 async move {
-                    select! {
-                        // if the abort message has been sent, bail and do nothing
-                        _ = abort_rx => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                        },
-                        // otherwise, update the value
-                        result = fut => {
-                            in_flight.update(|n| *n = n.saturating_sub(1));
-                            let is_latest = dispatched.get_value() <= current_version;
-                            if is_latest {
-                                version.update(|n| *n += 1);
-                                value.update(|n| *n = Some(result));
+                    {
+                        let fut = turn.run(fut).fuse();
+                        pin_mut!(fut);
+                        select! {
+                            // if the abort message has been sent, bail and do nothing
+                            _ = abort_rx => {
+                                in_flight.update(|n| *n = n.saturating_sub(1));
+                                on_settle(None);
+                            },
+                            // otherwise, update the value
+                            result = fut => {
+                                in_flight.update(|n| *n = n.saturating_sub(1));
+                                match result {
+                                    Some(result) => {
+                                        on_settle(Some(&result));
+                                        let is_latest = dispatched.get_value() <= current_version;
+                                        if is_latest {
+                                            version.update(|n| *n += 1);
+                                            value.update(|n| *n = Some(result));
+                                        }
+                                    }
+                                    // canceled by a later dispatch
+                                    None => on_settle(None),
+                                }
                             }
                         }
                     }
                     if in_flight.get_untracked() == 0 {
                         input.update(|inp| *inp = None);
                     }
+                    turn.finish().await;
                 }
             });
         }
@@ -333,6 +453,146 @@ where
     }
 }
 
+impl<I, O> ArcAction<I, O>
+where
+    I: Send + Sync + 'static,
+    O: ActionOutcome + Send + Sync + 'static,
+{
+    /// Dispatches the action, optimistically applying its effect before it resolves.
+    ///
+    /// `apply` is called immediately with the input. If the action is aborted, or resolves to an
+    /// error, `rollback` is called to undo the optimistic change. If the action succeeds, the
+    /// optimistic change is kept, and the result is available through [`value`](Self::value) as
+    /// usual.
+    ///
+    /// ```rust
+    /// # use reactive_graph::actions::*;
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::signal::ArcRwSignal;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// let likes = ArcRwSignal::new(10);
+    /// let like = ArcAction::new(|fail: &bool| {
+    ///     let fail = *fail;
+    ///     async move { if fail { Err("offline") } else { Ok(()) } }
+    /// });
+    ///
+    /// like.dispatch_optimistic(
+    ///     true,
+    ///     { let likes = likes.clone(); move |_| *likes.write() += 1 },
+    ///     { let likes = likes.clone(); move || *likes.write() -= 1 },
+    /// );
+    /// // the change is visible immediately...
+    /// assert_eq!(likes.get_untracked(), 11);
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    ///
+    /// // ...and rolled back once the action fails
+    /// assert_eq!(likes.get_untracked(), 10);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn dispatch_optimistic(
+        &self,
+        input: I,
+        apply: impl FnOnce(&I),
+        rollback: impl FnOnce() + Send + 'static,
+    ) -> ActionAbortHandle {
+        self.dispatch_and_settle(input, |input| {
+            apply(input);
+            rollback_on_failure(rollback)
+        })
+    }
+
+    /// Dispatches the action, optimistically updating `target` before it resolves.
+    ///
+    /// `update` is called immediately to modify the value of `target` using the input. If the
+    /// action is aborted, or resolves to an error, `target` is reset to the value it had before
+    /// the update. If the action succeeds, `reconcile` is called to bring the optimistic value in
+    /// line with the successful result: for example, by replacing a temporary ID with the one
+    /// assigned by the server.
+    ///
+    /// `target` can be any signal or store field that can be read and updated.
+    ///
+    /// The rollback restores a snapshot of the whole value, so it also discards any other changes
+    /// made to `target` while the action was pending, including those made by other dispatches.
+    /// If `target` can change concurrently, use [`dispatch_optimistic`](Self::dispatch_optimistic)
+    /// with a `rollback` that undoes only this update instead.
+    ///
+    /// ```rust
+    /// # use reactive_graph::actions::*;
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::signal::ArcRwSignal;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// // (id, title)
+    /// let todos = ArcRwSignal::new(Vec::<(Option<usize>, String)>::new());
+    /// let add_todo = ArcAction::new(|_title: &String| async {
+    ///     // the server assigns an ID
+    ///     Ok::<_, String>(42)
+    /// });
+    ///
+    /// add_todo.dispatch_optimistic_update(
+    ///     "Buy milk".to_string(),
+    ///     todos.clone(),
+    ///     |todos, title| todos.push((None, title.clone())),
+    ///     |todos, id| {
+    ///         if let Some(todo) = todos.iter_mut().find(|todo| todo.0.is_none()) {
+    ///             todo.0 = Some(*id);
+    ///         }
+    ///     },
+    /// );
+    /// assert_eq!(todos.get_untracked(), vec![(None, "Buy milk".to_string())]);
+    /// # tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    ///
+    /// assert_eq!(todos.get_untracked(), vec![(Some(42), "Buy milk".to_string())]);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn dispatch_optimistic_update<S, T>(
+        &self,
+        input: I,
+        target: S,
+        update: impl FnOnce(&mut T, &I),
+        reconcile: impl FnOnce(&mut T, &O::Success) + Send + 'static,
+    ) -> ActionAbortHandle
+    where
+        S: Update<Value = T>
+            + Set<Value = T>
+            + WithUntracked<Value = T>
+            + Send
+            + 'static,
+        T: Clone + Send + 'static,
+    {
+        self.dispatch_and_settle(input, |input| {
+            let previous = apply_update(&target, input, update);
+            settle_update(target, previous, reconcile)
+        })
+    }
+}
+
+impl<I, O> ArcAction<I, O>
+where
+    I: 'static,
+    O: ActionOutcome + 'static,
+{
+    /// Dispatches the action on the current thread, optimistically applying its effect before it
+    /// resolves.
+    ///
+    /// In all other ways, this is identical to [`ArcAction::dispatch_optimistic`].
+    #[track_caller]
+    pub fn dispatch_optimistic_local(
+        &self,
+        input: I,
+        apply: impl FnOnce(&I),
+        rollback: impl FnOnce() + 'static,
+    ) -> ActionAbortHandle {
+        self.dispatch_local_and_settle(input, |input| {
+            apply(input);
+            rollback_on_failure(rollback)
+        })
+    }
+}
+
 impl<I, O> ArcAction<I, O>
 where
     I: 'static,
@@ -367,6 +627,7 @@ where
             value: ArcRwSignal::new(value),
             version: Default::default(),
             dispatched: Default::default(),
+            concurrency: Default::default(),
             action_fn: Arc::new(move |input| {
                 Box::pin(SendWrapper::new(action_fn(input)))
             }),
@@ -701,6 +962,17 @@ where
     pub fn clear(&self) {
         self.inner.try_with_value(|inner| inner.value.set(None));
     }
+
+    /// Sets what happens when the action is dispatched while an earlier dispatch is still in
+    /// flight. By default, dispatches run concurrently.
+    ///
+    /// See [`ArcAction::with_concurrency`] for an example.
+    pub fn with_concurrency(self, policy: ConcurrencyPolicy) -> Self {
+        self.inner.try_with_value(|inner| {
+            inner.concurrency.lock().or_poisoned().policy = policy;
+        });
+        self
+    }
 }
 
 impl<I, O> Action<I, O, LocalStorage>
@@ -959,6 +1231,92 @@ where
     }
 }
 
+impl<I, O, S> Action<I, O, S>
+where
+    I: Send + Sync + 'static,
+    O: ActionOutcome + Send + Sync + 'static,
+    S: Storage<ArcAction<I, O>>,
+{
+    /// Dispatches the action, optimistically applying its effect before it resolves.
+    ///
+    /// `apply` is called immediately with the input. If the action is aborted, or resolves to an
+    /// error, `rollback` is called to undo the optimistic change. If the action succeeds, the
+    /// optimistic change is kept.
+    ///
+    /// See [`ArcAction::dispatch_optimistic`] for an example.
+    #[track_caller]
+    pub fn dispatch_optimistic(
+        &self,
+        input: I,
+        apply: impl FnOnce(&I),
+        rollback: impl FnOnce() + Send + 'static,
+    ) -> ActionAbortHandle {
+        self.inner
+            .try_with_value(|inner| {
+                inner.dispatch_optimistic(input, apply, rollback)
+            })
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+
+    /// Dispatches the action, optimistically updating `target` before it resolves.
+    ///
+    /// If the action is aborted, or resolves to an error, `target` is reset to the value it had
+    /// before the update. If the action succeeds, `reconcile` is called with the successful
+    /// result.
+    ///
+    /// Resetting `target` discards any other changes made to it while the action was pending.
+    /// See [`ArcAction::dispatch_optimistic_update`] for details and an example.
+    #[track_caller]
+    pub fn dispatch_optimistic_update<Tg, T>(
+        &self,
+        input: I,
+        target: Tg,
+        update: impl FnOnce(&mut T, &I),
+        reconcile: impl FnOnce(&mut T, &O::Success) + Send + 'static,
+    ) -> ActionAbortHandle
+    where
+        Tg: Update<Value = T>
+            + Set<Value = T>
+            + WithUntracked<Value = T>
+            + Send
+            + 'static,
+        T: Clone + Send + 'static,
+    {
+        self.inner
+            .try_with_value(|inner| {
+                inner.dispatch_optimistic_update(
+                    input, target, update, reconcile,
+                )
+            })
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
+impl<I, O, S> Action<I, O, S>
+where
+    I: 'static,
+    O: ActionOutcome + 'static,
+    S: Storage<ArcAction<I, O>>,
+{
+    /// Dispatches the action on the current thread, optimistically applying its effect before it
+    /// resolves.
+    ///
+    /// In all other ways, this is identical to [`Action::dispatch_optimistic`].
+    #[track_caller]
+    pub fn dispatch_optimistic_local(
+        &self,
+        input: I,
+        apply: impl FnOnce(&I),
+        rollback: impl FnOnce() + 'static,
+    ) -> ActionAbortHandle {
+        self.inner
+            .try_with_value(|inner| {
+                inner.dispatch_optimistic_local(input, apply, rollback)
+            })
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
 impl<I, O, S> Action<I, O, S>
 where
     I: Send + Sync + 'static,
@@ -0,0 +1,154 @@
+use futures::{
+    channel::oneshot,
+    future::{pending, Shared},
+    pin_mut, select, Future, FutureExt,
+};
+
+/// Determines what happens when an [`Action`](super::Action) is dispatched while an earlier
+/// dispatch is still in flight.
+///
+/// This is set with [`ArcAction::with_concurrency`](super::ArcAction::with_concurrency) or
+/// [`Action::with_concurrency`](super::Action::with_concurrency).
+///
+/// A dispatch that is canceled by a later one behaves as if its [`ActionAbortHandle`] had been
+/// used: it does not update the action's value, and any optimistic update is rolled back.
+///
+/// [`ActionAbortHandle`]: super::ActionAbortHandle
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConcurrencyPolicy {
+    /// Every dispatch runs immediately, concurrently with any others. The value of the action is
+    /// set by whichever dispatch finishes last.
+    #[default]
+    Concurrent,
+    /// Dispatches run one at a time, in the order they were dispatched.
+    Queue,
+    /// Dispatches are ignored while an earlier dispatch is in flight. This prevents
+    /// double-submits.
+    DropNew,
+    /// A new dispatch cancels every dispatch that is still in flight.
+    LatestWins,
+    /// While a dispatch is in flight, only the most recent new dispatch is kept: it runs once the
+    /// current one finishes, and any dispatch that was waiting before it is canceled.
+    ///
+    /// To ignore new dispatches while one is in flight instead, use
+    /// [`DropNew`](ConcurrencyPolicy::DropNew).
+    KeepLatest,
+}
+
+/// The state an action uses to apply its [`ConcurrencyPolicy`].
+#[derive(Debug, Default)]
+pub(crate) struct Concurrency {
+    pub(crate) policy: ConcurrencyPolicy,
+    // cancels every dispatch that is in flight
+    running: Vec<oneshot::Sender<()>>,
+    // cancels the dispatch that is waiting for its turn
+    waiting: Option<oneshot::Sender<()>>,
+    // resolves when the most recent dispatch has finished
+    last: Option<Shared<oneshot::Receiver<()>>>,
+}
+
+impl Concurrency {
+    /// Decides whether a new dispatch can run, canceling earlier dispatches if necessary.
+    ///
+    /// Returns `None` if the dispatch should be dropped.
+    pub(crate) fn admit(&mut self, busy: bool) -> Option<Turn> {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        let (done_tx, done_rx) = oneshot::channel();
+        let mut turn = Turn {
+            wait_for: None,
+            cancel: None,
+            cancel_while_running: false,
+            _done: done_tx,
+        };
+        match self.policy {
+            ConcurrencyPolicy::Concurrent => {}
+            ConcurrencyPolicy::DropNew => {
+                if busy {
+                    return None;
+                }
+            }
+            ConcurrencyPolicy::LatestWins => {
+                for running in self.running.drain(..) {
+                    _ = running.send(());
+                }
+                self.running.push(cancel_tx);
+                turn.cancel = Some(cancel_rx);
+                turn.cancel_while_running = true;
+            }
+            ConcurrencyPolicy::Queue => {
+                turn.wait_for = self.last.replace(done_rx.shared());
+            }
+            ConcurrencyPolicy::KeepLatest => {
+                if let Some(waiting) = self.waiting.take() {
+                    _ = waiting.send(());
+                }
+                if busy {
+                    self.waiting = Some(cancel_tx);
+                    turn.cancel = Some(cancel_rx);
+                }
+                turn.wait_for = self.last.replace(done_rx.shared());
+            }
+        }
+        Some(turn)
+    }
+}
+
+/// A dispatch that has been admitted by [`Concurrency::admit`].
+#[derive(Debug)]
+pub(crate) struct Turn {
+    wait_for: Option<Shared<oneshot::Receiver<()>>>,
+    cancel: Option<oneshot::Receiver<()>>,
+    cancel_while_running: bool,
+    // dropping this lets the next dispatch in the queue run
+    _done: oneshot::Sender<()>,
+}
+
+impl Turn {
+    /// Runs the future once it is this dispatch's turn.
+    ///
+    /// Returns `None` if the dispatch is canceled by a later one.
+    pub(crate) async fn run<T>(
+        &mut self,
+        fut: impl Future<Output = T>,
+    ) -> Option<T> {
+        let canceled = canceled(self.cancel.take()).fuse();
+        pin_mut!(canceled);
+        if let Some(wait_for) = self.wait_for.clone() {
+            select! {
+                _ = wait_for.fuse() => {},
+                _ = canceled => return None,
+            }
+        }
+        if self.cancel_while_running {
+            let fut = fut.fuse();
+            pin_mut!(fut);
+            select! {
+                value = fut => Some(value),
+                _ = canceled => None,
+            }
+        } else {
+            Some(fut.await)
+        }
+    }
+
+    /// Ends the dispatch.
+    ///
+    /// If it ended before its turn came, the next dispatch still waits for the one before this
+    /// one to finish, so that dispatches never overlap.
+    pub(crate) async fn finish(self) {
+        if let Some(wait_for) = self.wait_for {
+            _ = wait_for.await;
+        }
+    }
+}
+
+async fn canceled(cancel: Option<oneshot::Receiver<()>>) {
+    let canceled = match cancel {
+        Some(cancel) => cancel.await.is_ok(),
+        None => false,
+    };
+    // if the sender was dropped without canceling, this can no longer be canceled
+    if !canceled {
+        pending::<()>().await;
+    }
+}
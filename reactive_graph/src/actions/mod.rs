@@ -1,6 +1,10 @@
 //! Reactive primitives to asynchronously update some value.
 
 mod action;
+mod concurrency;
 mod multi_action;
+mod optimistic;
 pub use action::*;
+pub use concurrency::ConcurrencyPolicy;
 pub use multi_action::*;
+pub use optimistic::ActionOutcome;
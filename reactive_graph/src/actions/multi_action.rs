@@ -1,8 +1,11 @@
+use super::optimistic::{
+    apply_update, rollback_on_failure, settle_update, ActionOutcome,
+};
 use crate::{
     diagnostics::is_suppressing_resource_load,
     owner::{ArenaItem, FromLocal, LocalStorage, Storage, SyncStorage},
     signal::{ArcReadSignal, ArcRwSignal, ReadSignal, RwSignal},
-    traits::{DefinedAt, Dispose, GetUntracked, Set, Update},
+    traits::{DefinedAt, Dispose, GetUntracked, Set, Update, WithUntracked},
     unwrap_signal,
 };
 use std::{fmt::Debug, future::Future, panic::Location, pin::Pin, sync::Arc};
@@ -235,6 +238,50 @@ where
     }
 }
 
+impl<I, O, S> MultiAction<I, O, S>
+where
+    I: Send + Sync + 'static,
+    O: ActionOutcome + Send + Sync + 'static,
+    S: Storage<ArcMultiAction<I, O>>,
+{
+    /// Dispatches a submission, optimistically applying its effect before it resolves.
+    ///
+    /// See [`ArcMultiAction::dispatch_optimistic`] for details and an example.
+    pub fn dispatch_optimistic(
+        &self,
+        input: I,
+        apply: impl FnOnce(&I),
+        rollback: impl FnOnce() + Send + 'static,
+    ) {
+        self.inner.try_with_value(|inner| {
+            inner.dispatch_optimistic(input, apply, rollback)
+        });
+    }
+
+    /// Dispatches a submission, optimistically updating `target` before it resolves.
+    ///
+    /// Rolling back a failed submission discards any other changes made to `target` while it was
+    /// pending. See [`ArcMultiAction::dispatch_optimistic_update`] for details.
+    pub fn dispatch_optimistic_update<Tg, T>(
+        &self,
+        input: I,
+        target: Tg,
+        update: impl FnOnce(&mut T, &I),
+        reconcile: impl FnOnce(&mut T, &O::Success) + Send + 'static,
+    ) where
+        Tg: Update<Value = T>
+            + Set<Value = T>
+            + WithUntracked<Value = T>
+            + Send
+            + 'static,
+        T: Clone + Send + 'static,
+    {
+        self.inner.try_with_value(|inner| {
+            inner.dispatch_optimistic_update(input, target, update, reconcile)
+        });
+    }
+}
+
 impl<I, O> MultiAction<I, O>
 where
     I: Send + Sync + 'static,
@@ -490,6 +537,16 @@ where
     /// # });
     /// ```
     pub fn dispatch(&self, input: I) {
+        self.dispatch_and_settle(input, |_| {});
+    }
+
+    /// Dispatches a submission, calling `on_settle` with the result once it resolves, or with
+    /// `None` if it has been canceled.
+    fn dispatch_and_settle(
+        &self,
+        input: I,
+        on_settle: impl FnOnce(Option<&O>) + Send + 'static,
+    ) {
         if !is_suppressing_resource_load() {
             let fut = (self.action_fn)(&input);
 
@@ -508,7 +565,10 @@ where
             crate::spawn(async move {
                 let new_value = fut.await;
                 let canceled = submission.canceled.get_untracked();
-                if !canceled {
+                if canceled {
+                    on_settle(None);
+                } else {
+                    on_settle(Some(&new_value));
                     submission.value.try_set(Some(new_value));
                 }
                 submission.input.try_set(None);
@@ -573,6 +633,90 @@ where
     }
 }
 
+impl<I, O> ArcMultiAction<I, O>
+where
+    I: Send + Sync + 'static,
+    O: ActionOutcome + Send + Sync + 'static,
+{
+    /// Dispatches a submission, optimistically applying its effect before it resolves.
+    ///
+    /// `apply` is called immediately with the input. If the submission is canceled, or resolves
+    /// to an error, `rollback` is called to undo the optimistic change. If it succeeds, the
+    /// optimistic change is kept.
+    ///
+    /// ```rust
+    /// # use reactive_graph::actions::*;
+    /// # use reactive_graph::prelude::*;
+    /// # use reactive_graph::signal::ArcRwSignal;
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let todos = ArcRwSignal::new(Vec::<String>::new());
+    /// let add_todo = ArcMultiAction::new(|task: &String| {
+    ///     let valid = !task.is_empty();
+    ///     async move { if valid { Ok(()) } else { Err("empty task") } }
+    /// });
+    ///
+    /// for task in ["Buy milk", ""] {
+    ///     add_todo.dispatch_optimistic(
+    ///         task.to_string(),
+    ///         { let todos = todos.clone(); move |task| todos.write().push(task.clone()) },
+    ///         { let todos = todos.clone(); move || { todos.write().pop(); } },
+    ///     );
+    /// }
+    /// assert_eq!(todos.get_untracked(), vec!["Buy milk".to_string(), String::new()]);
+    /// # any_spawner::Executor::tick().await;
+    ///
+    /// assert_eq!(todos.get_untracked(), vec!["Buy milk".to_string()]);
+    /// # });
+    /// ```
+    pub fn dispatch_optimistic(
+        &self,
+        input: I,
+        apply: impl FnOnce(&I),
+        rollback: impl FnOnce() + Send + 'static,
+    ) {
+        if !is_suppressing_resource_load() {
+            apply(&input);
+        }
+        self.dispatch_and_settle(input, rollback_on_failure(rollback));
+    }
+
+    /// Dispatches a submission, optimistically updating `target` before it resolves.
+    ///
+    /// If the submission is canceled, or resolves to an error, `target` is reset to the value it
+    /// had before the update. If it succeeds, `reconcile` is called with the successful result.
+    ///
+    /// The rollback restores a snapshot of the whole value, so it also discards any other changes
+    /// made to `target` while the submission was pending. Because submissions run concurrently,
+    /// this includes the optimistic updates of any submissions that were dispatched after this
+    /// one. If that is a problem, use [`dispatch_optimistic`](Self::dispatch_optimistic) with a
+    /// `rollback` that undoes only this submission's change.
+    pub fn dispatch_optimistic_update<S, T>(
+        &self,
+        input: I,
+        target: S,
+        update: impl FnOnce(&mut T, &I),
+        reconcile: impl FnOnce(&mut T, &O::Success) + Send + 'static,
+    ) where
+        S: Update<Value = T>
+            + Set<Value = T>
+            + WithUntracked<Value = T>
+            + Send
+            + 'static,
+        T: Clone + Send + 'static,
+    {
+        if is_suppressing_resource_load() {
+            return;
+        }
+        let previous = apply_update(&target, &input, update);
+        self.dispatch_and_settle(
+            input,
+            settle_update(target, previous, reconcile),
+        );
+    }
+}
+
 impl<I, O> ArcMultiAction<I, O> {
     /// The set of all submissions to this multi-action.
     /// ```rust
@@ -0,0 +1,77 @@
+use crate::traits::{Set, Update, WithUntracked};
+
+/// The output of an action that can fail.
+///
+/// This is used by optimistic dispatches, like [`ArcAction::dispatch_optimistic`], to decide
+/// whether an optimistic update should be kept or rolled back when the action resolves.
+///
+/// [`ArcAction::dispatch_optimistic`]: super::ArcAction::dispatch_optimistic
+pub trait ActionOutcome {
+    /// The type of a successful result.
+    type Success;
+
+    /// Returns the successful result, or `None` if the action failed.
+    fn success(&self) -> Option<&Self::Success>;
+}
+
+impl<T, E> ActionOutcome for Result<T, E> {
+    type Success = T;
+
+    fn success(&self) -> Option<&Self::Success> {
+        self.as_ref().ok()
+    }
+}
+
+/// Creates the callback that runs when an optimistic dispatch settles, calling `rollback` if
+/// the action was aborted (`None`) or failed.
+pub(crate) fn rollback_on_failure<O>(
+    rollback: impl FnOnce(),
+) -> impl FnOnce(Option<&O>)
+where
+    O: ActionOutcome,
+{
+    move |result| {
+        if result.and_then(ActionOutcome::success).is_none() {
+            rollback();
+        }
+    }
+}
+
+/// Optimistically applies `update` to `target`, returning the value it had before the update.
+pub(crate) fn apply_update<I, S, T>(
+    target: &S,
+    input: &I,
+    update: impl FnOnce(&mut T, &I),
+) -> Option<T>
+where
+    S: Update<Value = T> + WithUntracked<Value = T>,
+    T: Clone,
+{
+    let previous = target.try_with_untracked(T::clone);
+    target.update(|value| update(value, input));
+    previous
+}
+
+/// Creates the callback that runs when an optimistic update settles: this either reconciles the
+/// target with the successful result, or restores the value it had before the update.
+///
+/// Restoring the snapshot also discards any writes made to the target in the meantime, which is
+/// documented on each of the public methods that use this.
+pub(crate) fn settle_update<O, S, T>(
+    target: S,
+    previous: Option<T>,
+    reconcile: impl FnOnce(&mut T, &O::Success),
+) -> impl FnOnce(Option<&O>)
+where
+    O: ActionOutcome,
+    S: Update<Value = T> + Set<Value = T>,
+{
+    move |result| match result.and_then(ActionOutcome::success) {
+        Some(success) => target.update(|value| reconcile(value, success)),
+        None => {
+            if let Some(previous) = previous {
+                target.set(previous);
+            }
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Synchronizing signals across browser tabs.
+//!
+//! [`sync_across_tabs`] keeps a signal in sync with signals that use the same channel name in
+//! other tabs or windows of the same origin, using a
+//! [`BroadcastChannel`](https://developer.mozilla.org/en-US/docs/Web/API/BroadcastChannel).
+//!
+//! Outside the browser, messages are delivered to every other signal synchronized on the same
+//! channel name on the current thread. This makes it possible to test cross-tab behavior
+//! natively by treating each signal as a separate "tab."
+
+use crate::{
+    effect::Effect,
+    traits::{Set, With, WithUntracked},
+};
+use codee::{Decoder, Encoder};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+/// Keeps the value of `signal` in sync with every other signal that is synchronized on the same
+/// `channel_name` in other tabs.
+///
+/// Whenever the value of the signal changes, it is encoded with the codec `C` and sent to the
+/// other tabs. Whenever a value is received from another tab, it is decoded and applied with
+/// [`Set`], without being broadcast again.
+///
+/// The current value is not broadcast when this is called, so opening a new tab does not
+/// overwrite the state of the tabs that are already open.
+///
+/// Synchronization stops when the current reactive [`Owner`](crate::owner::Owner) is cleaned up.
+/// If `BroadcastChannel` is not available (for example, in some older browsers or workers), a
+/// warning is logged and the signal is simply not synchronized.
+///
+/// ```rust
+/// # any_spawner::Executor::init_futures_executor();
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::RwSignal;
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// use codee::string::JsonSerdeCodec;
+/// use reactive_graph::broadcast::sync_across_tabs;
+///
+/// let cart = RwSignal::new(Vec::<u32>::new());
+/// sync_across_tabs(cart, "cart", JsonSerdeCodec);
+/// ```
+pub fn sync_across_tabs<S, T, C>(signal: S, channel_name: &str, _codec: C)
+where
+    S: With<Value = T>
+        + WithUntracked<Value = T>
+        + Set<Value = T>
+        + Clone
+        + 'static,
+    T: 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+    <C as Encoder<T>>::Error: Debug,
+    <C as Decoder<T>>::Error: Debug,
+{
+    // the most recent encoded value that was either sent to or received from another tab
+    // if the signal's value matches it, there is no need to broadcast it
+    let last_seen = Rc::new(RefCell::new(
+        signal
+            .try_with_untracked(|value| C::encode(value).ok())
+            .flatten(),
+    ));
+
+    let Some(channel) = Channel::open(channel_name, {
+        let signal = signal.clone();
+        let last_seen = Rc::clone(&last_seen);
+        move |message: &str| match C::decode(message) {
+            Ok(value) => {
+                *last_seen.borrow_mut() = Some(message.to_string());
+                signal.set(value);
+            }
+            Err(e) => crate::log_warning(format_args!(
+                "could not decode message from another tab: {e:?}"
+            )),
+        }
+    }) else {
+        return;
+    };
+
+    Effect::new(move |_| {
+        let encoded = signal.try_with(|value| C::encode(value));
+        match encoded {
+            Some(Ok(encoded)) => {
+                let mut last_seen = last_seen.borrow_mut();
+                if last_seen.as_deref() != Some(encoded.as_str()) {
+                    channel.post(&encoded);
+                    *last_seen = Some(encoded);
+                }
+            }
+            Some(Err(e)) => crate::log_warning(format_args!(
+                "could not encode value to send to other tabs: {e:?}"
+            )),
+            None => {}
+        }
+    });
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+struct Channel {
+    inner: web_sys::BroadcastChannel,
+    _on_message:
+        wasm_bindgen::closure::Closure<dyn FnMut(web_sys::MessageEvent)>,
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl Channel {
+    fn open(name: &str, on_message: impl Fn(&str) + 'static) -> Option<Self> {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let inner = match web_sys::BroadcastChannel::new(name) {
+            Ok(inner) => inner,
+            Err(e) => {
+                crate::log_warning(format_args!(
+                    "BroadcastChannel is not available, so {name:?} will not \
+                     be synchronized across tabs: {e:?}"
+                ));
+                return None;
+            }
+        };
+        let on_message = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(
+            move |ev: web_sys::MessageEvent| {
+                if let Some(message) = ev.data().as_string() {
+                    on_message(&message);
+                }
+            },
+        );
+        inner.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        Some(Self {
+            inner,
+            _on_message: on_message,
+        })
+    }
+
+    fn post(&self, message: &str) {
+        _ = self
+            .inner
+            .post_message(&wasm_bindgen::JsValue::from_str(message));
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl Drop for Channel {
+    fn drop(&mut self) {
+        self.inner.set_onmessage(None);
+        self.inner.close();
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+type Listener = (usize, Rc<dyn Fn(&str)>);
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+thread_local! {
+    static CHANNELS: RefCell<
+        std::collections::HashMap<String, Vec<Listener>>
+    > = Default::default();
+    static NEXT_ID: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+struct Channel {
+    name: String,
+    id: usize,
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Channel {
+    fn open(name: &str, on_message: impl Fn(&str) + 'static) -> Option<Self> {
+        let id = NEXT_ID.with(|n| {
+            let id = n.get();
+            n.set(id + 1);
+            id
+        });
+        CHANNELS.with_borrow_mut(|channels| {
+            channels
+                .entry(name.to_string())
+                .or_default()
+                .push((id, Rc::new(on_message)));
+        });
+        Some(Self {
+            name: name.to_string(),
+            id,
+        })
+    }
+
+    fn post(&self, message: &str) {
+        // clone the listeners out, so that they can open or close channels themselves
+        let listeners = CHANNELS.with_borrow(|channels| {
+            channels
+                .get(&self.name)
+                .map(|listeners| {
+                    listeners
+                        .iter()
+                        .filter(|(id, _)| *id != self.id)
+                        .map(|(_, listener)| Rc::clone(listener))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        });
+        for listener in listeners {
+            listener(message);
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+impl Drop for Channel {
+    fn drop(&mut self) {
+        _ = CHANNELS.try_with(|channels| {
+            let mut channels = channels.borrow_mut();
+            if let Some(listeners) = channels.get_mut(&self.name) {
+                listeners.retain(|(id, _)| *id != self.id);
+                if listeners.is_empty() {
+                    channels.remove(&self.name);
+                }
+            }
+        });
+    }
+}
@@ -1,10 +1,12 @@
+use crate::effect::{EffectPriority, Notification, PendingEffects};
 use core::sync::atomic::Ordering::Relaxed;
 use futures::{task::AtomicWaker, Stream};
+use or_poisoned::OrPoisoned;
 use std::{
     fmt::Debug,
     hash::Hash,
     pin::Pin,
-    sync::{atomic::AtomicBool, Arc, Weak},
+    sync::{atomic::AtomicBool, Arc, Mutex, Weak},
     task::{Context, Poll},
 };
 
@@ -18,6 +20,20 @@ pub(crate) struct Receiver(Weak<Inner>);
 struct Inner {
     waker: AtomicWaker,
     set: AtomicBool,
+    /// The priority of the effect that receives the notifications, if they are counted.
+    priority: Option<EffectPriority>,
+    /// The effects that a notification has been counted with as pending for the effect's
+    /// priority, if it has not been handed to the receiver yet.
+    counted: Mutex<Option<Arc<PendingEffects>>>,
+}
+
+impl Inner {
+    /// Takes responsibility for the pending notification, if there is one.
+    fn take_counted(&self) -> Option<(EffectPriority, Arc<PendingEffects>)> {
+        let priority = self.priority?;
+        let pending = self.counted.lock().or_poisoned().take()?;
+        Some((priority, pending))
+    }
 }
 
 impl Drop for Inner {
@@ -36,13 +52,31 @@ impl Drop for Inner {
         // therefore ending the task, and therefore dropping all data that the stream has
         // captured, avoiding a memory leak.
         self.waker.wake();
+
+        // the effect will not run again, so it should not hold up lower-priority effects
+        if let Some((priority, pending)) = self.take_counted() {
+            pending.finished(priority);
+        }
     }
 }
 
 pub fn channel() -> (Sender, Receiver) {
+    channel_with_priority(None)
+}
+
+/// Creates a channel for an effect, which counts each notification as pending for the effect's
+/// priority until the effect has handled it.
+///
+/// Notifications for user-blocking effects are not counted.
+pub fn channel_with_priority(
+    priority: Option<EffectPriority>,
+) -> (Sender, Receiver) {
     let inner = Arc::new(Inner {
         waker: AtomicWaker::new(),
         set: AtomicBool::new(false),
+        priority: priority
+            .filter(|priority| *priority != EffectPriority::UserBlocking),
+        counted: Mutex::new(None),
     });
     let rx = Arc::downgrade(&inner);
     (Sender(inner), Receiver(rx))
@@ -50,13 +84,32 @@ pub fn channel() -> (Sender, Receiver) {
 
 impl Sender {
     pub fn notify(&mut self) {
+        // only count the notification if there is still a receiver to handle it
+        if let Some(priority) = self.0.priority {
+            if Arc::weak_count(&self.0) > 0 {
+                let mut counted = self.0.counted.lock().or_poisoned();
+                if counted.is_none() {
+                    *counted = Some(PendingEffects::notified(priority));
+                }
+            }
+        }
         self.0.set.store(true, Relaxed);
         self.0.waker.wake();
     }
 }
 
+impl Drop for Receiver {
+    fn drop(&mut self) {
+        if let Some((priority, pending)) =
+            self.0.upgrade().and_then(|inner| inner.take_counted())
+        {
+            pending.finished(priority);
+        }
+    }
+}
+
 impl Stream for Receiver {
-    type Item = ();
+    type Item = Notification;
 
     fn poll_next(
         self: Pin<&mut Self>,
@@ -66,7 +119,7 @@ impl Stream for Receiver {
             inner.waker.register(cx.waker());
 
             if inner.set.swap(false, Relaxed) {
-                Poll::Ready(Some(()))
+                Poll::Ready(Some(Notification::new(inner.take_counted())))
             } else {
                 Poll::Pending
             }
@@ -0,0 +1,165 @@
+//! Reactive collections that describe how they have changed.
+//!
+//! An [`ArcSignalVec`] or [`ArcSignalMap`] can be read like any other signal. In addition, each
+//! change made through its methods is recorded as a diff: [`VecDiff`] or [`MapDiff`]. A
+//! [`DiffCursor`] returns the diffs that have been made since it last checked, so that code that
+//! mirrors the collection (like a rendered list) can apply only the changes, rather than
+//! comparing the whole collection to its previous value.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! use reactive_graph::collections::{ArcSignalVec, Changes, VecDiff};
+//!
+//! let list = ArcSignalVec::new(vec![1, 2, 3]);
+//! let mut cursor = list.cursor();
+//! // the first time a cursor is checked, it returns the whole collection
+//! assert_eq!(cursor.changes(), Changes::Reset(vec![1, 2, 3]));
+//!
+//! list.push(4);
+//! list.remove(0);
+//! assert_eq!(
+//!     cursor.changes(),
+//!     Changes::Diffs(vec![
+//!         VecDiff::Insert { index: 3, value: 4 },
+//!         VecDiff::Remove { index: 0 },
+//!     ])
+//! );
+//! assert_eq!(list.get(), vec![2, 3, 4]);
+//! ```
+
+mod map;
+mod vec;
+
+use crate::{
+    signal::ArcRwSignal,
+    traits::{ReadUntracked, Track},
+};
+pub use map::*;
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::VecDeque,
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+pub use vec::*;
+
+/// The changes to a collection since a [`DiffCursor`] last checked it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Changes<D, C> {
+    /// The diffs that have been made, in order.
+    Diffs(Vec<D>),
+    /// The current contents of the collection.
+    ///
+    /// This is returned the first time a cursor is checked, after the whole collection has been
+    /// replaced, or if so many changes have been made since the last check that the diffs are no
+    /// longer available.
+    Reset(C),
+}
+
+// the log always keeps at least this many diffs, even for small collections
+const MIN_RETAINED_DIFFS: usize = 32;
+
+/// The diffs that have recently been made to a collection.
+///
+/// Once there are more diffs than items in the collection, replaying them is no cheaper than
+/// starting from scratch, so older diffs are discarded.
+pub(crate) struct DiffLog<D> {
+    // the number of diffs that have ever been recorded, plus the number of resets
+    version: u64,
+    diffs: VecDeque<D>,
+}
+
+impl<D> Default for DiffLog<D> {
+    fn default() -> Self {
+        Self {
+            version: 0,
+            diffs: VecDeque::new(),
+        }
+    }
+}
+
+impl<D> DiffLog<D> {
+    pub(crate) fn record(&mut self, diff: D, len: usize) {
+        self.version += 1;
+        self.diffs.push_back(diff);
+        let retained = len.max(MIN_RETAINED_DIFFS);
+        while self.diffs.len() > retained {
+            self.diffs.pop_front();
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.version += 1;
+        self.diffs.clear();
+    }
+
+    fn since(&self, seen: u64) -> Option<impl Iterator<Item = &D>> {
+        let first = self.version - self.diffs.len() as u64;
+        (seen >= first).then(|| self.diffs.iter().skip((seen - first) as usize))
+    }
+}
+
+/// Reads the changes that are made to a reactive collection.
+///
+/// Every cursor keeps track of the changes it has already returned, so several cursors can follow
+/// the same collection independently.
+pub struct DiffCursor<D, C> {
+    values: ArcRwSignal<C>,
+    log: Arc<Mutex<DiffLog<D>>>,
+    seen: Option<u64>,
+}
+
+impl<D, C> Debug for DiffCursor<D, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiffCursor")
+            .field("values", &self.values)
+            .field("seen", &self.seen)
+            .finish()
+    }
+}
+
+impl<D, C> Clone for DiffCursor<D, C> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            log: Arc::clone(&self.log),
+            seen: self.seen,
+        }
+    }
+}
+
+impl<D, C> DiffCursor<D, C>
+where
+    D: Clone,
+    C: Clone + 'static,
+{
+    pub(crate) fn new(
+        values: ArcRwSignal<C>,
+        log: Arc<Mutex<DiffLog<D>>>,
+    ) -> Self {
+        Self {
+            values,
+            log,
+            seen: None,
+        }
+    }
+
+    /// Returns the changes that have been made since this was last called.
+    ///
+    /// This tracks the collection, so that a reactive context that calls it will run again
+    /// whenever the collection changes.
+    #[track_caller]
+    pub fn changes(&mut self) -> Changes<D, C> {
+        self.values.track();
+        let Some(values) = self.values.try_read_untracked() else {
+            return Changes::Diffs(Vec::new());
+        };
+        let log = self.log.lock().or_poisoned();
+        let changes = match self.seen.and_then(|seen| log.since(seen)) {
+            Some(diffs) => Changes::Diffs(diffs.cloned().collect()),
+            None => Changes::Reset(values.clone()),
+        };
+        self.seen = Some(log.version);
+        changes
+    }
+}
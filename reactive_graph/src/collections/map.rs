@@ -0,0 +1,290 @@
+use super::{DiffCursor, DiffLog};
+use crate::{
+    graph::SubscriberSet,
+    signal::{
+        guards::{Plain, ReadGuard},
+        subscriber_traits::AsSubscriberSet,
+        ArcRwSignal,
+    },
+    traits::{DefinedAt, IsDisposed, Notify, ReadUntracked},
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// A single change to an [`ArcSignalMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MapDiff<K, V> {
+    /// A new entry was added.
+    Insert {
+        /// The key of the new entry.
+        key: K,
+        /// The value of the new entry.
+        value: V,
+    },
+    /// The value of an existing entry was changed.
+    Update {
+        /// The key of the entry.
+        key: K,
+        /// The new value of the entry.
+        value: V,
+    },
+    /// An entry was removed.
+    Remove {
+        /// The key of the removed entry.
+        key: K,
+    },
+    /// All entries were removed.
+    Clear,
+}
+
+/// A reference-counted map signal that records each change made to it as a [`MapDiff`].
+///
+/// The map can be read like any other signal, but it can only be changed through its own methods
+/// (like [`insert`](ArcSignalMap::insert) or [`remove`](ArcSignalMap::remove)), each of which
+/// notifies subscribers and records a diff. Use [`cursor`](ArcSignalMap::cursor) to read the diffs.
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::collections::*;
+/// # use std::collections::HashMap;
+/// let scores = ArcSignalMap::new(HashMap::from([("alice", 1)]));
+/// let mut cursor = scores.cursor();
+/// cursor.changes();
+///
+/// scores.insert("bob", 2);
+/// scores.update_at(&"alice", |score| *score += 1);
+/// assert_eq!(scores.with(|scores| scores["alice"]), 2);
+/// assert_eq!(
+///     cursor.changes(),
+///     Changes::Diffs(vec![
+///         MapDiff::Insert { key: "bob", value: 2 },
+///         MapDiff::Update { key: "alice", value: 2 },
+///     ])
+/// );
+/// ```
+pub struct ArcSignalMap<K, V> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    values: ArcRwSignal<HashMap<K, V>>,
+    log: Arc<Mutex<DiffLog<MapDiff<K, V>>>>,
+}
+
+impl<K, V> Clone for ArcSignalMap<K, V> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            values: self.values.clone(),
+            log: Arc::clone(&self.log),
+        }
+    }
+}
+
+impl<K, V> Debug for ArcSignalMap<K, V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcSignalMap")
+            .field("key_type", &std::any::type_name::<K>())
+            .field("value_type", &std::any::type_name::<V>())
+            .field("value", &Arc::as_ptr(&self.values.value))
+            .finish()
+    }
+}
+
+impl<K, V> PartialEq for ArcSignalMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<K, V> Eq for ArcSignalMap<K, V> {}
+
+impl<K, V> Hash for ArcSignalMap<K, V> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.values.hash(state);
+    }
+}
+
+impl<K, V> Default for ArcSignalMap<K, V> {
+    #[track_caller]
+    fn default() -> Self {
+        Self::new(HashMap::new())
+    }
+}
+
+impl<K, V> ArcSignalMap<K, V> {
+    /// Creates a new map signal with the given entries.
+    #[track_caller]
+    pub fn new(values: HashMap<K, V>) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            values: ArcRwSignal::new(values),
+            log: Default::default(),
+        }
+    }
+}
+
+impl<K, V> ArcSignalMap<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    /// Returns a new cursor, which reads the changes made to this map.
+    ///
+    /// The first time the cursor is checked, it returns the whole map.
+    pub fn cursor(&self) -> DiffCursor<MapDiff<K, V>, HashMap<K, V>> {
+        DiffCursor::new(self.values.clone(), Arc::clone(&self.log))
+    }
+
+    /// Inserts an entry, returning the previous value for `key` if there was one.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.mutate(|values, log| {
+            let prev = values.insert(key.clone(), value.clone());
+            let diff = if prev.is_some() {
+                MapDiff::Update { key, value }
+            } else {
+                MapDiff::Insert { key, value }
+            };
+            log.record(diff, values.len());
+            prev
+        })
+    }
+
+    /// Removes the entry for `key`, returning its value if there was one.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.mutate(|values, log| {
+            let (key, value) = values.remove_entry(key)?;
+            log.record(MapDiff::Remove { key }, values.len());
+            Some(value)
+        })
+    }
+
+    /// Updates the value for `key` in place, returning the result of `fun`, or `None` if there is
+    /// no entry for that key.
+    pub fn update_at<U>(
+        &self,
+        key: &K,
+        fun: impl FnOnce(&mut V) -> U,
+    ) -> Option<U> {
+        self.mutate(|values, log| {
+            let value = values.get_mut(key)?;
+            let result = fun(value);
+            let diff = MapDiff::Update {
+                key: key.clone(),
+                value: value.clone(),
+            };
+            log.record(diff, values.len());
+            Some(result)
+        })
+    }
+
+    /// Removes all entries from the map.
+    pub fn clear(&self) {
+        self.mutate(|values, log| {
+            if !values.is_empty() {
+                values.clear();
+                log.record(MapDiff::Clear, 0);
+            }
+        })
+    }
+
+    /// Removes every entry for which `keep` returns `false`.
+    ///
+    /// Each removed entry is recorded as its own [`MapDiff::Remove`].
+    pub fn retain(&self, mut keep: impl FnMut(&K, &V) -> bool) {
+        self.mutate(|values, log| {
+            let len = values.len();
+            values.retain(|key, value| {
+                let kept = keep(key, value);
+                if !kept {
+                    log.record(MapDiff::Remove { key: key.clone() }, len);
+                }
+                kept
+            });
+        })
+    }
+
+    /// Replaces the whole map.
+    ///
+    /// Rather than a diff, cursors will return the new map the next time they are checked.
+    pub fn reset(&self, new_values: HashMap<K, V>) {
+        self.mutate(|values, log| {
+            *values = new_values;
+            log.reset();
+        })
+    }
+
+    fn mutate<U>(
+        &self,
+        fun: impl FnOnce(&mut HashMap<K, V>, &mut DiffLog<MapDiff<K, V>>) -> U,
+    ) -> U {
+        let (result, changed) = {
+            let mut values = self.values.value.write().or_poisoned();
+            let mut log = self.log.lock().or_poisoned();
+            let version = log.version;
+            let result = fun(&mut values, &mut log);
+            (result, log.version != version)
+        };
+        if changed {
+            self.values.notify();
+        }
+        result
+    }
+}
+
+impl<K, V> Extend<(K, V)> for ArcSignalMap<K, V>
+where
+    K: Hash + Eq + Clone + Send + Sync + 'static,
+    V: Clone + Send + Sync + 'static,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> DefinedAt for ArcSignalMap<K, V> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<K, V> IsDisposed for ArcSignalMap<K, V> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<K, V> AsSubscriberSet for ArcSignalMap<K, V> {
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    #[inline(always)]
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.values.as_subscriber_set()
+    }
+}
+
+impl<K: 'static, V: 'static> ReadUntracked for ArcSignalMap<K, V> {
+    type Value = ReadGuard<HashMap<K, V>, Plain<HashMap<K, V>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.values.try_read_untracked()
+    }
+}
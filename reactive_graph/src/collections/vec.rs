@@ -0,0 +1,353 @@
+use super::{DiffCursor, DiffLog};
+use crate::{
+    graph::SubscriberSet,
+    signal::{
+        guards::{Plain, ReadGuard},
+        subscriber_traits::AsSubscriberSet,
+        ArcRwSignal,
+    },
+    traits::{DefinedAt, IsDisposed, Notify, ReadUntracked},
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    fmt::{Debug, Formatter},
+    hash::Hash,
+    panic::Location,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// A single change to an [`ArcSignalVec`].
+///
+/// Indices refer to the list as it was when the change was made, so diffs should be applied in
+/// order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VecDiff<T> {
+    /// An item was inserted at `index`, shifting all later items to the right.
+    Insert {
+        /// The position of the new item.
+        index: usize,
+        /// The new item.
+        value: T,
+    },
+    /// The item at `index` was removed, shifting all later items to the left.
+    Remove {
+        /// The position of the removed item.
+        index: usize,
+    },
+    /// The item at `from` was removed, and then inserted at `to`.
+    Move {
+        /// The previous position of the item.
+        from: usize,
+        /// The new position of the item.
+        to: usize,
+    },
+    /// The item at `index` was changed.
+    Update {
+        /// The position of the item.
+        index: usize,
+        /// The new value of the item.
+        value: T,
+    },
+    /// All items were removed.
+    Clear,
+}
+
+/// A reference-counted list signal that records each change made to it as a [`VecDiff`].
+///
+/// The list can be read like any other signal, but it can only be changed through its own methods
+/// (like [`push`](ArcSignalVec::push) or [`remove`](ArcSignalVec::remove)), each of which
+/// notifies subscribers and records a diff. Use [`cursor`](ArcSignalVec::cursor) to read the diffs.
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::collections::*;
+/// let list = ArcSignalVec::new(vec!["a", "b"]);
+/// let mut cursor = list.cursor();
+/// cursor.changes();
+///
+/// list.move_item(0, 1);
+/// list.replace(0, "c");
+/// assert_eq!(list.get(), vec!["c", "a"]);
+/// assert_eq!(
+///     cursor.changes(),
+///     Changes::Diffs(vec![
+///         VecDiff::Move { from: 0, to: 1 },
+///         VecDiff::Update { index: 0, value: "c" },
+///     ])
+/// );
+/// ```
+pub struct ArcSignalVec<T> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    values: ArcRwSignal<Vec<T>>,
+    log: Arc<Mutex<DiffLog<VecDiff<T>>>>,
+}
+
+impl<T> Clone for ArcSignalVec<T> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            values: self.values.clone(),
+            log: Arc::clone(&self.log),
+        }
+    }
+}
+
+impl<T> Debug for ArcSignalVec<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcSignalVec")
+            .field("type", &std::any::type_name::<T>())
+            .field("value", &Arc::as_ptr(&self.values.value))
+            .finish()
+    }
+}
+
+impl<T> PartialEq for ArcSignalVec<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<T> Eq for ArcSignalVec<T> {}
+
+impl<T> Hash for ArcSignalVec<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.values.hash(state);
+    }
+}
+
+impl<T> Default for ArcSignalVec<T> {
+    #[track_caller]
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T> ArcSignalVec<T> {
+    /// Creates a new list signal with the given items.
+    #[track_caller]
+    pub fn new(values: Vec<T>) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            values: ArcRwSignal::new(values),
+            log: Default::default(),
+        }
+    }
+}
+
+impl<T> ArcSignalVec<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// Returns a new cursor, which reads the changes made to this list.
+    ///
+    /// The first time the cursor is checked, it returns the whole list.
+    pub fn cursor(&self) -> DiffCursor<VecDiff<T>, Vec<T>> {
+        DiffCursor::new(self.values.clone(), Arc::clone(&self.log))
+    }
+
+    /// Appends an item to the end of the list.
+    pub fn push(&self, value: T) {
+        self.mutate(|values, log| {
+            let index = values.len();
+            values.push(value.clone());
+            log.record(VecDiff::Insert { index, value }, values.len());
+        })
+    }
+
+    /// Removes the last item from the list and returns it, or `None` if it is empty.
+    pub fn pop(&self) -> Option<T> {
+        self.mutate(|values, log| {
+            let value = values.pop()?;
+            let index = values.len();
+            log.record(VecDiff::Remove { index }, index);
+            Some(value)
+        })
+    }
+
+    /// Inserts an item at `index`, shifting all later items to the right.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    #[track_caller]
+    pub fn insert(&self, index: usize, value: T) {
+        self.mutate(|values, log| {
+            values.insert(index, value.clone());
+            log.record(VecDiff::Insert { index, value }, values.len());
+        })
+    }
+
+    /// Removes and returns the item at `index`, shifting all later items to the left.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> T {
+        self.mutate(|values, log| {
+            let value = values.remove(index);
+            log.record(VecDiff::Remove { index }, values.len());
+            value
+        })
+    }
+
+    /// Replaces the item at `index`, returning the previous item.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    #[track_caller]
+    pub fn replace(&self, index: usize, value: T) -> T {
+        self.mutate(|values, log| {
+            let prev = std::mem::replace(&mut values[index], value.clone());
+            log.record(VecDiff::Update { index, value }, values.len());
+            prev
+        })
+    }
+
+    /// Updates the item at `index` in place, returning the result of `fun`, or `None` if there is
+    /// no item at that index.
+    pub fn update_at<U>(
+        &self,
+        index: usize,
+        fun: impl FnOnce(&mut T) -> U,
+    ) -> Option<U> {
+        self.mutate(|values, log| {
+            let value = values.get_mut(index)?;
+            let result = fun(value);
+            let value = value.clone();
+            log.record(VecDiff::Update { index, value }, values.len());
+            Some(result)
+        })
+    }
+
+    /// Moves the item at `from` so that it ends up at `to`.
+    ///
+    /// # Panics
+    /// Panics if either index is out of bounds.
+    #[track_caller]
+    pub fn move_item(&self, from: usize, to: usize) {
+        self.mutate(|values, log| {
+            assert!(
+                to < values.len(),
+                "move index (is {to}) should be < len (is {})",
+                values.len()
+            );
+            let value = values.remove(from);
+            values.insert(to, value);
+            if from != to {
+                log.record(VecDiff::Move { from, to }, values.len());
+            }
+        })
+    }
+
+    /// Removes all items from the list.
+    pub fn clear(&self) {
+        self.mutate(|values, log| {
+            if !values.is_empty() {
+                values.clear();
+                log.record(VecDiff::Clear, 0);
+            }
+        })
+    }
+
+    /// Removes every item for which `keep` returns `false`.
+    ///
+    /// Each removed item is recorded as its own [`VecDiff::Remove`].
+    pub fn retain(&self, mut keep: impl FnMut(&T) -> bool) {
+        self.mutate(|values, log| {
+            let len = values.len();
+            let mut index = 0;
+            values.retain(|value| {
+                let kept = keep(value);
+                if kept {
+                    index += 1;
+                } else {
+                    log.record(VecDiff::Remove { index }, len);
+                }
+                kept
+            });
+        })
+    }
+
+    /// Replaces the whole list.
+    ///
+    /// Rather than a diff, cursors will return the new list the next time they are checked.
+    pub fn reset(&self, new_values: Vec<T>) {
+        self.mutate(|values, log| {
+            *values = new_values;
+            log.reset();
+        })
+    }
+
+    fn mutate<U>(
+        &self,
+        fun: impl FnOnce(&mut Vec<T>, &mut DiffLog<VecDiff<T>>) -> U,
+    ) -> U {
+        let (result, changed) = {
+            let mut values = self.values.value.write().or_poisoned();
+            let mut log = self.log.lock().or_poisoned();
+            let version = log.version;
+            let result = fun(&mut values, &mut log);
+            (result, log.version != version)
+        };
+        if changed {
+            self.values.notify();
+        }
+        result
+    }
+}
+
+impl<T> Extend<T> for ArcSignalVec<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.mutate(|values, log| {
+            for value in iter {
+                let index = values.len();
+                values.push(value.clone());
+                log.record(VecDiff::Insert { index, value }, values.len());
+            }
+        })
+    }
+}
+
+impl<T> DefinedAt for ArcSignalVec<T> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T> IsDisposed for ArcSignalVec<T> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T> AsSubscriberSet for ArcSignalVec<T> {
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    #[inline(always)]
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.values.as_subscriber_set()
+    }
+}
+
+impl<T: 'static> ReadUntracked for ArcSignalVec<T> {
+    type Value = ReadGuard<Vec<T>, Plain<Vec<T>>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.values.try_read_untracked()
+    }
+}
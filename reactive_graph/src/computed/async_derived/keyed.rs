@@ -0,0 +1,243 @@
+use super::{ArcAsyncDerived, AsyncDerived};
+use crate::{
+    computed::ArcMemo,
+    owner::{provide_context, use_context, Owner},
+    traits::Get,
+};
+use futures::{
+    future::{BoxFuture, Shared},
+    FutureExt,
+};
+use or_poisoned::OrPoisoned;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    hash::Hash,
+    sync::{Arc, Mutex, Weak},
+};
+
+type InFlight<K, T> = HashMap<K, Shared<BoxFuture<'static, T>>>;
+
+/// The in-flight requests, by the name of their query and their types.
+type Entries = HashMap<(&'static str, TypeId), Box<dyn Any + Send + Sync>>;
+
+/// Tracks the in-flight requests of keyed async derived values, so that values with the same
+/// query and key share a single `Future`.
+///
+/// See [`ArcAsyncDerived::new_keyed`] for how this is used.
+///
+/// Requests are only shared while they are in flight: once a request has finished, the next value
+/// created with the same key makes a new request.
+///
+/// Keyed values use the registry provided via context. If there is none, a registry is provided
+/// at the root [`Owner`] of the current owner tree. On the server, where each request has its own
+/// root owner, this means requests are never shared between different users.
+#[derive(Clone, Default)]
+pub struct DedupRegistry {
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl Debug for DedupRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DedupRegistry").finish_non_exhaustive()
+    }
+}
+
+impl DedupRegistry {
+    /// Creates an empty registry.
+    ///
+    /// To share requests only within part of the application, provide the new registry via
+    /// context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the registry for the current owner tree, providing one at the root if necessary.
+    pub fn current() -> Self {
+        use_context::<Self>().unwrap_or_else(|| {
+            let registry = Self::new();
+            if let Some(owner) = Owner::current() {
+                owner.root().with(|| provide_context(registry.clone()));
+            }
+            registry
+        })
+    }
+
+    /// Returns the in-flight request for `key` of the query named `query`, starting a new one by
+    /// calling `fetcher` if there is none.
+    ///
+    /// Requests are only shared between fetchers that use the same `query`, so each kind of
+    /// request should be given its own name, even if it has the same key and value types as
+    /// another.
+    pub fn fetch<K, T, Fut>(
+        &self,
+        query: &'static str,
+        key: K,
+        fetcher: impl FnOnce(K) -> Fut,
+    ) -> Shared<BoxFuture<'static, T>>
+    where
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        if let Some(fut) = self
+            .with_in_flight(query, |in_flight: &mut InFlight<K, T>| {
+                in_flight.get(&key).cloned()
+            })
+        {
+            return fut;
+        }
+
+        // the registry is not locked while calling the fetcher, in case it creates keyed values
+        let fut = fetcher(key.clone());
+        let entries = Arc::downgrade(&self.entries);
+        let fut = {
+            let key = key.clone();
+            async move {
+                let value = fut.await;
+                Self::remove::<K, T>(&entries, query, &key);
+                value
+            }
+        }
+        .boxed()
+        .shared();
+
+        self.with_in_flight(query, |in_flight: &mut InFlight<K, T>| {
+            in_flight.entry(key).or_insert(fut).clone()
+        })
+    }
+
+    fn with_in_flight<K, T, U>(
+        &self,
+        query: &'static str,
+        fun: impl FnOnce(&mut InFlight<K, T>) -> U,
+    ) -> U
+    where
+        K: Send + Sync + 'static,
+        T: Send + Sync + 'static,
+    {
+        let mut entries = self.entries.lock().or_poisoned();
+        let in_flight = entries
+            .entry((query, TypeId::of::<InFlight<K, T>>()))
+            .or_insert_with(|| Box::new(InFlight::<K, T>::new()))
+            .downcast_mut::<InFlight<K, T>>()
+            .expect("entries are keyed by their type");
+        fun(in_flight)
+    }
+
+    fn remove<K, T>(
+        entries: &Weak<Mutex<Entries>>,
+        query: &'static str,
+        key: &K,
+    ) where
+        K: Hash + Eq + 'static,
+        T: 'static,
+    {
+        if let Some(entries) = entries.upgrade() {
+            let mut entries = entries.lock().or_poisoned();
+            let ty = (query, TypeId::of::<InFlight<K, T>>());
+            let now_empty = entries
+                .get_mut(&ty)
+                .and_then(|in_flight| {
+                    in_flight.downcast_mut::<InFlight<K, T>>()
+                })
+                .map(|in_flight| {
+                    in_flight.remove(key);
+                    in_flight.is_empty()
+                })
+                .unwrap_or(false);
+            if now_empty {
+                entries.remove(&ty);
+            }
+        }
+    }
+}
+
+impl<T: 'static> ArcAsyncDerived<T> {
+    /// Creates a new async derived computation that only runs its `Future` when its key changes.
+    ///
+    /// `key` is run reactively, like a [`Memo`](crate::computed::Memo). Whenever it returns a
+    /// different key, `fetcher` is called with the new key, and the value is updated once the
+    /// `Future` it returns has resolved. Reactive values that are read in `fetcher` are not
+    /// tracked.
+    ///
+    /// `query` names the kind of request that `fetcher` makes. While a request is in flight, any
+    /// other keyed value with the same query, key and value type awaits that request instead of
+    /// calling its own `fetcher`, so different fetchers should always use different names. See
+    /// [`DedupRegistry`] for how requests are shared.
+    ///
+    /// ```rust
+    /// # use reactive_graph::computed::*;
+    /// # use reactive_graph::signal::*;
+    /// # use reactive_graph::prelude::*;
+    /// # use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+    /// # tokio_test::block_on(async move {
+    /// # any_spawner::Executor::init_tokio(); let owner = reactive_graph::owner::Owner::new(); owner.set();
+    /// # let _guard = reactive_graph::diagnostics::SpecialNonReactiveZone::enter();
+    /// let fetches = Arc::new(AtomicUsize::new(0));
+    /// let fetch_user = {
+    ///     let fetches = Arc::clone(&fetches);
+    ///     move |id: u32| {
+    ///         fetches.fetch_add(1, Ordering::Relaxed);
+    ///         async move {
+    ///             tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    ///             format!("user {id}")
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// let id = RwSignal::new(1);
+    /// let header =
+    ///     ArcAsyncDerived::new_keyed("user", move || id.get(), fetch_user.clone());
+    /// let sidebar = ArcAsyncDerived::new_keyed("user", move || id.get(), fetch_user);
+    ///
+    /// assert_eq!(header.clone().await, "user 1");
+    /// assert_eq!(sidebar.clone().await, "user 1");
+    /// // both values shared a single request
+    /// assert_eq!(fetches.load(Ordering::Relaxed), 1);
+    /// # });
+    /// ```
+    #[track_caller]
+    pub fn new_keyed<K, Fut>(
+        query: &'static str,
+        key: impl Fn() -> K + Send + Sync + 'static,
+        fetcher: impl Fn(K) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        let registry = DedupRegistry::current();
+        let key = ArcMemo::new(move |_| key());
+        let fun = {
+            let key = key.clone();
+            move || registry.fetch(query, key.get(), &fetcher)
+        };
+        Self::new_with_manual_dependencies(None, fun, &key)
+    }
+}
+
+impl<T> AsyncDerived<T>
+where
+    T: 'static,
+{
+    /// Creates a new async derived computation that only runs its `Future` when its key changes.
+    ///
+    /// See [`ArcAsyncDerived::new_keyed`] for details and an example.
+    #[track_caller]
+    pub fn new_keyed<K, Fut>(
+        query: &'static str,
+        key: impl Fn() -> K + Send + Sync + 'static,
+        fetcher: impl Fn(K) -> Fut + Send + Sync + 'static,
+    ) -> Self
+    where
+        K: Hash + Eq + Clone + Send + Sync + 'static,
+        T: Clone + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        ArcAsyncDerived::new_keyed(query, key, fetcher).into()
+    }
+}
@@ -4,6 +4,7 @@ pub use arc_async_derived::*;
 mod async_derived;
 mod future_impls;
 mod inner;
+mod keyed;
 use crate::{
     graph::{AnySubscriber, Observer, WithObserver},
     owner::Owner,
@@ -11,6 +12,7 @@ use crate::{
 pub use async_derived::*;
 pub use future_impls::*;
 use futures::Future;
+pub use keyed::DedupRegistry;
 use pin_project_lite::pin_project;
 use std::{
     pin::Pin,
@@ -4,10 +4,12 @@
 mod effect;
 mod effect_function;
 mod inner;
+mod priority;
 mod render_effect;
 
 pub use effect::*;
 pub use effect_function::*;
+pub use priority::*;
 pub use render_effect::*;
 
 /// Creates a new render effect, which immediately runs `fun`.
@@ -1,6 +1,6 @@
 use crate::{
-    channel::{channel, Receiver},
-    effect::{inner::EffectInner, EffectFunction},
+    channel::{channel_with_priority, Receiver},
+    effect::{inner::EffectInner, EffectFunction, EffectPriority},
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
@@ -92,8 +92,10 @@ impl<S> Dispose for Effect<S> {
     }
 }
 
-fn effect_base() -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
-    let (mut observer, rx) = channel();
+fn effect_base(
+    priority: EffectPriority,
+) -> (Receiver, Owner, Arc<RwLock<EffectInner>>) {
+    let (mut observer, rx) = channel_with_priority(Some(priority));
 
     // spawn the effect asynchronously
     // we'll notify once so it runs on the next tick,
@@ -155,12 +157,27 @@ impl Effect<LocalStorage> {
     /// This spawns a task on the local thread using
     /// [`spawn_local`](any_spawner::Executor::spawn_local). For an effect that can be spawned on
     /// any thread, use [`new_sync`](Effect::new_sync).
-    pub fn new<T, M>(mut fun: impl EffectFunction<T, M> + 'static) -> Self
+    pub fn new<T, M>(fun: impl EffectFunction<T, M> + 'static) -> Self
+    where
+        T: 'static,
+    {
+        Self::new_with_priority(fun, EffectPriority::UserBlocking)
+    }
+
+    /// Creates a new effect, which runs according to the given [`EffectPriority`] once its
+    /// reactive dependencies have changed.
+    ///
+    /// This can be used to let expensive work yield to more urgent effects, or to schedule it
+    /// for the next animation frame or for when the browser is idle.
+    pub fn new_with_priority<T, M>(
+        mut fun: impl EffectFunction<T, M> + 'static,
+        priority: EffectPriority,
+    ) -> Self
     where
         T: 'static,
     {
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(priority);
             let value = Arc::new(RwLock::new(None::<T>));
             let mut first_run = true;
 
@@ -169,7 +186,9 @@ impl Effect<LocalStorage> {
                 let subscriber = inner.to_any_subscriber();
 
                 async move {
-                    while rx.next().await.is_some() {
+                    while let Some(notification) = rx.next().await {
+                        notification.ready().await;
+
                         if subscriber
                             .with_observer(|| subscriber.update_if_necessary())
                             || first_run
@@ -300,16 +319,35 @@ impl Effect<LocalStorage> {
     /// # });
     /// ```
     pub fn watch<D, T>(
+        dependency_fn: impl FnMut() -> D + 'static,
+        handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
+        immediate: bool,
+    ) -> Self
+    where
+        D: 'static,
+        T: 'static,
+    {
+        Self::watch_with_priority(
+            dependency_fn,
+            handler,
+            immediate,
+            EffectPriority::UserBlocking,
+        )
+    }
+
+    /// A version of [`Effect::watch`] that runs according to the given [`EffectPriority`].
+    pub fn watch_with_priority<D, T>(
         mut dependency_fn: impl FnMut() -> D + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + 'static,
         immediate: bool,
+        priority: EffectPriority,
     ) -> Self
     where
         D: 'static,
         T: 'static,
     {
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(priority);
             let mut first_run = true;
             let dep_value = Arc::new(RwLock::new(None::<D>));
             let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -320,7 +358,9 @@ impl Effect<LocalStorage> {
                 let subscriber = inner.to_any_subscriber();
 
                 async move {
-                    while rx.next().await.is_some() {
+                    while let Some(notification) = rx.next().await {
+                        notification.ready().await;
+
                         if subscriber
                             .with_observer(|| subscriber.update_if_necessary())
                             || first_run
@@ -372,13 +412,24 @@ impl Effect<SyncStorage> {
     /// This spawns a task that can be run on any thread. For an effect that will be spawned on
     /// the current thread, use [`new`](Effect::new).
     pub fn new_sync<T, M>(
+        fun: impl EffectFunction<T, M> + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        Self::new_sync_with_priority(fun, EffectPriority::UserBlocking)
+    }
+
+    /// A version of [`Effect::new_sync`] that runs according to the given [`EffectPriority`].
+    pub fn new_sync_with_priority<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
+        priority: EffectPriority,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
         let inner = cfg!(feature = "effects").then(|| {
-            let (mut rx, owner, inner) = effect_base();
+            let (mut rx, owner, inner) = effect_base(priority);
             let mut first_run = true;
             let value = Arc::new(RwLock::new(None::<T>));
 
@@ -387,7 +438,9 @@ impl Effect<SyncStorage> {
                 let subscriber = inner.to_any_subscriber();
 
                 async move {
-                    while rx.next().await.is_some() {
+                    while let Some(notification) = rx.next().await {
+                        notification.ready().await;
+
                         if subscriber
                             .with_observer(|| subscriber.update_if_necessary())
                             || first_run
@@ -419,12 +472,24 @@ impl Effect<SyncStorage> {
     ///
     /// This will run whether the `effects` feature is enabled or not.
     pub fn new_isomorphic<T, M>(
+        fun: impl EffectFunction<T, M> + Send + Sync + 'static,
+    ) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        Self::new_isomorphic_with_priority(fun, EffectPriority::UserBlocking)
+    }
+
+    /// A version of [`Effect::new_isomorphic`] that runs according to the given
+    /// [`EffectPriority`].
+    pub fn new_isomorphic_with_priority<T, M>(
         mut fun: impl EffectFunction<T, M> + Send + Sync + 'static,
+        priority: EffectPriority,
     ) -> Self
     where
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let (mut rx, owner, inner) = effect_base(priority);
         let mut first_run = true;
         let value = Arc::new(RwLock::new(None::<T>));
 
@@ -433,7 +498,9 @@ impl Effect<SyncStorage> {
             let subscriber = inner.to_any_subscriber();
 
             async move {
-                while rx.next().await.is_some() {
+                while let Some(notification) = rx.next().await {
+                    notification.ready().await;
+
                     if subscriber
                         .with_observer(|| subscriber.update_if_necessary())
                         || first_run
@@ -463,18 +530,37 @@ impl Effect<SyncStorage> {
 
     /// This is to [`Effect::watch`] what [`Effect::new_sync`] is to [`Effect::new`].
     pub fn watch_sync<D, T>(
+        dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
+        handler: impl FnMut(&D, Option<&D>, Option<T>) -> T + Send + Sync + 'static,
+        immediate: bool,
+    ) -> Self
+    where
+        D: Send + Sync + 'static,
+        T: Send + Sync + 'static,
+    {
+        Self::watch_sync_with_priority(
+            dependency_fn,
+            handler,
+            immediate,
+            EffectPriority::UserBlocking,
+        )
+    }
+
+    /// A version of [`Effect::watch_sync`] that runs according to the given [`EffectPriority`].
+    pub fn watch_sync_with_priority<D, T>(
         mut dependency_fn: impl FnMut() -> D + Send + Sync + 'static,
         mut handler: impl FnMut(&D, Option<&D>, Option<T>) -> T
             + Send
             + Sync
             + 'static,
         immediate: bool,
+        priority: EffectPriority,
     ) -> Self
     where
         D: Send + Sync + 'static,
         T: Send + Sync + 'static,
     {
-        let (mut rx, owner, inner) = effect_base();
+        let (mut rx, owner, inner) = effect_base(priority);
         let mut first_run = true;
         let dep_value = Arc::new(RwLock::new(None::<D>));
         let watch_value = Arc::new(RwLock::new(None::<T>));
@@ -486,7 +572,9 @@ impl Effect<SyncStorage> {
                 let subscriber = inner.to_any_subscriber();
 
                 async move {
-                    while rx.next().await.is_some() {
+                    while let Some(notification) = rx.next().await {
+                        notification.ready().await;
+
                         if subscriber
                             .with_observer(|| subscriber.update_if_necessary())
                             || first_run
@@ -0,0 +1,215 @@
+use any_spawner::Executor;
+use or_poisoned::OrPoisoned;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll, Waker},
+};
+
+/// Determines when an effect runs after one of its dependencies has changed.
+///
+/// Expensive effects can be given a lower priority, so that they yield to more urgent work like
+/// handling user input. An effect with a lower priority lets the user-blocking effects that were
+/// notified before it run first, and then waits until every other effect with a higher priority
+/// that was notified on the same thread has finished running.
+///
+/// In the browser (with the `effects` feature), [`AnimationFrame`](EffectPriority::AnimationFrame)
+/// and [`Idle`](EffectPriority::Idle) effects also wait for `requestAnimationFrame` and
+/// `requestIdleCallback`. Elsewhere, they are only ordered by priority.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EffectPriority {
+    /// Runs as soon as possible after its dependencies change. This is how effects created with
+    /// [`Effect::new`](super::Effect::new) are scheduled.
+    #[default]
+    UserBlocking,
+    /// Runs after the user-blocking effects that were notified before it.
+    Normal,
+    /// Runs before the next repaint, after any higher-priority effects that are waiting to run.
+    AnimationFrame,
+    /// Runs when the browser is idle, after all higher-priority effects have run.
+    Idle,
+}
+
+const PRIORITIES: usize = 4;
+
+/// The effects with each priority that have been notified on one thread, but have not finished
+/// running yet.
+///
+/// User-blocking effects are never counted, so that the effects that are created by default
+/// do not pay for any of this.
+#[derive(Debug, Default)]
+pub(crate) struct PendingEffects {
+    counts: [AtomicUsize; PRIORITIES],
+    /// Effects that are waiting for higher-priority effects to finish.
+    waiting: Mutex<Vec<Waker>>,
+}
+
+thread_local! {
+    static PENDING: Arc<PendingEffects> = Default::default();
+}
+
+impl PendingEffects {
+    /// Records that an effect with this priority has been notified on the current thread,
+    /// returning the effects it is counted with.
+    pub(crate) fn notified(priority: EffectPriority) -> Arc<Self> {
+        let pending = PENDING.with(Arc::clone);
+        pending.counts[priority.index()].fetch_add(1, Ordering::SeqCst);
+        pending
+    }
+
+    /// Records that an effect with this priority has finished running, or will not run.
+    pub(crate) fn finished(&self, priority: EffectPriority) {
+        if self.counts[priority.index()].fetch_sub(1, Ordering::SeqCst) == 1 {
+            let waiting =
+                std::mem::take(&mut *self.waiting.lock().or_poisoned());
+            for waker in waiting {
+                waker.wake();
+            }
+        }
+    }
+
+    fn higher_priority_pending(&self, priority: EffectPriority) -> bool {
+        self.counts[..priority.index()]
+            .iter()
+            .any(|pending| pending.load(Ordering::SeqCst) > 0)
+    }
+}
+
+impl EffectPriority {
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Waits until an effect with this priority should run.
+    async fn wait(self, pending: Arc<PendingEffects>) {
+        // user-blocking effects are not counted, but any that were notified along with this
+        // effect have already been woken, so they run while this effect waits for a tick
+        Executor::tick().await;
+        match self {
+            EffectPriority::AnimationFrame => animation_frame().await,
+            EffectPriority::Idle => idle().await,
+            _ => {}
+        }
+        Turn(self, pending).await
+    }
+}
+
+/// Resolves once no effects with a higher priority are waiting to run.
+struct Turn(EffectPriority, Arc<PendingEffects>);
+
+impl Future for Turn {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let Turn(priority, pending) = &*self;
+        if !pending.higher_priority_pending(*priority) {
+            return Poll::Ready(());
+        }
+
+        // register before checking again, so that an effect finishing in the meantime
+        // cannot be missed
+        let mut waiting = pending.waiting.lock().or_poisoned();
+        if pending.higher_priority_pending(*priority) {
+            waiting.push(cx.waker().clone());
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
+
+/// A notification that an effect's dependencies have changed.
+///
+/// The effect counts as waiting to run until this is dropped, so it should be held until the
+/// effect has run.
+#[derive(Debug)]
+pub(crate) struct Notification {
+    counted: Option<(EffectPriority, Arc<PendingEffects>)>,
+}
+
+impl Notification {
+    pub(crate) fn new(
+        counted: Option<(EffectPriority, Arc<PendingEffects>)>,
+    ) -> Self {
+        Self { counted }
+    }
+
+    /// Waits until the effect should run, according to its priority.
+    pub(crate) async fn ready(&self) {
+        if let Some((priority, pending)) = &self.counted {
+            priority.wait(Arc::clone(pending)).await;
+        }
+    }
+}
+
+impl Drop for Notification {
+    fn drop(&mut self) {
+        if let Some((priority, pending)) = &self.counted {
+            pending.finished(*priority);
+        }
+    }
+}
+
+// the browser callbacks are not `Send`, but the browser only has one thread anyway
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "effects"))]
+async fn animation_frame() {
+    send_wrapper::SendWrapper::new(request_animation_frame()).await
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "effects"))]
+async fn idle() {
+    send_wrapper::SendWrapper::new(request_idle_callback()).await
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "effects"))]
+async fn request_animation_frame() {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    let cb = Closure::once_into_js(move || {
+        _ = tx.send(());
+    });
+    if window.request_animation_frame(cb.unchecked_ref()).is_ok() {
+        _ = rx.await;
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", feature = "effects"))]
+async fn request_idle_callback() {
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+    let cb = Closure::once_into_js(move || {
+        _ = tx.send(());
+    });
+    // `requestIdleCallback` is not available in every browser, so fall back to a timeout
+    if window.request_idle_callback(cb.unchecked_ref()).is_ok()
+        || window.set_timeout_with_callback(cb.unchecked_ref()).is_ok()
+    {
+        _ = rx.await;
+    }
+}
+
+#[cfg(not(all(
+    target_arch = "wasm32",
+    target_os = "unknown",
+    feature = "effects"
+)))]
+async fn animation_frame() {}
+
+#[cfg(not(all(
+    target_arch = "wasm32",
+    target_os = "unknown",
+    feature = "effects"
+)))]
+async fn idle() {}
@@ -1,6 +1,6 @@
 use crate::{
-    channel::channel,
-    effect::inner::EffectInner,
+    channel::channel_with_priority,
+    effect::{inner::EffectInner, EffectPriority},
     graph::{
         AnySubscriber, ReactiveNode, SourceSet, Subscriber, ToAnySubscriber,
         WithObserver,
@@ -61,12 +61,27 @@ where
     pub fn new_with_value(
         fun: impl FnMut(Option<T>) -> T + 'static,
         initial_value: Option<T>,
+    ) -> Self {
+        Self::new_with_priority(
+            fun,
+            initial_value,
+            EffectPriority::UserBlocking,
+        )
+    }
+
+    /// Creates a new render effect with an initial value, which immediately runs `fun`, and
+    /// then reruns according to the given [`EffectPriority`] once its dependencies have changed.
+    pub fn new_with_priority(
+        fun: impl FnMut(Option<T>) -> T + 'static,
+        initial_value: Option<T>,
+        priority: EffectPriority,
     ) -> Self {
         fn erased<T>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + 'static>,
             initial_value: Option<T>,
+            priority: EffectPriority,
         ) -> RenderEffect<T> {
-            let (observer, mut rx) = channel();
+            let (observer, mut rx) = channel_with_priority(Some(priority));
             let value = Arc::new(RwLock::new(None::<T>));
             let owner = Owner::new();
             let inner = Arc::new(RwLock::new(EffectInner {
@@ -90,7 +105,9 @@ where
                     let subscriber = inner.to_any_subscriber();
 
                     async move {
-                        while rx.next().await.is_some() {
+                        while let Some(notification) = rx.next().await {
+                            notification.ready().await;
+
                             if subscriber.with_observer(|| {
                                 subscriber.update_if_necessary()
                             }) {
@@ -112,7 +129,7 @@ where
             RenderEffect { value, inner }
         }
 
-        erased(Box::new(fun), initial_value)
+        erased(Box::new(fun), initial_value, priority)
     }
 
     /// Mutably accesses the current value.
@@ -140,7 +157,8 @@ where
         fn erased<T: Send + Sync + 'static>(
             mut fun: Box<dyn FnMut(Option<T>) -> T + Send + Sync + 'static>,
         ) -> RenderEffect<T> {
-            let (observer, mut rx) = channel();
+            let (observer, mut rx) =
+                channel_with_priority(Some(EffectPriority::UserBlocking));
             let value = Arc::new(RwLock::new(None::<T>));
             let owner = Owner::new();
             let inner = Arc::new(RwLock::new(EffectInner {
@@ -158,7 +176,9 @@ where
                 let subscriber = inner.to_any_subscriber();
 
                 async move {
-                    while rx.next().await.is_some() {
+                    while let Some(notification) = rx.next().await {
+                        notification.ready().await;
+
                         if subscriber
                             .with_observer(|| subscriber.update_if_necessary())
                         {
@@ -0,0 +1,200 @@
+//! Errors that describe why a reactive value could not be accessed.
+//!
+//! The `try_` methods of the [reactive traits](crate::traits) return `None` when a value cannot be
+//! accessed. The `checked_` methods (like [`checked_get`](crate::traits::Get::checked_get) or
+//! [`checked_update`](crate::traits::Update::checked_update)) instead return a [`ReactiveError`],
+//! which describes where the value was defined, where it was accessed, and which [`Owner`] was
+//! active at the time.
+//!
+//! A value usually cannot be accessed because it has been disposed, but its lock may also have
+//! been poisoned by a panic, or it may already be locked (for example, when a signal is read
+//! inside its own update). [`ReactiveError::kind`] tells these cases apart.
+//!
+//! Updating an inaccessible value with [`update`](crate::traits::Update::update) or
+//! [`set`](crate::traits::Set::set) does nothing. Reading one with a method that cannot fail,
+//! like [`get`](crate::traits::Get::get), has no value to return: in debug builds, it panics with
+//! a message describing the error. In release builds, it unwinds with the [`ReactiveError`] as
+//! the payload instead, without calling the panic hook, so that it can be caught with
+//! [`catch_unwind`](std::panic::catch_unwind) and downcast. In every case, the error is first
+//! passed to the hook set with [`set_disposed_access_hook`], so that it can be recorded even in
+//! release builds, where the location at which the value was defined is not available.
+
+use crate::owner::Owner;
+use or_poisoned::OrPoisoned;
+use std::{
+    cell::Cell,
+    error::Error,
+    fmt::Display,
+    panic::Location,
+    sync::{Arc, RwLock},
+};
+
+type DisposedAccessHook = Arc<dyn Fn(&ReactiveError) + Send + Sync>;
+
+static DISPOSED_ACCESS_HOOK: RwLock<Option<DisposedAccessHook>> =
+    RwLock::new(None);
+
+thread_local! {
+    // why the last access to a reactive value on this thread failed, if it has not been
+    // reported yet
+    static ACCESS_FAILURE: Cell<Option<ReactiveErrorKind>> = const { Cell::new(None) };
+}
+
+/// Why a reactive value could not be accessed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ReactiveErrorKind {
+    /// The value has already been disposed.
+    Disposed,
+    /// The lock around the value was poisoned by a panic while it was being accessed.
+    Poisoned,
+    /// The value is already locked, for example because it is being updated.
+    Locked,
+}
+
+/// An error that occurs when trying to access a reactive value that cannot be accessed, usually
+/// because it has already been disposed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReactiveError {
+    kind: ReactiveErrorKind,
+    defined_at: Option<&'static Location<'static>>,
+    accessed_at: &'static Location<'static>,
+    owner: Option<usize>,
+}
+
+impl ReactiveError {
+    /// Creates an error for an access, at `accessed_at`, to a value that has been disposed.
+    pub fn disposed(
+        defined_at: Option<&'static Location<'static>>,
+        accessed_at: &'static Location<'static>,
+    ) -> Self {
+        Self::new(ReactiveErrorKind::Disposed, defined_at, accessed_at)
+    }
+
+    /// Creates an error of the given kind for an access, at `accessed_at`, to a value.
+    pub fn new(
+        kind: ReactiveErrorKind,
+        defined_at: Option<&'static Location<'static>>,
+        accessed_at: &'static Location<'static>,
+    ) -> Self {
+        Self {
+            kind,
+            defined_at,
+            accessed_at,
+            owner: Owner::current().map(|owner| owner.debug_id()),
+        }
+    }
+
+    /// Creates an error for an access that has just failed on this thread.
+    ///
+    /// The value is assumed to have been disposed, unless the access recorded another reason.
+    #[doc(hidden)]
+    pub fn failed_access(
+        defined_at: Option<&'static Location<'static>>,
+        accessed_at: &'static Location<'static>,
+    ) -> Self {
+        let kind = ACCESS_FAILURE.take().unwrap_or(ReactiveErrorKind::Disposed);
+        Self::new(kind, defined_at, accessed_at)
+    }
+
+    /// Why the value could not be accessed.
+    pub fn kind(&self) -> ReactiveErrorKind {
+        self.kind
+    }
+
+    /// The location at which the reactive value was defined.
+    ///
+    /// This is usually `None` in release builds.
+    pub fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.defined_at
+    }
+
+    /// The location at which the value was accessed.
+    pub fn accessed_at(&self) -> &'static Location<'static> {
+        self.accessed_at
+    }
+
+    /// The [`debug_id`](Owner::debug_id) of the owner that was active when the value was
+    /// accessed, if any.
+    pub fn owner(&self) -> Option<usize> {
+        self.owner
+    }
+}
+
+impl Display for ReactiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            ReactiveErrorKind::Disposed => {
+                f.write_str(&crate::traits::panic_getting_disposed_signal(
+                    self.defined_at,
+                    self.accessed_at,
+                ))
+            }
+            kind => {
+                let reason = if kind == ReactiveErrorKind::Poisoned {
+                    "its lock was poisoned by a panic"
+                } else {
+                    "it is already locked"
+                };
+                write!(
+                    f,
+                    "At {}, you tried to access a reactive value",
+                    self.accessed_at
+                )?;
+                if let Some(defined_at) = self.defined_at {
+                    write!(f, " which was defined at {defined_at}")?;
+                }
+                write!(f, ", but {reason}.")
+            }
+        }
+    }
+}
+
+impl Error for ReactiveError {}
+
+/// Sets a global hook that is called whenever a reactive value cannot be accessed by a method that
+/// cannot return an error, replacing any hook that was set before.
+///
+/// This is intended for recording these accesses, for example in production telemetry. Accesses
+/// through the `checked_` methods are not reported, because the caller handles the error.
+pub fn set_disposed_access_hook(
+    hook: impl Fn(&ReactiveError) + Send + Sync + 'static,
+) {
+    *DISPOSED_ACCESS_HOOK.write().or_poisoned() = Some(Arc::new(hook));
+}
+
+/// Removes the hook set with [`set_disposed_access_hook`].
+pub fn clear_disposed_access_hook() {
+    DISPOSED_ACCESS_HOOK.write().or_poisoned().take();
+}
+
+/// Records why a reactive value could not be accessed, so that the error for the failed access
+/// can describe it.
+pub(crate) fn record_access_failure(kind: ReactiveErrorKind) {
+    ACCESS_FAILURE.set(Some(kind));
+}
+
+/// Returns the guard for a lock, or records that the lock has been poisoned.
+pub(crate) fn unpoisoned<G>(result: std::sync::LockResult<G>) -> Option<G> {
+    match result {
+        Ok(guard) => Some(guard),
+        Err(_) => {
+            record_access_failure(ReactiveErrorKind::Poisoned);
+            None
+        }
+    }
+}
+
+/// Forgets any access failure that has not been reported, before a new access is attempted.
+pub(crate) fn clear_access_failure() {
+    ACCESS_FAILURE.set(None);
+}
+
+#[doc(hidden)]
+pub fn report_disposed_access(error: &ReactiveError) {
+    // the lock is not held while calling the hook, in case the hook accesses reactive values
+    let hook = DISPOSED_ACCESS_HOOK.read().or_poisoned().clone();
+    if let Some(hook) = hook {
+        hook(error);
+    }
+}
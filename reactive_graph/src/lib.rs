@@ -75,15 +75,23 @@
 use std::{fmt::Arguments, future::Future};
 
 pub mod actions;
+#[cfg(feature = "broadcast")]
+pub mod broadcast;
 pub(crate) mod channel;
+pub mod collections;
 pub mod computed;
 pub mod diagnostics;
 pub mod effect;
+pub mod error;
 pub mod graph;
 pub mod owner;
+#[cfg(feature = "persistent")]
+pub mod persistent;
 #[cfg(feature = "serde")]
 mod serde;
 pub mod signal;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 mod trait_options;
 pub mod traits;
 pub mod transition;
@@ -25,6 +25,10 @@ pub use arena::sandboxed::Sandboxed;
 #[cfg(feature = "sandboxed-arenas")]
 use arena::ArenaMap;
 use arena::NodeId;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub use arena::{check_leaks, leak_report, LeakReason, LeakReport, LeakedNode};
+#[cfg(feature = "sandboxed-arenas")]
+pub use arena::{ArenaLimitExceeded, ArenaStats};
 pub use arena_item::*;
 pub use context::*;
 pub use storage::*;
@@ -104,6 +108,29 @@ impl Owner {
         ancestors
     }
 
+    /// Returns the outermost ancestor of this owner that is still alive, or this owner if it has
+    /// no parent.
+    pub(crate) fn root(&self) -> Owner {
+        let mut root = Arc::clone(&self.inner);
+        loop {
+            let parent = root
+                .read()
+                .or_poisoned()
+                .parent
+                .as_ref()
+                .and_then(|n| n.upgrade());
+            match parent {
+                Some(parent) => root = parent,
+                None => break,
+            }
+        }
+        Owner {
+            inner: root,
+            #[cfg(feature = "hydration")]
+            shared_context: self.shared_context.clone(),
+        }
+    }
+
     /// Creates a new `Owner` and registers it as a child of the current `Owner`, if there is one.
     pub fn new() -> Self {
         #[cfg(not(feature = "hydration"))]
@@ -276,6 +303,78 @@ impl Owner {
         })
     }
 
+    /// Returns the number of items in this owner's arena, and the memory they use.
+    ///
+    /// The arena is shared by this owner, its ancestors, and its descendants, so calling this on
+    /// the root owner of a request describes the whole request.
+    #[cfg(feature = "sandboxed-arenas")]
+    pub fn arena_stats(&self) -> ArenaStats {
+        self.inner
+            .read()
+            .or_poisoned()
+            .arena
+            .read()
+            .or_poisoned()
+            .stats()
+    }
+
+    /// Sets the maximum number of items that can be stored in this owner's arena, or removes the
+    /// limit if `None`.
+    ///
+    /// Once the limit is reached, creating another reactive value fails with an
+    /// [`ArenaLimitExceeded`] error, which is returned by [`Owner::try_with`]. This fails the
+    /// current render rather than exhausting memory.
+    #[cfg(feature = "sandboxed-arenas")]
+    pub fn set_arena_limit(&self, limit: Option<usize>) {
+        self.inner
+            .read()
+            .or_poisoned()
+            .arena
+            .write()
+            .or_poisoned()
+            .set_limit(limit);
+    }
+
+    /// Returns the first error caused by reaching the limit set with
+    /// [`Owner::set_arena_limit`], if any, since the limit was set.
+    ///
+    /// This includes work that was abandoned outside of [`Owner::try_with`], such as a task
+    /// that was spawned while rendering.
+    #[cfg(feature = "sandboxed-arenas")]
+    pub fn arena_limit_exceeded(&self) -> Option<ArenaLimitExceeded> {
+        self.inner
+            .read()
+            .or_poisoned()
+            .arena
+            .read()
+            .or_poisoned()
+            .limit_exceeded()
+    }
+
+    /// Runs the given function with this as the current `Owner`, returning an error if it
+    /// reaches the limit set with [`Owner::set_arena_limit`].
+    ///
+    /// Any other panic is resumed.
+    #[cfg(feature = "sandboxed-arenas")]
+    pub fn try_with<T>(
+        &self,
+        fun: impl FnOnce() -> T,
+    ) -> Result<T, ArenaLimitExceeded> {
+        let prev = Owner::current();
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                self.with(fun)
+            }));
+        result.or_else(|payload| {
+            // `with` does not restore the previous owner if `fun` unwinds
+            OWNER.with_borrow_mut(|owner| *owner = prev);
+            match payload.downcast::<ArenaLimitExceeded>() {
+                Ok(error) => Err(*error),
+                Err(payload) => std::panic::resume_unwind(payload),
+            }
+        })
+    }
+
     /// Returns the current [`SharedContext`], if any.
     #[cfg(feature = "hydration")]
     pub fn current_shared_context(
@@ -357,7 +456,7 @@ pub fn on_cleanup(fun: impl FnOnce() + Send + Sync + 'static) {
 #[derive(Default)]
 pub(crate) struct OwnerInner {
     pub parent: Option<Weak<RwLock<OwnerInner>>>,
-    nodes: Vec<NodeId>,
+    pub(crate) nodes: Vec<NodeId>,
     pub contexts: FxHashMap<TypeId, Box<dyn Any + Send + Sync>>,
     pub cleanups: Vec<Box<dyn FnOnce() + Send + Sync>>,
     pub children: Vec<Weak<RwLock<OwnerInner>>>,
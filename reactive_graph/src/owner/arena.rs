@@ -1,6 +1,12 @@
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+pub use leaks::*;
 use or_poisoned::OrPoisoned;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use slotmap::SecondaryMap;
 use slotmap::{new_key_type, SlotMap};
 #[cfg(feature = "sandboxed-arenas")]
+pub use stats::*;
+#[cfg(feature = "sandboxed-arenas")]
 use std::cell::RefCell;
 #[cfg(not(feature = "sandboxed-arenas"))]
 use std::sync::OnceLock;
@@ -19,7 +25,53 @@ new_key_type! {
 
 pub struct Arena;
 
-pub type ArenaMap = SlotMap<NodeId, Box<dyn Any + Send + Sync>>;
+/// The storage for all arena-allocated values.
+///
+/// In debug mode, this also records where each item was created and which owner it belongs to,
+/// so that leaked items can be reported with [`leak_report`].
+///
+/// With the `sandboxed-arenas` feature, each arena also counts the items it holds, so that the
+/// usage of a single request can be read with [`Owner::arena_stats`](crate::owner::Owner::arena_stats).
+#[derive(Default)]
+pub struct ArenaMap {
+    items: SlotMap<NodeId, Box<dyn Any + Send + Sync>>,
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    info: SecondaryMap<NodeId, leaks::NodeInfo>,
+    #[cfg(feature = "sandboxed-arenas")]
+    accounting: stats::Accounting,
+}
+
+impl ArenaMap {
+    pub fn insert(&mut self, value: Box<dyn Any + Send + Sync>) -> NodeId {
+        self.items.insert(value)
+    }
+
+    pub fn remove(
+        &mut self,
+        node: NodeId,
+    ) -> Option<Box<dyn Any + Send + Sync>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        self.info.remove(node);
+        #[cfg(feature = "sandboxed-arenas")]
+        self.accounting.release(node);
+        self.items.remove(node)
+    }
+
+    pub fn get(&self, node: NodeId) -> Option<&(dyn Any + Send + Sync)> {
+        self.items.get(node).map(|n| &**n)
+    }
+
+    pub fn get_mut(
+        &mut self,
+        node: NodeId,
+    ) -> Option<&mut (dyn Any + Send + Sync)> {
+        self.items.get_mut(node).map(|n| &mut **n)
+    }
+
+    pub fn contains_key(&self, node: NodeId) -> bool {
+        self.items.contains_key(node)
+    }
+}
 
 #[cfg(not(feature = "sandboxed-arenas"))]
 static MAP: OnceLock<RwLock<ArenaMap>> = OnceLock::new();
@@ -168,3 +220,443 @@ pub mod sandboxed {
         }
     }
 }
+
+#[cfg(feature = "sandboxed-arenas")]
+mod stats {
+    use super::{ArenaMap, NodeId};
+    use rustc_hash::FxHashMap;
+    use slotmap::SecondaryMap;
+    use std::{error::Error, fmt::Display};
+
+    #[derive(Default)]
+    pub(crate) struct Accounting {
+        nodes: SecondaryMap<NodeId, (&'static str, usize)>,
+        by_type: FxHashMap<&'static str, usize>,
+        bytes: usize,
+        peak: usize,
+        created: usize,
+        limit: Option<usize>,
+        exceeded: Option<ArenaLimitExceeded>,
+    }
+
+    impl Accounting {
+        pub(crate) fn release(&mut self, node: NodeId) {
+            if let Some((type_name, size)) = self.nodes.remove(node) {
+                self.bytes -= size;
+                if let Some(count) = self.by_type.get_mut(type_name) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.by_type.remove(type_name);
+                    }
+                }
+            }
+        }
+    }
+
+    impl ArenaMap {
+        /// Records the type and size of a newly-inserted item.
+        pub(crate) fn account(
+            &mut self,
+            node: NodeId,
+            type_name: &'static str,
+        ) {
+            let Some(item) = self.items.get(node) else {
+                return;
+            };
+            let size = std::mem::size_of_val(&**item);
+            let accounting = &mut self.accounting;
+            accounting.nodes.insert(node, (type_name, size));
+            *accounting.by_type.entry(type_name).or_default() += 1;
+            accounting.bytes += size;
+            accounting.created += 1;
+            accounting.peak = accounting.peak.max(self.items.len());
+        }
+
+        /// Returns an error if adding another item would exceed this arena's limit.
+        ///
+        /// The first such error is kept, so that it can still be found once the work that
+        /// failed has been abandoned.
+        pub(crate) fn check_limit(
+            &mut self,
+            type_name: &'static str,
+        ) -> Result<(), ArenaLimitExceeded> {
+            match self.accounting.limit {
+                Some(limit) if self.items.len() >= limit => {
+                    let error = ArenaLimitExceeded { limit, type_name };
+                    self.accounting.exceeded.get_or_insert(error);
+                    Err(error)
+                }
+                _ => Ok(()),
+            }
+        }
+
+        pub(crate) fn set_limit(&mut self, limit: Option<usize>) {
+            self.accounting.limit = limit;
+            self.accounting.exceeded = None;
+        }
+
+        pub(crate) fn limit_exceeded(&self) -> Option<ArenaLimitExceeded> {
+            self.accounting.exceeded
+        }
+
+        pub(crate) fn stats(&self) -> ArenaStats {
+            let accounting = &self.accounting;
+            let mut by_type = accounting
+                .by_type
+                .iter()
+                .map(|(type_name, count)| (*type_name, *count))
+                .collect::<Vec<_>>();
+            by_type.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+            ArenaStats {
+                live: self.items.len(),
+                peak: accounting.peak,
+                created: accounting.created,
+                bytes: accounting.bytes,
+                limit: accounting.limit,
+                by_type,
+            }
+        }
+    }
+
+    impl Drop for ArenaMap {
+        fn drop(&mut self) {
+            // every owner that shared this arena is gone, so anything left was never disposed
+            //
+            // this is summarized in a single line, as arenas that were only used on one thread,
+            // outside of any request, often still hold the values of their root owner
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            if !self.items.is_empty() {
+                const TYPES_SHOWN: usize = 3;
+
+                let stats = self.stats();
+                let mut types = stats
+                    .by_type
+                    .iter()
+                    .take(TYPES_SHOWN)
+                    .map(|(type_name, count)| format!("{type_name} ({count})"))
+                    .collect::<Vec<_>>();
+                if stats.by_type.len() > TYPES_SHOWN {
+                    types.push("...".to_string());
+                }
+                crate::log_warning(format_args!(
+                    "{} reactive value(s) were still in a sandboxed arena \
+                     when it was dropped: {}",
+                    stats.live,
+                    types.join(", ")
+                ));
+            }
+        }
+    }
+
+    /// A snapshot of the items held by a sandboxed arena, returned by
+    /// [`Owner::arena_stats`](crate::owner::Owner::arena_stats).
+    ///
+    /// Sizes are shallow: they count the size of each stored value, but not any heap memory it
+    /// owns.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct ArenaStats {
+        /// The number of items currently in the arena.
+        pub live: usize,
+        /// The largest number of items that have been in the arena at once.
+        pub peak: usize,
+        /// The total number of items that have been added to the arena.
+        pub created: usize,
+        /// The total size, in bytes, of the items currently in the arena.
+        pub bytes: usize,
+        /// The maximum number of items allowed in the arena, if any.
+        pub limit: Option<usize>,
+        /// The number of live items of each type, from most to least common.
+        pub by_type: Vec<(&'static str, usize)>,
+    }
+
+    /// The error with which a render fails when the current arena already holds as many items
+    /// as the limit set with [`Owner::set_arena_limit`](crate::owner::Owner::set_arena_limit).
+    ///
+    /// Creating another reactive value unwinds with this error, which is returned by
+    /// [`Owner::try_with`](crate::owner::Owner::try_with). Work that was running elsewhere (for
+    /// example, in a spawned task) is abandoned instead, and the error can be read afterwards
+    /// with [`Owner::arena_limit_exceeded`](crate::owner::Owner::arena_limit_exceeded).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ArenaLimitExceeded {
+        /// The maximum number of items allowed in the arena.
+        pub limit: usize,
+        /// The type of the value that could not be stored.
+        pub type_name: &'static str,
+    }
+
+    impl Display for ArenaLimitExceeded {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "could not store a {} because the reactive arena already \
+                 holds its limit of {} items",
+                self.type_name, self.limit
+            )
+        }
+    }
+
+    impl Error for ArenaLimitExceeded {}
+}
+
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+mod leaks {
+    use super::{ArenaMap, NodeId};
+    use crate::owner::{OwnerInner, OWNER};
+    use or_poisoned::OrPoisoned;
+    use std::{
+        fmt::Display,
+        panic::Location,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            RwLock, Weak,
+        },
+        thread::ThreadId,
+    };
+
+    static NEXT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+    pub(crate) struct NodeInfo {
+        defined_at: &'static Location<'static>,
+        type_name: &'static str,
+        owner: Option<Weak<RwLock<OwnerInner>>>,
+        thread: ThreadId,
+        sequence: u64,
+    }
+
+    impl ArenaMap {
+        /// Records where the item was created, and the current owner, if any.
+        pub(crate) fn track(
+            &mut self,
+            node: NodeId,
+            defined_at: &'static Location<'static>,
+            type_name: &'static str,
+        ) {
+            let owner = OWNER.with_borrow(|owner| {
+                owner
+                    .as_ref()
+                    .map(|owner| std::sync::Arc::downgrade(&owner.inner))
+            });
+            self.info.insert(
+                node,
+                NodeInfo {
+                    defined_at,
+                    type_name,
+                    owner,
+                    thread: std::thread::current().id(),
+                    sequence: NEXT_SEQUENCE.fetch_add(1, Ordering::Relaxed),
+                },
+            );
+        }
+
+        pub(super) fn leaks(
+            &self,
+            filter: impl Fn(&NodeInfo) -> bool,
+        ) -> Vec<LeakedNode> {
+            self.info
+                .iter()
+                .filter(|(_, info)| filter(info))
+                .filter_map(|(node, info)| {
+                    let reason = match &info.owner {
+                        None => LeakReason::Unowned,
+                        Some(owner) => match owner.upgrade() {
+                            // the owner has been dropped, but this item was not removed
+                            None => LeakReason::OutlivedOwner,
+                            Some(owner) => {
+                                // the owner is still alive, and still owns this item
+                                if owner
+                                    .read()
+                                    .or_poisoned()
+                                    .nodes
+                                    .contains(&node)
+                                {
+                                    return None;
+                                }
+                                // the owner has been cleaned up, but this item was not removed
+                                LeakReason::OutlivedOwner
+                            }
+                        },
+                    };
+                    Some(LeakedNode {
+                        defined_at: info.defined_at,
+                        type_name: info.type_name,
+                        reason,
+                    })
+                })
+                .collect()
+        }
+    }
+
+    /// Why an arena-allocated item is considered to have leaked.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum LeakReason {
+        /// The item was created without a reactive [`Owner`](crate::owner::Owner), so it will never
+        /// be disposed.
+        Unowned,
+        /// The item is still in the arena, even though the owner it was created under has been
+        /// cleaned up or dropped.
+        OutlivedOwner,
+    }
+
+    /// An arena-allocated item that has leaked.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct LeakedNode {
+        /// Where the item was created.
+        pub defined_at: &'static Location<'static>,
+        /// The type of the value stored in the arena.
+        pub type_name: &'static str,
+        /// Why the item is considered to have leaked.
+        pub reason: LeakReason,
+    }
+
+    impl Display for LeakedNode {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let reason = match self.reason {
+                LeakReason::Unowned => "was created outside any reactive Owner",
+                LeakReason::OutlivedOwner => "outlived its reactive Owner",
+            };
+            write!(
+                f,
+                "{} (defined at {}) {reason}",
+                self.type_name, self.defined_at
+            )
+        }
+    }
+
+    /// A list of arena-allocated items that have leaked, created by [`leak_report`] or
+    /// [`check_leaks`].
+    ///
+    /// This is only available in debug mode.
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct LeakReport {
+        /// The leaked items, in no particular order.
+        pub leaks: Vec<LeakedNode>,
+    }
+
+    impl LeakReport {
+        /// Returns `true` if no items have leaked.
+        pub fn is_empty(&self) -> bool {
+            self.leaks.is_empty()
+        }
+
+        /// Panics with a list of leaked items, if there are any.
+        ///
+        /// This can be used to fail a test if it leaks reactive values.
+        #[track_caller]
+        pub fn assert_no_leaks(&self) {
+            if !self.is_empty() {
+                panic!("{self}");
+            }
+        }
+    }
+
+    impl Display for LeakReport {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            writeln!(f, "{} reactive value(s) leaked:", self.leaks.len())?;
+            for leak in &self.leaks {
+                writeln!(f, "  - {leak}")?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Reports every item in the current arena that was either created outside any reactive
+    /// [`Owner`](crate::owner::Owner), or is still in the arena even though its owner has been
+    /// cleaned up.
+    ///
+    /// This is only available in debug mode.
+    ///
+    /// With the `sandboxed-arenas` feature, the report is empty if no arena is active.
+    pub fn leak_report() -> LeakReport {
+        LeakReport {
+            leaks: leaks_in(&current_arena(), |_| true),
+        }
+    }
+
+    /// The arena that is currently active, if any.
+    #[cfg(feature = "sandboxed-arenas")]
+    type CurrentArena = Option<std::sync::Arc<RwLock<ArenaMap>>>;
+    #[cfg(not(feature = "sandboxed-arenas"))]
+    type CurrentArena = ();
+
+    fn current_arena() -> CurrentArena {
+        #[cfg(feature = "sandboxed-arenas")]
+        {
+            super::MAP
+                .with_borrow(|arena| arena.as_ref().and_then(Weak::upgrade))
+        }
+    }
+
+    fn leaks_in(
+        arena: &CurrentArena,
+        filter: impl Fn(&NodeInfo) -> bool,
+    ) -> Vec<LeakedNode> {
+        #[cfg(feature = "sandboxed-arenas")]
+        {
+            arena
+                .as_ref()
+                .map(|arena| arena.read().or_poisoned().leaks(filter))
+                .unwrap_or_default()
+        }
+        #[cfg(not(feature = "sandboxed-arenas"))]
+        {
+            _ = arena;
+            super::Arena::with(|arena| arena.leaks(filter))
+        }
+    }
+
+    /// Runs the given function, and reports any items that were created on this thread while it
+    /// was running and have leaked by the time it returns.
+    ///
+    /// Unlike [`leak_report`], this ignores items created by other threads or before the
+    /// function was called, which makes it suitable for tests that run in parallel.
+    ///
+    /// This is only available in debug mode.
+    /// ```
+    /// # use reactive_graph::owner::{check_leaks, Owner};
+    /// # use reactive_graph::signal::RwSignal;
+    /// let (_, report) = check_leaks(|| {
+    ///     let owner = Owner::new();
+    ///     owner.with(|| RwSignal::new(0));
+    ///     drop(owner);
+    /// });
+    /// report.assert_no_leaks();
+    ///
+    /// # // with `sandboxed-arenas`, an arena must be active to create values without an owner
+    /// # let root = Owner::new(); root.set(); root.clone().unset();
+    /// let (_, report) = check_leaks(|| RwSignal::new(0));
+    /// assert_eq!(report.leaks.len(), 1);
+    /// ```
+    ///
+    /// With the `sandboxed-arenas` feature, this checks the arenas that were active before and
+    /// after `fun` ran. Items in an arena that `fun` created and dropped again are gone, so they
+    /// are not reported.
+    pub fn check_leaks<T>(fun: impl FnOnce() -> T) -> (T, LeakReport) {
+        let thread = std::thread::current().id();
+        // hold on to the arena, in case `fun` drops the owner that created it
+        // (without `sandboxed-arenas`, there is only one arena and this is `()`)
+        #[allow(clippy::let_unit_value)]
+        let arena = current_arena();
+        let start = NEXT_SEQUENCE.load(Ordering::Relaxed);
+        let value = fun();
+        let end = NEXT_SEQUENCE.load(Ordering::Relaxed);
+        let filter = |info: &NodeInfo| {
+            info.thread == thread
+                && info.sequence >= start
+                && info.sequence < end
+        };
+        #[allow(unused_mut)]
+        let mut leaks = leaks_in(&arena, filter);
+        #[cfg(feature = "sandboxed-arenas")]
+        {
+            let after = current_arena();
+            let same = match (&arena, &after) {
+                (Some(a), Some(b)) => std::sync::Arc::ptr_eq(a, b),
+                _ => false,
+            };
+            if !same {
+                leaks.extend(leaks_in(&after, filter));
+            }
+        }
+        (value, LeakReport { leaks })
+    }
+}
@@ -2,7 +2,10 @@ use super::{
     arena::{Arena, NodeId},
     LocalStorage, Storage, SyncStorage, OWNER,
 };
-use crate::traits::{Dispose, IntoInner, IsDisposed};
+use crate::{
+    error::{record_access_failure, ReactiveErrorKind},
+    traits::{Dispose, IntoInner, IsDisposed},
+};
 use send_wrapper::SendWrapper;
 use std::{any::Any, hash::Hash, marker::PhantomData};
 
@@ -45,11 +48,29 @@ where
     /// Stores the given value in the arena allocator.
     #[track_caller]
     pub fn new_with_storage(value: T) -> Self {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        let defined_at = std::panic::Location::caller();
+        // the lock is released before unwinding, so that the arena can still be cleaned up
+        //
+        // this unwinds without running the panic hook: the error is not a bug, and is returned
+        // by `Owner::try_with`
+        #[cfg(feature = "sandboxed-arenas")]
+        if let Err(e) = Arena::with_mut(|arena| {
+            arena.check_limit(std::any::type_name::<T>())
+        }) {
+            std::panic::resume_unwind(Box::new(e));
+        }
         let node = {
             Arena::with_mut(|arena| {
-                arena.insert(
-                    Box::new(S::wrap(value)) as Box<dyn Any + Send + Sync>
-                )
+                let node =
+                    arena
+                        .insert(Box::new(S::wrap(value))
+                            as Box<dyn Any + Send + Sync>);
+                #[cfg(any(debug_assertions, leptos_debuginfo))]
+                arena.track(node, defined_at, std::any::type_name::<T>());
+                #[cfg(feature = "sandboxed-arenas")]
+                arena.account(node, std::any::type_name::<T>());
+                node
             })
         };
         OWNER.with(|o| {
@@ -102,7 +123,7 @@ impl<T, S: Storage<T>> ArenaItem<T, S> {
     /// Applies a function to a reference to the stored value and returns the result, or `None` if it has already been disposed.
     #[track_caller]
     pub fn try_with_value<U>(&self, fun: impl FnOnce(&T) -> U) -> Option<U> {
-        S::try_with(self.node, fun)
+        disposed_if_none(S::try_with(self.node, fun))
     }
 
     /// Applies a function to a mutable reference to the stored value and returns the result, or `None` if it has already been disposed.
@@ -111,7 +132,7 @@ impl<T, S: Storage<T>> ArenaItem<T, S> {
         &self,
         fun: impl FnOnce(&mut T) -> U,
     ) -> Option<U> {
-        S::try_with_mut(self.node, fun)
+        disposed_if_none(S::try_with_mut(self.node, fun))
     }
 }
 
@@ -119,8 +140,15 @@ impl<T: Clone, S: Storage<T>> ArenaItem<T, S> {
     /// Returns a clone of the stored value, or `None` if it has already been disposed.
     #[track_caller]
     pub fn try_get_value(&self) -> Option<T> {
-        S::try_with(self.node, Clone::clone)
+        disposed_if_none(S::try_with(self.node, Clone::clone))
+    }
+}
+
+fn disposed_if_none<U>(value: Option<U>) -> Option<U> {
+    if value.is_none() {
+        record_access_failure(ReactiveErrorKind::Disposed);
     }
+    value
 }
 
 impl<T, S> IsDisposed for ArenaItem<T, S> {
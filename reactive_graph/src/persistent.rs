@@ -0,0 +1,587 @@
+//! Signals whose values are persisted to, and restored from, a storage backend.
+//!
+//! An [`ArcPersistentSignal`] behaves like an [`ArcRwSignal`], but loads its initial value from a
+//! [`StorageBackend`] and writes its value back to that backend whenever it changes. Values are
+//! encoded as strings using one of the [`codee`] string codecs.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::persistent::*;
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use codee::string::FromToStringCodec;
+//!
+//! let storage = MemoryStorage::default();
+//!
+//! let count = ArcPersistentSignal::<i32, FromToStringCodec, _>::new(
+//!     "count",
+//!     storage.clone(),
+//!     || 0,
+//! );
+//! count.set(42);
+//! assert_eq!(storage.get_item("count").as_deref(), Some("42"));
+//!
+//! // a new signal with the same key picks up the stored value
+//! let restored = ArcPersistentSignal::<i32, FromToStringCodec, _>::new(
+//!     "count",
+//!     storage,
+//!     || 0,
+//! );
+//! assert_eq!(restored.get_untracked(), 42);
+//! ```
+//!
+//! ## Hydration
+//! If the signal is created while hydrating, it starts with the initial value (which is what
+//! the server rendered) and only reads the stored value after hydration, to avoid hydration
+//! mismatches.
+
+use crate::{
+    graph::SubscriberSet,
+    signal::{
+        guards::{Plain, ReadGuard, UntrackedWriteGuard, WriteGuard},
+        subscriber_traits::AsSubscriberSet,
+        ArcRwSignal,
+    },
+    traits::{
+        DefinedAt, IsDisposed, Notify, ReadUntracked, Set, UntrackableGuard,
+        Write,
+    },
+};
+use codee::{Decoder, Encoder};
+use or_poisoned::OrPoisoned;
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    hash::Hash,
+    marker::PhantomData,
+    panic::Location,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
+    time::Duration,
+};
+
+/// A key-value store in which an [`ArcPersistentSignal`] can save its encoded value.
+pub trait StorageBackend: Send + Sync + 'static {
+    /// Returns the value stored for the given key, if any.
+    fn get_item(&self, key: &str) -> Option<String>;
+
+    /// Stores the value for the given key.
+    fn set_item(&self, key: &str, value: &str);
+
+    /// Removes the value stored for the given key.
+    fn remove_item(&self, key: &str);
+}
+
+/// An in-memory [`StorageBackend`].
+///
+/// Clones of this share the same underlying map, which makes it useful for testing.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryStorage(Arc<RwLock<HashMap<String, String>>>);
+
+impl StorageBackend for MemoryStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        self.0.read().or_poisoned().get(key).cloned()
+    }
+
+    fn set_item(&self, key: &str, value: &str) {
+        self.0
+            .write()
+            .or_poisoned()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    fn remove_item(&self, key: &str) {
+        self.0.write().or_poisoned().remove(key);
+    }
+}
+
+/// A [`StorageBackend`] that stores each key as a file in a directory.
+///
+/// The key is used as the file name, so it should be a valid file name on the target platform.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    dir: PathBuf,
+}
+
+impl FileStorage {
+    /// Creates a backend that stores values in the given directory, which will be created if it
+    /// does not exist.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl StorageBackend for FileStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.dir.join(key)).ok()
+    }
+
+    fn set_item(&self, key: &str, value: &str) {
+        let res = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(self.dir.join(key), value));
+        if let Err(e) = res {
+            crate::log_warning(format_args!(
+                "could not write {key:?} to {:?}: {e}",
+                self.dir
+            ));
+        }
+    }
+
+    fn remove_item(&self, key: &str) {
+        _ = std::fs::remove_file(self.dir.join(key));
+    }
+}
+
+/// A [`StorageBackend`] that uses the browser's `localStorage` or `sessionStorage`.
+///
+/// Outside the browser, this does not store anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebStorage {
+    #[allow(dead_code)] // only used in the browser
+    session: bool,
+}
+
+impl WebStorage {
+    /// Uses `window.localStorage`.
+    pub fn local() -> Self {
+        Self { session: false }
+    }
+
+    /// Uses `window.sessionStorage`.
+    pub fn session() -> Self {
+        Self { session: true }
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    fn storage(&self) -> Option<web_sys::Storage> {
+        let window = web_sys::window()?;
+        if self.session {
+            window.session_storage().ok().flatten()
+        } else {
+            window.local_storage().ok().flatten()
+        }
+    }
+}
+
+impl StorageBackend for WebStorage {
+    #[allow(unused_variables)]
+    fn get_item(&self, key: &str) -> Option<String> {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        {
+            self.storage()?.get_item(key).ok().flatten()
+        }
+        #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+        {
+            None
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn set_item(&self, key: &str, value: &str) {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        if let Some(storage) = self.storage() {
+            _ = storage.set_item(key, value);
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn remove_item(&self, key: &str) {
+        #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+        if let Some(storage) = self.storage() {
+            _ = storage.remove_item(key);
+        }
+    }
+}
+
+/// A reference-counted signal that loads its value from a [`StorageBackend`], and writes its
+/// value back to that backend whenever it changes.
+///
+/// The value is encoded and decoded with the string codec `C`, for example
+/// [`JsonSerdeCodec`](codee::string::JsonSerdeCodec) or
+/// [`FromToStringCodec`](codee::string::FromToStringCodec).
+///
+/// By default, every change is written through to storage immediately. Use
+/// [`with_debounce`](ArcPersistentSignal::with_debounce) to only write once the value has stopped
+/// changing for some duration.
+pub struct ArcPersistentSignal<T, C, B> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    signal: ArcRwSignal<T>,
+    key: Arc<str>,
+    backend: Arc<B>,
+    debounce: Option<Duration>,
+    generation: Arc<AtomicUsize>,
+    codec: PhantomData<fn() -> C>,
+}
+
+impl<T, C, B> Clone for ArcPersistentSignal<T, C, B> {
+    #[track_caller]
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            signal: self.signal.clone(),
+            key: Arc::clone(&self.key),
+            backend: Arc::clone(&self.backend),
+            debounce: self.debounce,
+            generation: Arc::clone(&self.generation),
+            codec: PhantomData,
+        }
+    }
+}
+
+impl<T, C, B> Debug for ArcPersistentSignal<T, C, B> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArcPersistentSignal")
+            .field("type", &std::any::type_name::<T>())
+            .field("key", &self.key)
+            .field("value", &Arc::as_ptr(&self.signal.value))
+            .finish()
+    }
+}
+
+impl<T, C, B> PartialEq for ArcPersistentSignal<T, C, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.signal == other.signal
+    }
+}
+
+impl<T, C, B> Eq for ArcPersistentSignal<T, C, B> {}
+
+impl<T, C, B> Hash for ArcPersistentSignal<T, C, B> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.signal.hash(state);
+    }
+}
+
+impl<T, C, B> ArcPersistentSignal<T, C, B>
+where
+    T: Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+    <C as Encoder<T>>::Error: Debug,
+    <C as Decoder<T>>::Error: Debug,
+    B: StorageBackend,
+{
+    /// Creates a new signal, which is stored under `key` in the given backend.
+    ///
+    /// If a value has already been stored under that key, it is used as the initial value.
+    /// Otherwise, `initial` is called to create the initial value.
+    #[track_caller]
+    pub fn new(
+        key: impl Into<Arc<str>>,
+        backend: B,
+        initial: impl FnOnce() -> T,
+    ) -> Self {
+        let key = key.into();
+        let backend = Arc::new(backend);
+
+        #[cfg(feature = "hydration")]
+        let hydrating = crate::owner::Owner::current_shared_context()
+            .map(|sc| sc.during_hydration())
+            .unwrap_or(false);
+        #[cfg(not(feature = "hydration"))]
+        let hydrating = false;
+
+        let value = if hydrating {
+            initial()
+        } else {
+            Self::load(&key, &backend).unwrap_or_else(initial)
+        };
+
+        let this = Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            signal: ArcRwSignal::new(value),
+            key,
+            backend,
+            debounce: None,
+            generation: Default::default(),
+            codec: PhantomData,
+        };
+
+        // reading from storage during hydration would cause a mismatch with the server-rendered
+        // HTML, so we wait a tick until hydration is complete
+        if hydrating {
+            let this = this.clone();
+            crate::spawn(async move {
+                any_spawner::Executor::tick().await;
+                this.reload();
+            });
+        }
+
+        this
+    }
+
+    /// Only writes the value to storage once it has not changed for the given duration, rather
+    /// than on every change.
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = Some(debounce);
+        self
+    }
+
+    /// The key under which this signal is stored.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Re-reads the stored value, and updates the signal if a value is found.
+    ///
+    /// This does not write the value back to storage.
+    pub fn reload(&self) {
+        if let Some(value) = Self::load(&self.key, &self.backend) {
+            self.signal.set(value);
+        }
+    }
+
+    /// Immediately writes the current value to storage, cancelling any pending debounced write.
+    pub fn flush(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.save();
+    }
+
+    /// Removes the stored value from the backend, without changing the value of the signal.
+    pub fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.backend.remove_item(&self.key);
+    }
+
+    fn load(key: &str, backend: &B) -> Option<T> {
+        let stored = backend.get_item(key)?;
+        match C::decode(&stored) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                crate::log_warning(format_args!(
+                    "could not decode persisted value for {key:?}: {e:?}"
+                ));
+                None
+            }
+        }
+    }
+
+    fn save(&self) {
+        let encoded = C::encode(&self.signal.value.read().or_poisoned());
+        match encoded {
+            Ok(encoded) => self.backend.set_item(&self.key, &encoded),
+            Err(e) => crate::log_warning(format_args!(
+                "could not encode persisted value for {:?}: {e:?}",
+                self.key
+            )),
+        }
+    }
+
+    fn schedule_save(&self) {
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        match self.debounce {
+            None => self.save(),
+            Some(debounce) => {
+                let this = self.clone();
+                crate::spawn(async move {
+                    sleep(debounce).await;
+                    // only save if this is still the most recent change
+                    if this.generation.load(Ordering::Relaxed) == generation {
+                        this.save();
+                    }
+                });
+            }
+        }
+    }
+}
+
+impl<T, C, B> DefinedAt for ArcPersistentSignal<T, C, B> {
+    #[inline(always)]
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<T, C, B> IsDisposed for ArcPersistentSignal<T, C, B> {
+    #[inline(always)]
+    fn is_disposed(&self) -> bool {
+        false
+    }
+}
+
+impl<T, C, B> AsSubscriberSet for ArcPersistentSignal<T, C, B> {
+    type Output = Arc<RwLock<SubscriberSet>>;
+
+    #[inline(always)]
+    fn as_subscriber_set(&self) -> Option<Self::Output> {
+        self.signal.as_subscriber_set()
+    }
+}
+
+impl<T: 'static, C, B> ReadUntracked for ArcPersistentSignal<T, C, B> {
+    type Value = ReadGuard<T, Plain<T>>;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.signal.try_read_untracked()
+    }
+}
+
+impl<T, C, B> Notify for ArcPersistentSignal<T, C, B>
+where
+    T: Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+    <C as Encoder<T>>::Error: Debug,
+    <C as Decoder<T>>::Error: Debug,
+    B: StorageBackend,
+{
+    fn notify(&self) {
+        self.signal.notify();
+        self.schedule_save();
+    }
+}
+
+impl<T, C, B> Write for ArcPersistentSignal<T, C, B>
+where
+    T: Send + Sync + 'static,
+    C: Encoder<T, Encoded = String> + Decoder<T, Encoded = str>,
+    <C as Encoder<T>>::Error: Debug,
+    <C as Decoder<T>>::Error: Debug,
+    B: StorageBackend,
+{
+    type Value = T;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        crate::error::unpoisoned(self.signal.value.write())
+            .map(|guard| WriteGuard::new(self.clone(), guard))
+    }
+
+    #[allow(refining_impl_trait)]
+    fn try_write_untracked(&self) -> Option<UntrackedWriteGuard<Self::Value>> {
+        self.signal.try_write_untracked()
+    }
+}
+
+/// Resolves after the given duration, without depending on any particular async runtime.
+fn sleep(duration: Duration) -> impl std::future::Future<Output = ()> {
+    let (tx, rx) = futures::channel::oneshot::channel::<()>();
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let cb = Closure::once_into_js(move || {
+            _ = tx.send(());
+        });
+        if let Some(window) = web_sys::window() {
+            _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                cb.unchecked_ref(),
+                duration.as_millis() as i32,
+            );
+        }
+    }
+    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+    timer::schedule(std::time::Instant::now() + duration, tx);
+
+    async move {
+        _ = rx.await;
+    }
+}
+
+/// A single background thread that wakes debounced writes, so that each write does not need a
+/// thread of its own.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod timer {
+    use futures::channel::oneshot;
+    use std::{
+        cmp::Reverse,
+        collections::BinaryHeap,
+        sync::{
+            mpsc::{self, RecvTimeoutError},
+            Mutex, OnceLock,
+        },
+        time::Instant,
+    };
+
+    struct Timer {
+        deadline: Instant,
+        id: u64,
+        tx: oneshot::Sender<()>,
+    }
+
+    impl PartialEq for Timer {
+        fn eq(&self, other: &Self) -> bool {
+            (self.deadline, self.id) == (other.deadline, other.id)
+        }
+    }
+
+    impl Eq for Timer {}
+
+    impl PartialOrd for Timer {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Timer {
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            (self.deadline, self.id).cmp(&(other.deadline, other.id))
+        }
+    }
+
+    static TIMERS: OnceLock<Mutex<mpsc::Sender<Timer>>> = OnceLock::new();
+
+    pub(super) fn schedule(deadline: Instant, tx: oneshot::Sender<()>) {
+        let sender = TIMERS.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel();
+            std::thread::Builder::new()
+                .name("persistent-signal-timer".into())
+                .spawn(move || run(receiver))
+                .expect("could not spawn the persistent signal timer thread");
+            Mutex::new(sender)
+        });
+        if let Ok(sender) = sender.lock() {
+            _ = sender.send(Timer {
+                deadline,
+                id: 0,
+                tx,
+            });
+        }
+    }
+
+    fn run(receiver: mpsc::Receiver<Timer>) {
+        let mut timers = BinaryHeap::<Reverse<Timer>>::new();
+        let mut next_id = 0;
+        loop {
+            // fire everything that is due
+            let now = Instant::now();
+            while timers
+                .peek()
+                .is_some_and(|Reverse(timer)| timer.deadline <= now)
+            {
+                if let Some(Reverse(timer)) = timers.pop() {
+                    _ = timer.tx.send(());
+                }
+            }
+
+            let received = match timers.peek() {
+                Some(Reverse(next)) => receiver.recv_timeout(
+                    next.deadline.saturating_duration_since(Instant::now()),
+                ),
+                None => {
+                    receiver.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                }
+            };
+            match received {
+                Ok(mut timer) => {
+                    // keeps timers with the same deadline in the order they were scheduled
+                    next_id += 1;
+                    timer.id = next_id;
+                    timers.push(Reverse(timer));
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+}
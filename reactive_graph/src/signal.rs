@@ -8,7 +8,8 @@ mod arc_write;
 pub mod guards;
 mod read;
 mod rw;
-mod subscriber_traits;
+mod sender;
+pub(crate) mod subscriber_traits;
 mod trigger;
 mod write;
 
@@ -19,6 +20,7 @@ pub use arc_trigger::*;
 pub use arc_write::*;
 pub use read::*;
 pub use rw::*;
+pub use sender::*;
 pub use trigger::*;
 pub use write::*;
 
@@ -135,9 +135,7 @@ impl<T: 'static> Write for ArcWriteSignal<T> {
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.value
-            .write()
-            .ok()
+        crate::error::unpoisoned(self.value.write())
             .map(|guard| WriteGuard::new(self.clone(), guard))
     }
 
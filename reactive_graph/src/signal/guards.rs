@@ -2,6 +2,7 @@
 
 use crate::{
     computed::BlockingLock,
+    error::{record_access_failure, ReactiveErrorKind},
     traits::{Notify, UntrackableGuard},
 };
 use core::fmt::Debug;
@@ -11,9 +12,24 @@ use std::{
     fmt::Display,
     marker::PhantomData,
     ops::{Deref, DerefMut},
-    sync::{Arc, RwLock},
+    sync::{Arc, LockResult, RwLock},
 };
 
+/// Returns the guard if the lock was taken, or records why it could not be.
+fn lock_result<G>(result: Option<LockResult<G>>) -> Option<G> {
+    match result {
+        Some(Ok(guard)) => Some(guard),
+        Some(Err(_)) => {
+            record_access_failure(ReactiveErrorKind::Poisoned);
+            None
+        }
+        None => {
+            record_access_failure(ReactiveErrorKind::Locked);
+            None
+        }
+    }
+}
+
 /// A wrapper type for any kind of guard returned by [`Read`](crate::traits::Read).
 ///
 /// If `Inner` implements `Deref`, so does `ReadGuard<_, Inner>`.
@@ -104,8 +120,7 @@ impl<T: 'static> Debug for Plain<T> {
 impl<T: 'static> Plain<T> {
     /// Takes a reference-counted read guard on the given lock.
     pub fn try_new(inner: Arc<RwLock<T>>) -> Option<Self> {
-        ArcRwLockReadGuardian::try_take(inner)?
-            .ok()
+        lock_result(ArcRwLockReadGuardian::try_take(inner))
             .map(|guard| Plain { guard })
     }
 }
@@ -331,8 +346,7 @@ pub struct UntrackedWriteGuard<T: 'static>(ArcRwLockWriteGuardian<T>);
 impl<T: 'static> UntrackedWriteGuard<T> {
     /// Creates a write guard from the given lock.
     pub fn try_new(inner: Arc<RwLock<T>>) -> Option<Self> {
-        ArcRwLockWriteGuardian::try_take(inner)?
-            .ok()
+        lock_result(ArcRwLockWriteGuardian::try_take(inner))
             .map(UntrackedWriteGuard)
     }
 }
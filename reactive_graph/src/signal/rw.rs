@@ -1,7 +1,8 @@
 use super::{
     guards::{Plain, ReadGuard},
     subscriber_traits::AsSubscriberSet,
-    ArcReadSignal, ArcRwSignal, ArcWriteSignal, ReadSignal, WriteSignal,
+    ArcReadSignal, ArcRwSignal, ArcWriteSignal, ReadSignal, SignalSender,
+    WriteSignal,
 };
 use crate::{
     graph::{ReactiveNode, SubscriberSet},
@@ -162,6 +163,26 @@ where
     }
 }
 
+impl<T, S> RwSignal<T, S>
+where
+    T: 'static,
+    S: Storage<ArcRwSignal<T>>,
+{
+    /// Returns a [`SignalSender`] that can update this signal from any thread.
+    ///
+    /// Updates are applied on the current thread. Because the sender keeps its own reference to
+    /// the signal, updates continue to be applied, but are not observed, after this signal has
+    /// been disposed.
+    #[track_caller]
+    pub fn sender(&self) -> SignalSender<T> {
+        SignalSender::new(
+            self.inner
+                .try_get_value()
+                .unwrap_or_else(unwrap_signal!(self)),
+        )
+    }
+}
+
 impl<T, S> RwSignal<T, S>
 where
     T: 'static,
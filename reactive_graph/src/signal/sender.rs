@@ -0,0 +1,115 @@
+use super::ArcRwSignal;
+use crate::traits::{Set, Update};
+use any_spawner::{LocalSender, LoopClosed, PinnedLocalFuture};
+use std::fmt::Debug;
+
+/// A handle that can update a signal from any thread.
+///
+/// Updates are not applied on the calling thread. Instead, they are queued, and applied in order
+/// by a task that runs on the thread on which the sender was created, which is usually the thread
+/// that runs the UI. This means that the sender is `Send` whenever `T` is, even if the signal
+/// itself is pinned to one thread with [`LocalStorage`](crate::owner::LocalStorage).
+///
+/// ```rust
+/// # use reactive_graph::prelude::*;
+/// # use reactive_graph::signal::*;
+/// # use any_spawner::Executor;
+/// # _ = Executor::init_futures_executor();
+/// # let owner = reactive_graph::owner::Owner::new(); owner.set();
+/// let count = RwSignal::new_local(0);
+/// let sender = count.sender();
+/// std::thread::spawn(move || {
+///     // some expensive work...
+///     sender.set(42).unwrap();
+/// })
+/// .join()
+/// .unwrap();
+///
+/// // the update is applied once the executor runs the task on this thread
+/// assert_eq!(count.get_untracked(), 0);
+/// Executor::poll_local();
+/// assert_eq!(count.get_untracked(), 42);
+/// ```
+pub struct SignalSender<T> {
+    inner: LocalSender<ArcRwSignal<T>>,
+}
+
+impl<T> Clone for SignalSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for SignalSender<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalSender")
+            .field("type", &std::any::type_name::<T>())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl<T: 'static> SignalSender<T> {
+    /// Creates a sender whose updates are applied by a task that is spawned on the current thread
+    /// with [`Executor::spawn_local`](any_spawner::Executor::spawn_local).
+    pub fn new(signal: ArcRwSignal<T>) -> Self {
+        Self::new_with(signal, |task| {
+            #[cfg(feature = "sandboxed-arenas")]
+            let task = crate::owner::Sandboxed::new(task);
+            any_spawner::Executor::spawn_local(task);
+        })
+    }
+
+    /// Creates a sender whose updates are applied by a task that is spawned with the given
+    /// function, which can be used to apply them on a specific executor or event loop.
+    pub fn new_with(
+        signal: ArcRwSignal<T>,
+        spawn: impl FnOnce(PinnedLocalFuture<()>),
+    ) -> Self {
+        Self {
+            inner: LocalSender::spawn_with(signal, spawn),
+        }
+    }
+}
+
+impl<T> SignalSender<T> {
+    /// Queues an update to the value of the signal, notifying its subscribers once it is applied.
+    ///
+    /// Returns an error if the task that applies updates has stopped.
+    pub fn update(
+        &self,
+        fun: impl FnOnce(&mut T) + Send + 'static,
+    ) -> Result<(), LoopClosed>
+    where
+        T: 'static,
+    {
+        self.inner.send(move |signal| signal.update(fun))
+    }
+
+    /// Queues a new value for the signal, notifying its subscribers once it is applied.
+    ///
+    /// Returns an error if the task that applies updates has stopped.
+    pub fn set(&self, value: T) -> Result<(), LoopClosed>
+    where
+        T: Send + 'static,
+    {
+        self.inner.send(move |signal| signal.set(value))
+    }
+
+    /// Returns `true` if the task that applies updates has stopped, so that updates can no longer
+    /// be sent.
+    pub fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+impl<T: 'static> ArcRwSignal<T> {
+    /// Returns a [`SignalSender`] that can update this signal from any thread.
+    ///
+    /// Updates are applied on the current thread.
+    pub fn sender(&self) -> SignalSender<T> {
+        SignalSender::new(self.clone())
+    }
+}
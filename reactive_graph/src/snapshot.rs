@@ -0,0 +1,234 @@
+//! Serializing and restoring the state of many reactive values at once.
+//!
+//! Values are registered by name in a [`SnapshotRegistry`]. The registry can then export the
+//! current values of all of them as a single serializable [`Snapshot`], and later apply such a
+//! snapshot to restore their values. This can be used for session restore, for attaching the
+//! application state to a bug report, or for time-travel debugging.
+//!
+//! ```rust
+//! # use reactive_graph::prelude::*;
+//! # use reactive_graph::signal::RwSignal;
+//! # use reactive_graph::owner::StoredValue;
+//! # let owner = reactive_graph::owner::Owner::new(); owner.set();
+//! use reactive_graph::snapshot::SnapshotRegistry;
+//!
+//! let registry = SnapshotRegistry::new();
+//! let count = RwSignal::new(1);
+//! let name = StoredValue::new(String::from("Alice"));
+//! registry.register("count", count);
+//! registry.register("name", name);
+//!
+//! let snapshot = registry.snapshot().unwrap();
+//!
+//! count.set(2);
+//! name.set_value(String::from("Bob"));
+//!
+//! registry.restore(&snapshot).unwrap();
+//! assert_eq!(count.get_untracked(), 1);
+//! assert_eq!(name.get_value(), "Alice");
+//! ```
+
+use crate::{
+    owner::{ArcStoredValue, Storage, StoredValue},
+    traits::{Set, SetValue, WithUntracked, WithValue},
+};
+use or_poisoned::OrPoisoned;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{Arc, RwLock},
+};
+use thiserror::Error;
+
+/// A reactive value whose current value can be saved to, and restored from, a [`Snapshot`].
+///
+/// This is implemented for any signal or store that can be read with [`WithUntracked`] and
+/// updated with [`Set`], as well as for [`StoredValue`] and [`ArcStoredValue`].
+pub trait Snapshottable {
+    /// Serializes the current value, or returns `None` if the value has been disposed.
+    fn save(&self) -> Option<Result<Value, serde_json::Error>>;
+
+    /// Deserializes a value, and returns a function that replaces the current value with it.
+    ///
+    /// This allows every value in a snapshot to be deserialized before any of them is restored.
+    /// For reactive types, calling the function notifies subscribers.
+    fn prepare_restore(
+        &self,
+        value: Value,
+    ) -> Result<Box<dyn FnOnce() + '_>, serde_json::Error>;
+}
+
+impl<S, T> Snapshottable for S
+where
+    S: WithUntracked<Value = T> + Set<Value = T>,
+    T: Serialize + DeserializeOwned + 'static,
+{
+    fn save(&self) -> Option<Result<Value, serde_json::Error>> {
+        self.try_with_untracked(|value| serde_json::to_value(value))
+    }
+
+    fn prepare_restore(
+        &self,
+        value: Value,
+    ) -> Result<Box<dyn FnOnce() + '_>, serde_json::Error> {
+        let value = serde_json::from_value::<T>(value)?;
+        Ok(Box::new(move || self.set(value)))
+    }
+}
+
+impl<T, St> Snapshottable for StoredValue<T, St>
+where
+    T: Serialize + DeserializeOwned + 'static,
+    St: Storage<ArcStoredValue<T>>,
+{
+    fn save(&self) -> Option<Result<Value, serde_json::Error>> {
+        self.try_with_value(|value| serde_json::to_value(value))
+    }
+
+    fn prepare_restore(
+        &self,
+        value: Value,
+    ) -> Result<Box<dyn FnOnce() + '_>, serde_json::Error> {
+        let value = serde_json::from_value::<T>(value)?;
+        Ok(Box::new(move || self.set_value(value)))
+    }
+}
+
+impl<T> Snapshottable for ArcStoredValue<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+{
+    fn save(&self) -> Option<Result<Value, serde_json::Error>> {
+        self.try_with_value(|value| serde_json::to_value(value))
+    }
+
+    fn prepare_restore(
+        &self,
+        value: Value,
+    ) -> Result<Box<dyn FnOnce() + '_>, serde_json::Error> {
+        let value = serde_json::from_value::<T>(value)?;
+        Ok(Box::new(move || self.set_value(value)))
+    }
+}
+
+/// The serialized values of every entry in a [`SnapshotRegistry`], keyed by name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Snapshot(pub BTreeMap<String, Value>);
+
+/// An error that occurred while saving or restoring a [`Snapshot`].
+#[derive(Debug, Error)]
+#[error("could not snapshot {name:?}: {source}")]
+pub struct SnapshotError {
+    /// The name under which the value was registered.
+    pub name: String,
+    /// The underlying (de)serialization error.
+    #[source]
+    pub source: serde_json::Error,
+}
+
+type Entry = Arc<dyn Snapshottable + Send + Sync>;
+
+type Entries = BTreeMap<String, Entry>;
+
+/// A set of named reactive values that can be saved and restored together.
+///
+/// Cloning the registry returns another handle to the same set of values, so it can be shared,
+/// for example through [`provide_context`](crate::owner::provide_context).
+#[derive(Clone, Default)]
+pub struct SnapshotRegistry {
+    entries: Arc<RwLock<Entries>>,
+}
+
+impl Debug for SnapshotRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotRegistry")
+            .field("entries", &self.entries.read().or_poisoned().keys())
+            .finish()
+    }
+}
+
+impl SnapshotRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a value under the given name, replacing any value that was previously
+    /// registered with the same name.
+    pub fn register(
+        &self,
+        name: impl Into<String>,
+        value: impl Snapshottable + Send + Sync + 'static,
+    ) {
+        self.entries
+            .write()
+            .or_poisoned()
+            .insert(name.into(), Arc::new(value));
+    }
+
+    /// Removes the value registered under the given name, if any.
+    pub fn unregister(&self, name: &str) {
+        self.entries.write().or_poisoned().remove(name);
+    }
+
+    /// Serializes the current value of every registered value.
+    ///
+    /// Values that have been disposed are left out of the snapshot.
+    pub fn snapshot(&self) -> Result<Snapshot, SnapshotError> {
+        self.entries
+            .read()
+            .or_poisoned()
+            .iter()
+            .filter_map(|(name, entry)| {
+                let value = entry.save()?;
+                Some(value.map(|value| (name.clone(), value)).map_err(
+                    |source| SnapshotError {
+                        name: name.clone(),
+                        source,
+                    },
+                ))
+            })
+            .collect::<Result<_, _>>()
+            .map(Snapshot)
+    }
+
+    /// Restores the values in the snapshot to the values registered with the same names.
+    ///
+    /// Names in the snapshot that have not been registered are ignored, as are registered values
+    /// that are not included in the snapshot. Every value is deserialized before any of them is
+    /// restored, so if a value cannot be deserialized, this returns an error without restoring
+    /// anything.
+    pub fn restore(&self, snapshot: &Snapshot) -> Result<(), SnapshotError> {
+        // the entries are cloned out, so that restoring a value can run code that registers or
+        // unregisters other values
+        let entries = {
+            let entries = self.entries.read().or_poisoned();
+            snapshot
+                .0
+                .iter()
+                .filter_map(|(name, value)| {
+                    let entry = Arc::clone(entries.get(name)?);
+                    Some((name, entry, value))
+                })
+                .collect::<Vec<_>>()
+        };
+        let restores = entries
+            .iter()
+            .map(|(name, entry, value)| {
+                entry.prepare_restore((*value).clone()).map_err(|source| {
+                    SnapshotError {
+                        name: (*name).clone(),
+                        source,
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        for restore in restores {
+            restore();
+        }
+        Ok(())
+    }
+}
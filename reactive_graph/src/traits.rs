@@ -6,6 +6,8 @@
 //! 2. **Fallibility**: Most traits includes a `try_` variant, which returns `None` if the method
 //!    fails (e.g., if signals are arena allocated and this can't be found, or if an `RwLock` is
 //!    poisoned).
+//!    The most common access methods also include a `checked_` variant, which returns a
+//!    [`ReactiveError`] describing the failure instead.
 //!
 //! ## Metadata Traits
 //! - [`DefinedAt`] is used for debugging in the case of errors and should be implemented for all
@@ -51,6 +53,7 @@
 pub use crate::trait_options::*;
 use crate::{
     effect::Effect,
+    error::{clear_access_failure, report_disposed_access, ReactiveError},
     graph::{Observer, Source, Subscriber, ToAnySource},
     owner::Owner,
     signal::{arc_signal, guards::UntrackedWriteGuard, ArcReadSignal},
@@ -63,29 +66,26 @@ use std::{
 };
 
 #[doc(hidden)]
-/// Provides a sensible panic message for accessing disposed signals.
+/// Reports an access to a value that could not be accessed, then panics with a sensible message
+/// in debug builds, or unwinds with the [`ReactiveError`](crate::error::ReactiveError) in release
+/// builds.
 #[macro_export]
 macro_rules! unwrap_signal {
     ($signal:ident) => {{
-        #[cfg(any(debug_assertions, leptos_debuginfo))]
         let location = std::panic::Location::caller();
         || {
+            let error = $crate::error::ReactiveError::failed_access(
+                $signal.defined_at(),
+                location,
+            );
+            $crate::error::report_disposed_access(&error);
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             {
-                panic!(
-                    "{}",
-                    $crate::traits::panic_getting_disposed_signal(
-                        $signal.defined_at(),
-                        location
-                    )
-                );
+                panic!("{error}");
             }
             #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
             {
-                panic!(
-                    "Tried to access a reactive value that has already been \
-                     disposed."
-                );
+                std::panic::resume_unwind(Box::new(error));
             }
         }
     }};
@@ -286,6 +286,19 @@ pub trait WithUntracked: DefinedAt {
         self.try_with_untracked(fun)
             .unwrap_or_else(unwrap_signal!(self))
     }
+
+    /// Applies the closure to the value, and returns the result,
+    /// or a [`ReactiveError`] if the signal cannot be accessed.
+    #[track_caller]
+    fn checked_with_untracked<U>(
+        &self,
+        fun: impl FnOnce(&Self::Value) -> U,
+    ) -> Result<U, ReactiveError> {
+        let location = Location::caller();
+        clear_access_failure();
+        self.try_with_untracked(fun)
+            .ok_or_else(|| access_error(self, location))
+    }
 }
 
 impl<T> WithUntracked for T
@@ -321,6 +334,19 @@ pub trait With: DefinedAt {
     fn with<U>(&self, fun: impl FnOnce(&Self::Value) -> U) -> U {
         self.try_with(fun).unwrap_or_else(unwrap_signal!(self))
     }
+
+    /// Subscribes to the signal, applies the closure to the value, and returns the result,
+    /// or a [`ReactiveError`] if the signal cannot be accessed.
+    #[track_caller]
+    fn checked_with<U>(
+        &self,
+        fun: impl FnOnce(&Self::Value) -> U,
+    ) -> Result<U, ReactiveError> {
+        let location = Location::caller();
+        clear_access_failure();
+        self.try_with(fun)
+            .ok_or_else(|| access_error(self, location))
+    }
 }
 
 impl<T> With for T
@@ -354,6 +380,16 @@ pub trait GetUntracked: DefinedAt {
         self.try_get_untracked()
             .unwrap_or_else(unwrap_signal!(self))
     }
+
+    /// Clones and returns the value of the signal,
+    /// or a [`ReactiveError`] if the signal cannot be accessed.
+    #[track_caller]
+    fn checked_get_untracked(&self) -> Result<Self::Value, ReactiveError> {
+        let location = Location::caller();
+        clear_access_failure();
+        self.try_get_untracked()
+            .ok_or_else(|| access_error(self, location))
+    }
 }
 
 impl<T> GetUntracked for T
@@ -387,6 +423,15 @@ pub trait Get: DefinedAt {
     fn get(&self) -> Self::Value {
         self.try_get().unwrap_or_else(unwrap_signal!(self))
     }
+
+    /// Subscribes to the signal, then clones and returns the value of the signal,
+    /// or a [`ReactiveError`] if the signal cannot be accessed.
+    #[track_caller]
+    fn checked_get(&self) -> Result<Self::Value, ReactiveError> {
+        let location = Location::caller();
+        clear_access_failure();
+        self.try_get().ok_or_else(|| access_error(self, location))
+    }
 }
 
 impl<T> Get for T
@@ -493,6 +538,22 @@ pub trait Update {
         &self,
         fun: impl FnOnce(&mut Self::Value) -> (bool, U),
     ) -> Option<U>;
+
+    /// Updates the value of the signal and notifies subscribers, returning the value that is
+    /// returned by the update function, or a [`ReactiveError`] if the signal cannot be accessed.
+    #[track_caller]
+    fn checked_update<U>(
+        &self,
+        fun: impl FnOnce(&mut Self::Value) -> U,
+    ) -> Result<U, ReactiveError>
+    where
+        Self: DefinedAt,
+    {
+        let location = Location::caller();
+        clear_access_failure();
+        self.try_update(fun)
+            .ok_or_else(|| access_error(self, location))
+    }
 }
 
 impl<T> Update for T
@@ -501,6 +562,24 @@ where
 {
     type Value = <Self as Write>::Value;
 
+    #[track_caller]
+    fn update(&self, fun: impl FnOnce(&mut Self::Value)) {
+        if self.try_update(fun).is_none() {
+            report_disposed_access(&access_error(self, Location::caller()));
+        }
+    }
+
+    #[track_caller]
+    fn maybe_update(&self, fun: impl FnOnce(&mut Self::Value) -> bool) {
+        let updated = self.try_maybe_update(|val| {
+            let did_update = fun(val);
+            (did_update, ())
+        });
+        if updated.is_none() {
+            report_disposed_access(&access_error(self, Location::caller()));
+        }
+    }
+
     #[track_caller]
     fn try_maybe_update<U>(
         &self,
@@ -529,6 +608,21 @@ pub trait Set {
     /// If the signal has already been disposed, returns `Some(value)` with the value that was
     /// passed in. Otherwise, returns `None`.
     fn try_set(&self, value: Self::Value) -> Option<Self::Value>;
+
+    /// Updates the value by replacing it, and notifies subscribers that it has changed,
+    /// or returns a [`ReactiveError`] if the signal cannot be accessed.
+    #[track_caller]
+    fn checked_set(&self, value: Self::Value) -> Result<(), ReactiveError>
+    where
+        Self: DefinedAt,
+    {
+        let location = Location::caller();
+        clear_access_failure();
+        match self.try_set(value) {
+            None => Ok(()),
+            Some(_) => Err(access_error(self, location)),
+        }
+    }
 }
 
 impl<T> Set for T
@@ -539,7 +633,7 @@ where
 
     #[track_caller]
     fn set(&self, value: Self::Value) {
-        self.try_update(|n| *n = value);
+        self.update(|n| *n = value);
     }
 
     #[track_caller]
@@ -652,6 +746,13 @@ pub trait DefinedAt {
     fn defined_at(&self) -> Option<&'static Location<'static>>;
 }
 
+fn access_error(
+    signal: &(impl DefinedAt + ?Sized),
+    accessed_at: &'static Location<'static>,
+) -> ReactiveError {
+    ReactiveError::failed_access(signal.defined_at(), accessed_at)
+}
+
 #[doc(hidden)]
 pub fn panic_getting_disposed_signal(
     defined_at: Option<&'static Location<'static>>,
@@ -0,0 +1,290 @@
+use any_spawner::Executor;
+use futures::channel::oneshot;
+use reactive_graph::{
+    actions::{Action, ConcurrencyPolicy, MultiAction},
+    owner::Owner,
+    signal::RwSignal,
+    traits::{GetUntracked, Update},
+};
+use std::{
+    collections::HashMap,
+    future::pending,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+/// The two ends of a channel, taken by the test and by the dispatch.
+type Gate = (Option<oneshot::Sender<()>>, Option<oneshot::Receiver<()>>);
+
+/// Gates that hold each dispatch of a [`gated_action`] open until the test releases it.
+#[derive(Clone, Default)]
+struct Gates(Arc<Mutex<HashMap<u64, Gate>>>);
+
+impl Gates {
+    fn with<T>(&self, id: u64, fun: impl FnOnce(&mut Gate) -> T) -> T {
+        let mut gates = self.0.lock().unwrap();
+        let gate = gates.entry(id).or_insert_with(|| {
+            let (tx, rx) = oneshot::channel();
+            (Some(tx), Some(rx))
+        });
+        fun(gate)
+    }
+
+    /// Lets the dispatch with this input finish.
+    fn release(&self, id: u64) {
+        let tx = self.with(id, |gate| gate.0.take()).expect("released twice");
+        _ = tx.send(());
+    }
+
+    fn wait(&self, id: u64) -> oneshot::Receiver<()> {
+        self.with(id, |gate| gate.1.take())
+            .expect("dispatched twice")
+    }
+}
+
+/// Creates an action that waits until its input is released through the returned [`Gates`],
+/// recording when each dispatch starts and finishes.
+fn gated_action(policy: ConcurrencyPolicy) -> (Action<u64, u64>, Log, Gates) {
+    let log = Log::default();
+    let gates = Gates::default();
+    let action = Action::new({
+        let log = Arc::clone(&log);
+        let gates = gates.clone();
+        move |id: &u64| {
+            let id = *id;
+            let log = Arc::clone(&log);
+            let gates = gates.clone();
+            async move {
+                log.lock().unwrap().push(format!("start {id}"));
+                _ = gates.wait(id).await;
+                log.lock().unwrap().push(format!("end {id}"));
+                id
+            }
+        }
+    })
+    .with_concurrency(policy);
+    (action, log, gates)
+}
+
+/// Lets spawned tasks run until `done` returns `true`.
+async fn until(done: impl Fn() -> bool) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while !done() {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .expect("timed out waiting for the action");
+}
+
+fn entries(log: &Log) -> Vec<String> {
+    log.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn optimistic_update_is_rolled_back_on_abort() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let action = Action::new(|_: &()| pending::<Result<(), ()>>());
+
+    let handle = action.dispatch_optimistic(
+        (),
+        |_| count.update(|n| *n += 1),
+        move || count.update(|n| *n -= 1),
+    );
+    assert_eq!(count.get_untracked(), 1);
+
+    handle.abort();
+    until(|| !action.pending().get_untracked()).await;
+    assert_eq!(count.get_untracked(), 0);
+}
+
+#[tokio::test]
+async fn optimistic_update_is_reconciled_or_restored() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let names = RwSignal::new(vec!["Alice".to_string()]);
+    let rename = Action::new(|name: &String| {
+        let name = name.clone();
+        async move {
+            if name.is_empty() {
+                Err("name cannot be empty")
+            } else {
+                Ok(name.to_uppercase())
+            }
+        }
+    });
+
+    rename.dispatch_optimistic_update(
+        String::new(),
+        names,
+        |names, name| names[0] = name.clone(),
+        |names, name| names[0] = name.clone(),
+    );
+    assert_eq!(names.get_untracked(), vec![String::new()]);
+    until(|| !rename.pending().get_untracked()).await;
+    assert_eq!(names.get_untracked(), vec!["Alice".to_string()]);
+
+    rename.dispatch_optimistic_update(
+        "Bob".to_string(),
+        names,
+        |names, name| names[0] = name.clone(),
+        |names, name| names[0] = name.clone(),
+    );
+    assert_eq!(names.get_untracked(), vec!["Bob".to_string()]);
+    until(|| !rename.pending().get_untracked()).await;
+    assert_eq!(names.get_untracked(), vec!["BOB".to_string()]);
+}
+
+#[tokio::test]
+async fn canceled_submission_is_rolled_back() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let action = MultiAction::new(|_: &()| async {
+        Executor::tick().await;
+        Ok::<_, ()>(())
+    });
+
+    action.dispatch_optimistic(
+        (),
+        |_| count.update(|n| *n += 1),
+        move || count.update(|n| *n -= 1),
+    );
+    action.dispatch_optimistic(
+        (),
+        |_| count.update(|n| *n += 1),
+        move || count.update(|n| *n -= 1),
+    );
+    assert_eq!(count.get_untracked(), 2);
+
+    action.submissions().get_untracked()[0].cancel();
+    until(|| count.get_untracked() == 1).await;
+}
+
+#[tokio::test]
+async fn concurrent_dispatches_overlap() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, log, gates) = gated_action(ConcurrencyPolicy::Concurrent);
+    action.dispatch(40);
+    action.dispatch(10);
+    until(|| entries(&log).len() == 2).await;
+    gates.release(10);
+    until(|| entries(&log).len() == 3).await;
+    gates.release(40);
+    until(|| !action.pending().get_untracked()).await;
+    assert_eq!(entries(&log), ["start 40", "start 10", "end 10", "end 40"]);
+    // the last dispatch to finish wins
+    assert_eq!(action.value().get_untracked(), Some(40));
+}
+
+#[tokio::test]
+async fn queued_dispatches_run_in_order() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, log, gates) = gated_action(ConcurrencyPolicy::Queue);
+    action.dispatch(40);
+    let aborted = action.dispatch(20);
+    action.dispatch(10);
+    aborted.abort();
+    until(|| !entries(&log).is_empty()).await;
+    assert_eq!(entries(&log), ["start 40"]);
+    assert!(action.pending().get_untracked());
+
+    gates.release(40);
+    until(|| entries(&log).len() == 3).await;
+    gates.release(10);
+    until(|| !action.pending().get_untracked()).await;
+    assert_eq!(entries(&log), ["start 40", "end 40", "start 10", "end 10"]);
+    assert_eq!(action.value().get_untracked(), Some(10));
+}
+
+#[tokio::test]
+async fn dispatches_while_busy_are_dropped() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let count = RwSignal::new(0);
+    let (action, log, gates) = gated_action(ConcurrencyPolicy::DropNew);
+    action.dispatch(20);
+    action.dispatch(10);
+    assert_eq!(action.input().get_untracked(), Some(20));
+
+    let action2 = Action::new(move |_: &()| async { Ok::<_, ()>(()) })
+        .with_concurrency(ConcurrencyPolicy::DropNew);
+    action2.dispatch(());
+    // a dropped dispatch does not apply its optimistic update
+    action2.dispatch_optimistic(
+        (),
+        |_| count.update(|n| *n += 1),
+        move || count.update(|n| *n -= 1),
+    );
+    assert_eq!(count.get_untracked(), 0);
+
+    gates.release(20);
+    until(|| !action.pending().get_untracked()).await;
+    assert_eq!(entries(&log), ["start 20", "end 20"]);
+    assert_eq!(action.value().get_untracked(), Some(20));
+
+    action.dispatch(10);
+    gates.release(10);
+    until(|| !action.pending().get_untracked()).await;
+    assert_eq!(action.value().get_untracked(), Some(10));
+}
+
+#[tokio::test]
+async fn latest_dispatch_cancels_earlier_ones() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, log, gates) = gated_action(ConcurrencyPolicy::LatestWins);
+    // canceled before it starts
+    action.dispatch(10);
+    action.dispatch(40);
+    until(|| !entries(&log).is_empty()).await;
+    // canceled while it is running
+    action.dispatch(20);
+    until(|| entries(&log).len() == 2).await;
+    gates.release(20);
+    until(|| !action.pending().get_untracked()).await;
+    assert_eq!(entries(&log), ["start 40", "start 20", "end 20"]);
+    assert_eq!(action.value().get_untracked(), Some(20));
+    assert_eq!(action.version().get_untracked(), 1);
+}
+
+#[tokio::test]
+async fn keep_latest_keeps_only_the_latest_waiting_dispatch() {
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let (action, log, gates) = gated_action(ConcurrencyPolicy::KeepLatest);
+    action.dispatch(30);
+    action.dispatch(10);
+    action.dispatch(20);
+    action.dispatch(5);
+    until(|| !entries(&log).is_empty()).await;
+    gates.release(30);
+    until(|| entries(&log).len() == 3).await;
+    gates.release(5);
+    until(|| !action.pending().get_untracked()).await;
+    assert_eq!(entries(&log), ["start 30", "end 30", "start 5", "end 5"]);
+    assert_eq!(action.value().get_untracked(), Some(5));
+    assert_eq!(action.version().get_untracked(), 2);
+}
@@ -0,0 +1,102 @@
+#[cfg(feature = "sandboxed-arenas")]
+pub mod imports {
+    pub use reactive_graph::{
+        owner::{Owner, StoredValue},
+        signal::RwSignal,
+        traits::GetValue,
+    };
+}
+
+#[cfg(feature = "sandboxed-arenas")]
+#[test]
+fn counts_items_by_type() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let child = owner.child();
+    child.with(|| {
+        RwSignal::new(0);
+        RwSignal::new(1);
+        StoredValue::new("a");
+    });
+
+    let stats = owner.arena_stats();
+    assert!(stats.live >= 3);
+    assert_eq!(stats.live, stats.peak);
+    assert_eq!(stats.created, stats.live);
+    assert!(stats.bytes > 0);
+    assert!(stats
+        .by_type
+        .iter()
+        .any(|(ty, count)| ty.contains("&str") && *count == 1));
+
+    // the counts are shared by every owner in the tree, and only the live count drops
+    child.cleanup();
+    let after = child.arena_stats();
+    assert!(after.live < stats.live);
+    assert_eq!(after.peak, stats.peak);
+    assert_eq!(after.created, stats.created);
+    assert!(!after.by_type.iter().any(|(ty, _)| ty.contains("&str")));
+}
+
+#[cfg(feature = "sandboxed-arenas")]
+#[test]
+fn arenas_of_separate_roots_are_counted_separately() {
+    use imports::*;
+
+    let a = Owner::new();
+    a.with(|| RwSignal::new(0));
+    let b = Owner::new();
+    b.with(|| {
+        RwSignal::new(0);
+        RwSignal::new(0);
+    });
+
+    assert_eq!(a.arena_stats().created + 1, b.arena_stats().created);
+}
+
+#[cfg(feature = "sandboxed-arenas")]
+#[test]
+fn fails_with_typed_error_at_limit() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+    let live = owner.arena_stats().live;
+    owner.set_arena_limit(Some(live + 2));
+
+    let error = owner
+        .try_with(|| {
+            for n in 0..3 {
+                StoredValue::new(n);
+            }
+        })
+        .unwrap_err();
+    assert_eq!(error.limit, live + 2);
+    assert!(error.type_name.contains("i32"));
+    assert_eq!(owner.arena_limit_exceeded(), Some(error));
+    // the owner that was current before is restored
+    assert_eq!(Owner::current(), Some(owner.clone()));
+
+    // the arena can still be used once items have been removed
+    owner.cleanup();
+    owner.set_arena_limit(None);
+    assert_eq!(owner.arena_limit_exceeded(), None);
+    assert_eq!(owner.try_with(|| StoredValue::new(0).get_value()), Ok(0));
+    assert_eq!(owner.arena_stats().limit, None);
+}
+
+#[cfg(feature = "sandboxed-arenas")]
+#[test]
+fn other_panics_are_resumed() {
+    use imports::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let owner = Owner::new();
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        owner.try_with(|| panic!("not an arena error"))
+    }));
+    assert!(result.is_err());
+}
@@ -3,7 +3,7 @@ use reactive_graph::{
     computed::{ArcAsyncDerived, AsyncDerived},
     owner::Owner,
     signal::RwSignal,
-    traits::{Get, Read, Set, With, WithUntracked},
+    traits::{Get, GetUntracked, Read, Set, With, WithUntracked},
 };
 use std::future::pending;
 
@@ -137,3 +137,119 @@ async fn async_derived_with_initial() {
     signal2.set(1);
     assert_eq!(derived.await, 2);
 }
+
+#[tokio::test]
+async fn keyed_async_derived_reruns_only_when_key_changes() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let page = RwSignal::new(1);
+    let size = RwSignal::new(10);
+    let value = AsyncDerived::new_keyed("page", move || page.get() / 2, {
+        let runs = Arc::clone(&runs);
+        move |half: i32| {
+            runs.fetch_add(1, Ordering::Relaxed);
+            // this is not tracked
+            let size = size.get_untracked();
+            async move {
+                Executor::tick().await;
+                half * size
+            }
+        }
+    });
+
+    assert_eq!(value.await, 0);
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+    // the key does not change
+    size.set(100);
+    Executor::tick().await;
+    assert_eq!(value.await, 0);
+    assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+    page.set(2);
+    Executor::tick().await;
+    assert_eq!(value.await, 100);
+    assert_eq!(runs.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn keyed_async_derived_shares_requests_within_owner_tree() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    _ = Executor::init_tokio();
+    let runs = Arc::new(AtomicUsize::new(0));
+    let fetcher = {
+        let runs = Arc::clone(&runs);
+        move |id: u32| {
+            runs.fetch_add(1, Ordering::Relaxed);
+            async move {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                id * 2
+            }
+        }
+    };
+
+    // a separate owner tree, like another request on the server
+    let other_root = Owner::new();
+
+    let owner = Owner::new();
+    owner.set();
+    let a = ArcAsyncDerived::new_keyed("double", || 1, fetcher.clone());
+    // created in a child owner, but in the same tree
+    let b = owner
+        .child()
+        .with(|| ArcAsyncDerived::new_keyed("double", || 1, fetcher.clone()));
+    let other_key = ArcAsyncDerived::new_keyed("double", || 2, fetcher.clone());
+    let c =
+        other_root.with(|| ArcAsyncDerived::new_keyed("double", || 1, fetcher));
+
+    assert_eq!(a.await, 2);
+    assert_eq!(b.await, 2);
+    assert_eq!(other_key.await, 4);
+    assert_eq!(c.await, 2);
+    assert_eq!(runs.load(Ordering::Relaxed), 3);
+}
+
+#[tokio::test]
+async fn keyed_async_derived_only_shares_requests_of_the_same_query() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let runs = Arc::new(AtomicUsize::new(0));
+    let fetcher = |kind: &'static str| {
+        let runs = Arc::clone(&runs);
+        move |id: u32| {
+            runs.fetch_add(1, Ordering::Relaxed);
+            async move {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                format!("{kind} {id}")
+            }
+        }
+    };
+
+    // both fetchers have the same key and value types
+    let user = ArcAsyncDerived::new_keyed("user", || 1, fetcher("user"));
+    let post_title =
+        ArcAsyncDerived::new_keyed("post_title", || 1, fetcher("post"));
+
+    assert_eq!(user.await, "user 1");
+    assert_eq!(post_title.await, "post 1");
+    assert_eq!(runs.load(Ordering::Relaxed), 2);
+}
@@ -0,0 +1,104 @@
+#[cfg(all(feature = "broadcast", feature = "effects"))]
+pub mod imports {
+    pub use any_spawner::Executor;
+    pub use codee::string::{FromToStringCodec, JsonSerdeCodec};
+    pub use reactive_graph::{
+        broadcast::sync_across_tabs, effect::Effect, owner::Owner, prelude::*,
+        signal::RwSignal,
+    };
+    pub use tokio::task;
+}
+
+#[cfg(all(feature = "broadcast", feature = "effects"))]
+#[tokio::test]
+async fn syncs_between_tabs() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let tab_a = RwSignal::new(vec![1]);
+            let tab_b = RwSignal::new(vec![2]);
+            sync_across_tabs(tab_a, "syncs_between_tabs", JsonSerdeCodec);
+            sync_across_tabs(tab_b, "syncs_between_tabs", JsonSerdeCodec);
+
+            // opening a tab does not overwrite the other tabs
+            Executor::tick().await;
+            assert_eq!(tab_a.get_untracked(), vec![1]);
+            assert_eq!(tab_b.get_untracked(), vec![2]);
+
+            tab_a.update(|list| list.push(3));
+            Executor::tick().await;
+            assert_eq!(tab_b.get_untracked(), vec![1, 3]);
+
+            tab_b.set(vec![4]);
+            Executor::tick().await;
+            assert_eq!(tab_a.get_untracked(), vec![4]);
+        })
+        .await
+}
+
+#[cfg(all(feature = "broadcast", feature = "effects"))]
+#[tokio::test]
+async fn received_values_are_not_rebroadcast() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let tab_a = RwSignal::new(0);
+            let tab_b = RwSignal::new(0);
+            sync_across_tabs(tab_a, "not_rebroadcast", FromToStringCodec);
+            sync_across_tabs(tab_b, "not_rebroadcast", FromToStringCodec);
+            Executor::tick().await;
+
+            // if tab B re-broadcast the value it received, tab A would be set again
+            let sets = RwSignal::new(0);
+            Effect::new(move |_| {
+                tab_a.track();
+                sets.update_untracked(|n| *n += 1);
+            });
+            Executor::tick().await;
+            assert_eq!(sets.get_untracked(), 1);
+
+            tab_a.set(1);
+            Executor::tick().await;
+            Executor::tick().await;
+            assert_eq!(tab_b.get_untracked(), 1);
+            assert_eq!(sets.get_untracked(), 2);
+        })
+        .await
+}
+
+#[cfg(all(feature = "broadcast", feature = "effects"))]
+#[tokio::test]
+async fn stops_syncing_on_cleanup() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    task::LocalSet::new()
+        .run_until(async {
+            let tab_a = RwSignal::new(0);
+            let tab_b = RwSignal::new(0);
+            sync_across_tabs(tab_a, "cleanup", FromToStringCodec);
+            let child = owner.child();
+            child
+                .with(|| sync_across_tabs(tab_b, "cleanup", FromToStringCodec));
+            Executor::tick().await;
+
+            child.cleanup();
+            tab_a.set(1);
+            Executor::tick().await;
+            assert_eq!(tab_b.get_untracked(), 0);
+        })
+        .await
+}
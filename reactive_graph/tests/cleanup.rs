@@ -86,3 +86,31 @@ fn leak_on_dispose() {
 
     assert!(weak.upgrade().is_none()); // Should have been dropped.
 }
+
+#[cfg(debug_assertions)]
+#[test]
+fn leak_check_reports_unowned_values() {
+    use reactive_graph::owner::{check_leaks, LeakReason};
+
+    let (_, report) = check_leaks(|| {
+        let owner = Owner::new();
+        owner.with(|| {
+            RwSignal::new(0);
+            Memo::new(|_| 0);
+        });
+        owner.cleanup();
+    });
+    report.assert_no_leaks();
+
+    // keep an arena active for the values created below, without setting an owner
+    let root = Owner::new();
+    root.set();
+    root.clone().unset();
+
+    let (signal, report) = check_leaks(|| RwSignal::new(0));
+    assert_eq!(report.leaks.len(), 1);
+    assert_eq!(report.leaks[0].reason, LeakReason::Unowned);
+    assert_eq!(report.leaks[0].defined_at.line(), line!() - 3);
+
+    signal.dispose();
+}
@@ -0,0 +1,147 @@
+use reactive_graph::{
+    collections::{ArcSignalMap, ArcSignalVec, Changes, MapDiff, VecDiff},
+    traits::{GetUntracked, With},
+};
+use std::collections::HashMap;
+
+#[test]
+fn vec_cursor_replays_changes_in_order() {
+    let list = ArcSignalVec::new(vec![1, 2, 3]);
+    let mut cursor = list.cursor();
+    assert_eq!(cursor.changes(), Changes::Reset(vec![1, 2, 3]));
+    assert_eq!(cursor.changes(), Changes::Diffs(vec![]));
+
+    list.insert(1, 10);
+    list.retain(|n| n % 2 == 0);
+    list.update_at(0, |n| *n *= 2);
+    assert_eq!(list.pop(), Some(2));
+    // out of bounds, so nothing is recorded
+    assert_eq!(list.update_at(5, |n| *n += 1), None);
+
+    assert_eq!(list.get_untracked(), vec![20]);
+    assert_eq!(
+        cursor.changes(),
+        Changes::Diffs(vec![
+            VecDiff::Insert {
+                index: 1,
+                value: 10
+            },
+            VecDiff::Remove { index: 0 },
+            VecDiff::Remove { index: 2 },
+            VecDiff::Update {
+                index: 0,
+                value: 20
+            },
+            VecDiff::Remove { index: 1 },
+        ])
+    );
+}
+
+#[test]
+fn vec_cursors_are_independent() {
+    let list = ArcSignalVec::new(vec!["a"]);
+    let mut first = list.cursor();
+    first.changes();
+
+    list.push("b");
+    let mut second = list.cursor();
+    assert_eq!(second.changes(), Changes::Reset(vec!["a", "b"]));
+
+    list.clear();
+    assert_eq!(second.changes(), Changes::Diffs(vec![VecDiff::Clear]));
+    assert_eq!(
+        first.changes(),
+        Changes::Diffs(vec![
+            VecDiff::Insert {
+                index: 1,
+                value: "b"
+            },
+            VecDiff::Clear
+        ])
+    );
+}
+
+#[test]
+fn vec_cursor_resets_after_replacement_or_many_changes() {
+    let list = ArcSignalVec::new(vec![0]);
+    let mut cursor = list.cursor();
+    cursor.changes();
+
+    list.reset(vec![1, 2]);
+    assert_eq!(cursor.changes(), Changes::Reset(vec![1, 2]));
+
+    // more changes than the log retains
+    for n in 0..100 {
+        list.replace(0, n);
+    }
+    assert_eq!(cursor.changes(), Changes::Reset(vec![99, 2]));
+}
+
+#[test]
+fn map_records_inserts_updates_and_removals() {
+    let map = ArcSignalMap::new(HashMap::from([("a", 1)]));
+    let mut cursor = map.cursor();
+    assert_eq!(cursor.changes(), Changes::Reset(HashMap::from([("a", 1)])));
+
+    assert_eq!(map.insert("b", 2), None);
+    assert_eq!(map.insert("a", 3), Some(1));
+    assert_eq!(map.remove(&"b"), Some(2));
+    assert_eq!(map.remove(&"b"), None);
+
+    assert_eq!(map.with(|map| map.len()), 1);
+    assert_eq!(
+        cursor.changes(),
+        Changes::Diffs(vec![
+            MapDiff::Insert { key: "b", value: 2 },
+            MapDiff::Update { key: "a", value: 3 },
+            MapDiff::Remove { key: "b" },
+        ])
+    );
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn only_changes_notify_subscribers() {
+    use any_spawner::Executor;
+    use reactive_graph::{effect::Effect, owner::Owner};
+    use std::sync::{Arc, RwLock};
+    use tokio::task;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let list = ArcSignalVec::new(vec![1]);
+            let seen = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::new({
+                let seen = Arc::clone(&seen);
+                let mut cursor = list.cursor();
+                move |_| seen.write().unwrap().push(cursor.changes())
+            });
+            Executor::tick().await;
+
+            list.push(2);
+            Executor::tick().await;
+            list.clear();
+            Executor::tick().await;
+            // clearing an empty list, or popping from it, does not notify
+            list.clear();
+            assert_eq!(list.pop(), None);
+            Executor::tick().await;
+
+            assert_eq!(
+                *seen.read().unwrap(),
+                vec![
+                    Changes::Reset(vec![1]),
+                    Changes::Diffs(vec![VecDiff::Insert {
+                        index: 1,
+                        value: 2
+                    }]),
+                    Changes::Diffs(vec![VecDiff::Clear]),
+                ]
+            );
+        })
+        .await
+}
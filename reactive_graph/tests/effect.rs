@@ -2,7 +2,7 @@
 pub mod imports {
     pub use any_spawner::Executor;
     pub use reactive_graph::{
-        effect::{Effect, RenderEffect},
+        effect::{Effect, EffectPriority, RenderEffect},
         owner::Owner,
         prelude::*,
         signal::RwSignal,
@@ -196,3 +196,121 @@ async fn recursive_effect_runs_recursively() {
         })
         .await;
 }
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn effects_run_in_priority_order() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let log = Arc::new(RwLock::new(Vec::new()));
+
+            // created in reverse order, so that they would run in reverse order without
+            // priorities; the many user-blocking effects make sure that lower-priority effects
+            // wait for all of the ones that were notified along with them
+            let mut effects = vec![
+                ("idle", EffectPriority::Idle),
+                ("animation_frame", EffectPriority::AnimationFrame),
+                ("normal", EffectPriority::Normal),
+            ];
+            effects.extend(
+                std::iter::repeat((
+                    "user_blocking",
+                    EffectPriority::UserBlocking,
+                ))
+                .take(50),
+            );
+            for (name, priority) in effects {
+                Effect::new_with_priority(
+                    {
+                        let log = Arc::clone(&log);
+                        move |_| {
+                            a.track();
+                            log.write().unwrap().push(name);
+                        }
+                    },
+                    priority,
+                );
+            }
+
+            let run_all = || async {
+                while log.read().unwrap().len() < 53 {
+                    Executor::tick().await;
+                }
+            };
+
+            run_all().await;
+            log.write().unwrap().clear();
+
+            a.set(1);
+            run_all().await;
+            let log = log.read().unwrap();
+            assert!(log[..50].iter().all(|name| *name == "user_blocking"));
+            assert_eq!(&log[50..], &["normal", "animation_frame", "idle"]);
+        })
+        .await;
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn sync_effects_and_watch_use_priorities() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let a = RwSignal::new(0);
+            let log = Arc::new(RwLock::new(Vec::new()));
+
+            Effect::new_sync_with_priority(
+                {
+                    let log = Arc::clone(&log);
+                    move |_| {
+                        a.track();
+                        log.write().unwrap().push("idle");
+                    }
+                },
+                EffectPriority::Idle,
+            );
+            Effect::watch_with_priority(
+                move || a.get(),
+                {
+                    let log = Arc::clone(&log);
+                    move |_, _, _| log.write().unwrap().push("normal")
+                },
+                true,
+                EffectPriority::Normal,
+            );
+            Effect::new_isomorphic({
+                let log = Arc::clone(&log);
+                move |_| {
+                    a.track();
+                    log.write().unwrap().push("user_blocking");
+                }
+            });
+
+            let run_all = || async {
+                while log.read().unwrap().len() < 3 {
+                    Executor::tick().await;
+                }
+            };
+
+            run_all().await;
+            log.write().unwrap().clear();
+
+            a.set(1);
+            run_all().await;
+            assert_eq!(
+                &*log.read().unwrap(),
+                &["user_blocking", "normal", "idle"]
+            );
+        })
+        .await;
+}
@@ -0,0 +1,130 @@
+#[cfg(feature = "persistent")]
+pub mod imports {
+    pub use any_spawner::Executor;
+    pub use codee::string::{FromToStringCodec, JsonSerdeCodec};
+    pub use reactive_graph::{
+        owner::Owner,
+        persistent::{
+            ArcPersistentSignal, FileStorage, MemoryStorage, StorageBackend,
+        },
+        prelude::*,
+    };
+    pub use std::time::Duration;
+}
+
+#[cfg(feature = "persistent")]
+#[test]
+fn loads_stored_value_or_initial() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    let a = ArcPersistentSignal::<i32, FromToStringCodec, _>::new(
+        "a",
+        storage.clone(),
+        || 1,
+    );
+    assert_eq!(a.get_untracked(), 1);
+    assert_eq!(storage.get_item("a"), None);
+
+    storage.set_item("b", "5");
+    let b = ArcPersistentSignal::<i32, FromToStringCodec, _>::new(
+        "b",
+        storage.clone(),
+        || 1,
+    );
+    assert_eq!(b.get_untracked(), 5);
+
+    // values that cannot be decoded fall back to the initial value
+    storage.set_item("c", "not a number");
+    let c = ArcPersistentSignal::<i32, FromToStringCodec, _>::new(
+        "c",
+        storage,
+        || 1,
+    );
+    assert_eq!(c.get_untracked(), 1);
+}
+
+#[cfg(feature = "persistent")]
+#[test]
+fn writes_through_on_change() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    let list = ArcPersistentSignal::<Vec<i32>, JsonSerdeCodec, _>::new(
+        "list",
+        storage.clone(),
+        Vec::new,
+    );
+    list.set(vec![1, 2]);
+    assert_eq!(storage.get_item("list").as_deref(), Some("[1,2]"));
+    list.update(|list| list.push(3));
+    assert_eq!(storage.get_item("list").as_deref(), Some("[1,2,3]"));
+
+    // untracked updates are not persisted
+    list.update_untracked(|list| list.push(4));
+    assert_eq!(storage.get_item("list").as_deref(), Some("[1,2,3]"));
+    list.flush();
+    assert_eq!(storage.get_item("list").as_deref(), Some("[1,2,3,4]"));
+
+    list.clear();
+    assert_eq!(storage.get_item("list"), None);
+}
+
+#[cfg(feature = "persistent")]
+#[tokio::test]
+async fn debounced_writes() {
+    use imports::*;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let storage = MemoryStorage::default();
+    let count = ArcPersistentSignal::<i32, FromToStringCodec, _>::new(
+        "count",
+        storage.clone(),
+        || 0,
+    )
+    .with_debounce(Duration::from_millis(20));
+
+    count.set(1);
+    count.set(2);
+    count.set(3);
+    assert_eq!(storage.get_item("count"), None);
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(storage.get_item("count").as_deref(), Some("3"));
+}
+
+#[cfg(feature = "persistent")]
+#[test]
+fn file_storage() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let dir = std::env::temp_dir()
+        .join(format!("reactive_graph_persistent_{}", std::process::id()));
+    let name = ArcPersistentSignal::<String, FromToStringCodec, _>::new(
+        "name",
+        FileStorage::new(&dir),
+        || "Alice".to_string(),
+    );
+    name.set("Bob".to_string());
+
+    let restored = ArcPersistentSignal::<String, FromToStringCodec, _>::new(
+        "name",
+        FileStorage::new(&dir),
+        || "Alice".to_string(),
+    );
+    assert_eq!(restored.get_untracked(), "Bob");
+
+    _ = std::fs::remove_dir_all(dir);
+}
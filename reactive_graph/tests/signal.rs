@@ -1,11 +1,13 @@
 use reactive_graph::{
+    error::{set_disposed_access_hook, ReactiveError, ReactiveErrorKind},
     owner::Owner,
     signal::{arc_signal, signal, ArcRwSignal, RwSignal},
     traits::{
-        Dispose, Get, GetUntracked, IntoInner, Read, Set, Update,
+        DefinedAt, Dispose, Get, GetUntracked, IntoInner, Read, Set, Update,
         UpdateUntracked, With, WithUntracked, Write,
     },
 };
+use std::sync::{Arc, Mutex};
 
 #[test]
 fn create_arc_rw_signal() {
@@ -140,3 +142,137 @@ fn into_inner_non_arc_signal() {
     b.dispose();
     assert_eq!(a.into_inner(), Some(2));
 }
+
+#[test]
+fn checked_access_to_disposed_signal() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(0);
+    assert_eq!(a.checked_get(), Ok(0));
+    assert_eq!(a.checked_update(|n| *n + 1), Ok(1));
+    assert_eq!(a.checked_set(2), Ok(()));
+    assert_eq!(a.checked_with_untracked(|n| *n), Ok(2));
+
+    a.dispose();
+    let err = a.checked_get().unwrap_err();
+    assert_eq!(err.defined_at(), a.defined_at());
+    assert_eq!(err.accessed_at().line(), line!() - 2);
+    assert_eq!(err.kind(), ReactiveErrorKind::Disposed);
+    assert_eq!(err.owner(), Some(owner.debug_id()));
+    assert!(a.checked_with(|n| *n).is_err());
+    assert!(a.checked_get_untracked().is_err());
+    assert!(a.checked_update(|n| *n += 1).is_err());
+    assert!(a.checked_set(3).is_err());
+}
+
+#[test]
+fn checked_access_distinguishes_poisoned_and_locked_values() {
+    let a = ArcRwSignal::new(0);
+
+    // reading a signal while it is being updated
+    a.update_untracked(|_| {
+        let err = a.checked_get_untracked().unwrap_err();
+        assert_eq!(err.kind(), ReactiveErrorKind::Locked);
+    });
+
+    let poisoned = std::thread::spawn({
+        let a = a.clone();
+        move || a.update_untracked(|_| panic!("poisoning the lock"))
+    })
+    .join();
+    assert!(poisoned.is_err());
+    let err = a.checked_get_untracked().unwrap_err();
+    assert_eq!(err.kind(), ReactiveErrorKind::Poisoned);
+    assert!(err.to_string().contains("poisoned"));
+    assert_eq!(
+        a.checked_update(|n| *n += 1).unwrap_err().kind(),
+        ReactiveErrorKind::Poisoned
+    );
+}
+
+#[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+#[test]
+fn reading_disposed_signal_unwinds_with_error_in_release() {
+    let owner = Owner::new();
+    owner.set();
+
+    let a = RwSignal::new(0);
+    a.dispose();
+    let payload = std::panic::catch_unwind(|| a.get()).unwrap_err();
+    let err = payload.downcast::<ReactiveError>().unwrap();
+    assert_eq!(err.kind(), ReactiveErrorKind::Disposed);
+}
+
+#[test]
+fn disposed_access_hook_records_ignored_updates() {
+    let owner = Owner::new();
+    owner.set();
+
+    let errors = Arc::new(Mutex::new(Vec::<ReactiveError>::new()));
+    set_disposed_access_hook({
+        let errors = Arc::clone(&errors);
+        move |err| errors.lock().unwrap().push(err.clone())
+    });
+
+    let a = RwSignal::new(0);
+    a.set(1);
+    a.dispose();
+    a.set(2);
+    a.update(|n| *n += 1);
+    // errors returned by `checked_` methods are not reported
+    _ = a.checked_set(3);
+
+    // other tests may run in parallel, so only count accesses under this owner
+    let errors = errors
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|err| err.owner() == Some(owner.debug_id()))
+        .count();
+    assert_eq!(errors, 2);
+}
+
+#[cfg(feature = "effects")]
+#[tokio::test]
+async fn signal_sender_updates_local_signal_from_other_threads() {
+    use any_spawner::Executor;
+    use reactive_graph::{
+        owner::Owner,
+        signal::RwSignal,
+        traits::{GetUntracked, Set},
+    };
+    use tokio::task;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+    task::LocalSet::new()
+        .run_until(async {
+            let count = RwSignal::new_local(0);
+            let sender = count.sender();
+
+            let handles = (0..4)
+                .map(|_| {
+                    let sender = sender.clone();
+                    std::thread::spawn(move || {
+                        sender.update(|n| *n += 1).unwrap();
+                    })
+                })
+                .collect::<Vec<_>>();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+            // updates are only applied when the local task runs
+            assert_eq!(count.get_untracked(), 0);
+            Executor::tick().await;
+            task::yield_now().await;
+            assert_eq!(count.get_untracked(), 4);
+
+            count.set(10);
+            sender.set(20).unwrap();
+            task::yield_now().await;
+            assert_eq!(count.get_untracked(), 20);
+        })
+        .await
+}
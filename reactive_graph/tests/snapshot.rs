@@ -0,0 +1,110 @@
+#[cfg(feature = "snapshot")]
+pub mod imports {
+    pub use reactive_graph::{
+        owner::{ArcStoredValue, Owner},
+        prelude::*,
+        signal::{ArcRwSignal, RwSignal},
+        snapshot::{Snapshot, SnapshotRegistry},
+    };
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot_and_restore() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let registry = SnapshotRegistry::new();
+    let count = ArcRwSignal::new(1);
+    let todos = RwSignal::new(vec![String::from("Buy milk")]);
+    let token = ArcStoredValue::new(Some(42));
+    registry.register("count", count.clone());
+    registry.register("todos", todos);
+    registry.register("token", token.clone());
+
+    let snapshot = registry.snapshot().unwrap();
+    let serialized = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(serialized, r#"{"count":1,"todos":["Buy milk"],"token":42}"#);
+
+    count.set(2);
+    todos.update(|todos| todos.clear());
+    token.set_value(None);
+
+    let snapshot: Snapshot = serde_json::from_str(&serialized).unwrap();
+    registry.restore(&snapshot).unwrap();
+    assert_eq!(count.get_untracked(), 1);
+    assert_eq!(todos.get_untracked(), vec![String::from("Buy milk")]);
+    assert_eq!(token.get_value(), Some(42));
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn restore_reports_invalid_entries() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let registry = SnapshotRegistry::new();
+    let count = ArcRwSignal::new(1);
+    registry.register("count", count.clone());
+
+    // unknown names are ignored
+    let snapshot: Snapshot = serde_json::from_str(r#"{"other":true}"#).unwrap();
+    registry.restore(&snapshot).unwrap();
+    assert_eq!(count.get_untracked(), 1);
+
+    let snapshot: Snapshot =
+        serde_json::from_str(r#"{"count":"not a number"}"#).unwrap();
+    let err = registry.restore(&snapshot).unwrap_err();
+    assert_eq!(err.name, "count");
+    assert_eq!(count.get_untracked(), 1);
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn restore_applies_nothing_if_any_entry_is_invalid() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let registry = SnapshotRegistry::new();
+    let count = ArcRwSignal::new(1);
+    let name = ArcRwSignal::new(String::from("Alice"));
+    let total = ArcRwSignal::new(10);
+    registry.register("a_count", count.clone());
+    registry.register("b_name", name.clone());
+    registry.register("c_total", total.clone());
+
+    // the entries before and after the invalid one are not restored either
+    let snapshot: Snapshot =
+        serde_json::from_str(r#"{"a_count":2,"b_name":false,"c_total":20}"#)
+            .unwrap();
+    let err = registry.restore(&snapshot).unwrap_err();
+    assert_eq!(err.name, "b_name");
+    assert_eq!(count.get_untracked(), 1);
+    assert_eq!(name.get_untracked(), "Alice");
+    assert_eq!(total.get_untracked(), 10);
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot_skips_disposed_values() {
+    use imports::*;
+
+    let owner = Owner::new();
+    owner.set();
+
+    let registry = SnapshotRegistry::new();
+    let count = RwSignal::new(1);
+    let name = RwSignal::new(String::from("Alice"));
+    registry.register("count", count);
+    registry.register("name", name);
+
+    name.dispose();
+    let snapshot = registry.snapshot().unwrap();
+    assert_eq!(serde_json::to_string(&snapshot).unwrap(), r#"{"count":1}"#);
+}
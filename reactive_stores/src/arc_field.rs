@@ -1,10 +1,12 @@
 use crate::{
     path::{StorePath, StorePathSegment},
+    store_field::{record_change, this_trigger, DowngradeField},
     ArcStore, AtIndex, AtKeyed, DerefedField, KeyMap, KeyedSubfield, Store,
     StoreField, StoreFieldTrigger, Subfield,
 };
 use reactive_graph::{
     owner::Storage,
+    signal::guards::WriteGuard,
     traits::{
         DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
         Write,
@@ -15,7 +17,7 @@ use std::{
     hash::Hash,
     ops::{Deref, DerefMut, IndexMut},
     panic::Location,
-    sync::Arc,
+    sync::{Arc, Weak},
 };
 
 /// Reference-counted access to a single field of type `T`.
@@ -319,6 +321,132 @@ where
     }
 }
 
+impl<T> ArcField<T>
+where
+    T: 'static,
+{
+    /// Returns a field that views this field's value as a different type, through a pair of
+    /// functions that convert to and from it.
+    ///
+    /// The new field has the same path as this one, so it tracks and notifies the same
+    /// subscribers. Reading it calls `get` with the current value; writing to it calls `get` to
+    /// create the value that is updated, and then `set` with the updated value once the write
+    /// guard is dropped.
+    ///
+    /// ```rust
+    /// use reactive_graph::traits::{Get, Set};
+    /// use reactive_stores::{ArcField, Store};
+    ///
+    /// #[derive(Store)]
+    /// struct Weather {
+    ///     celsius: f64,
+    /// }
+    ///
+    /// let store = Store::new(Weather { celsius: 20.0 });
+    /// let fahrenheit = ArcField::from(store.celsius())
+    ///     .lens(|c| c * 9.0 / 5.0 + 32.0, |c, f| *c = (f - 32.0) * 5.0 / 9.0);
+    /// assert_eq!(fahrenheit.get(), 68.0);
+    ///
+    /// fahrenheit.set(212.0);
+    /// assert_eq!(store.celsius().get(), 100.0);
+    /// ```
+    #[track_caller]
+    pub fn lens<U>(
+        &self,
+        get: impl Fn(&T) -> U + Send + Sync + 'static,
+        set: impl Fn(&mut T, U) + Send + Sync + 'static,
+    ) -> ArcField<U>
+    where
+        U: 'static,
+    {
+        let get = Arc::new(get);
+        let set: LensSetter<T, U> = Arc::new(set);
+        ArcField {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            path: self.path.clone(),
+            trigger: self.trigger.clone(),
+            get_trigger: Arc::clone(&self.get_trigger),
+            read: Arc::new({
+                let read = Arc::clone(&self.read);
+                let get = Arc::clone(&get);
+                move || {
+                    let value = get(&*read()?);
+                    Some(StoreFieldReader::new(LensReader(value)))
+                }
+            }),
+            write: Arc::new({
+                let write = Arc::clone(&self.write);
+                move || {
+                    let inner = write()?;
+                    let value = get(&*inner);
+                    Some(StoreFieldWriter::new(LensWriter {
+                        inner,
+                        value: Some(value),
+                        set: Arc::clone(&set),
+                    }))
+                }
+            }),
+            keys: Arc::clone(&self.keys),
+            track_field: Arc::clone(&self.track_field),
+        }
+    }
+}
+
+/// Holds the value of a field that is viewed through [`ArcField::lens`].
+struct LensReader<U>(U);
+
+impl<U> Deref for LensReader<U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+type LensSetter<T, U> = Arc<dyn Fn(&mut T, U) + Send + Sync>;
+
+/// Holds the value of a field that is viewed through [`ArcField::lens`], and sets the value of
+/// the underlying field when it is dropped.
+struct LensWriter<T, U> {
+    inner: StoreFieldWriter<T>,
+    value: Option<U>,
+    set: LensSetter<T, U>,
+}
+
+impl<T, U> Deref for LensWriter<T, U> {
+    type Target = U;
+
+    fn deref(&self) -> &Self::Target {
+        self.value
+            .as_ref()
+            .expect("value is only taken when dropped")
+    }
+}
+
+impl<T, U> DerefMut for LensWriter<T, U> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.value
+            .as_mut()
+            .expect("value is only taken when dropped")
+    }
+}
+
+impl<T, U> UntrackableGuard for LensWriter<T, U> {
+    fn untrack(&mut self) {
+        self.inner.untrack();
+    }
+}
+
+impl<T, U> Drop for LensWriter<T, U> {
+    fn drop(&mut self) {
+        if let Some(value) = self.value.take() {
+            // the inner guard, which notifies the field's subscribers, is dropped after this
+            (self.set)(&mut self.inner, value);
+        }
+    }
+}
+
 impl<T> Clone for ArcField<T> {
     fn clone(&self) -> Self {
         Self {
@@ -335,6 +463,71 @@ impl<T> Clone for ArcField<T> {
     }
 }
 
+/// An [`ArcField`] that does not keep its store alive.
+#[doc(hidden)]
+pub struct WeakArcField<T>
+where
+    T: 'static,
+{
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    path: StorePath,
+    trigger: StoreFieldTrigger,
+    get_trigger: Weak<dyn Fn(StorePath) -> StoreFieldTrigger + Send + Sync>,
+    read: Weak<dyn Fn() -> Option<StoreFieldReader<T>> + Send + Sync>,
+    write: Weak<dyn Fn() -> Option<StoreFieldWriter<T>> + Send + Sync>,
+    keys: Weak<dyn Fn() -> Option<KeyMap> + Send + Sync>,
+    track_field: Weak<dyn Fn() + Send + Sync>,
+}
+
+impl<T> Clone for WeakArcField<T> {
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            path: self.path.clone(),
+            trigger: self.trigger.clone(),
+            get_trigger: Weak::clone(&self.get_trigger),
+            read: Weak::clone(&self.read),
+            write: Weak::clone(&self.write),
+            keys: Weak::clone(&self.keys),
+            track_field: Weak::clone(&self.track_field),
+        }
+    }
+}
+
+impl<T> DowngradeField for ArcField<T> {
+    type Weak = WeakArcField<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        WeakArcField {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            path: self.path.clone(),
+            trigger: self.trigger.clone(),
+            get_trigger: Arc::downgrade(&self.get_trigger),
+            read: Arc::downgrade(&self.read),
+            write: Arc::downgrade(&self.write),
+            keys: Arc::downgrade(&self.keys),
+            track_field: Arc::downgrade(&self.track_field),
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        Some(ArcField {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: weak.defined_at,
+            path: weak.path.clone(),
+            trigger: weak.trigger.clone(),
+            get_trigger: weak.get_trigger.upgrade()?,
+            read: weak.read.upgrade()?,
+            write: weak.write.upgrade()?,
+            keys: weak.keys.upgrade()?,
+            track_field: weak.track_field.upgrade()?,
+        })
+    }
+}
+
 impl<T> DefinedAt for ArcField<T> {
     fn defined_at(&self) -> Option<&'static Location<'static>> {
         #[cfg(any(debug_assertions, leptos_debuginfo))]
@@ -350,6 +543,7 @@ impl<T> DefinedAt for ArcField<T> {
 
 impl<T> Notify for ArcField<T> {
     fn notify(&self) {
+        record_change(self);
         self.trigger.this.notify();
     }
 }
@@ -372,7 +566,9 @@ impl<T> Write for ArcField<T> {
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        (self.write)()
+        record_change(self);
+        let this = this_trigger(self);
+        (self.write)().map(|writer| WriteGuard::new(this, writer))
     }
 
     fn try_write_untracked(
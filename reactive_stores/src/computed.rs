@@ -0,0 +1,323 @@
+use crate::{path::StorePath, store_field::DowngradeField, KeyMap};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{computed::ArcMemo, traits::WithUntracked};
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock, Weak},
+};
+
+/// A memoized value derived from a store, declared with `#[store(computed(...))]`.
+///
+/// ```rust
+/// use reactive_graph::traits::{Get, Update};
+/// use reactive_stores::{Store, StoreFieldIterator};
+///
+/// #[derive(Store)]
+/// #[store(computed(remaining: usize = |todos| {
+///     todos
+///         .todos()
+///         .iter_unkeyed()
+///         .filter(|todo| !todo.clone().completed().get())
+///         .count()
+/// }))]
+/// struct Todos {
+///     user: String,
+///     todos: Vec<Todo>,
+/// }
+///
+/// #[derive(Store)]
+/// struct Todo {
+///     label: String,
+///     completed: bool,
+/// }
+///
+/// let store = Store::new(Todos {
+///     user: "Alice".to_string(),
+///     todos: vec![Todo {
+///         label: "Write docs".to_string(),
+///         completed: false,
+///     }],
+/// });
+/// assert_eq!(store.remaining().get(), 1);
+///
+/// store.todos().update(|todos| todos[0].completed = true);
+/// assert_eq!(store.remaining().get(), 0);
+/// ```
+///
+/// Each computed field is a method on the generated `StoreFields` trait, which returns a memo
+/// that only tracks the fields read by the function that computes it. The memo is created the
+/// first time the method is called, and the same memo is returned for later calls on the same
+/// field of the same store, so it is only recalculated once for each change, however many places
+/// it is read from.
+///
+/// The cached memo only holds a weak reference to its field, so it does not keep an
+/// [`ArcStore`](crate::ArcStore) alive. Once the field no longer exists, because its store has
+/// been dropped or it was an item that has been removed, the memo keeps its last value, and it
+/// is removed from the cache when it next recalculates or when another computed field is cached.
+pub type Computed<T> = ArcMemo<T>;
+
+/// The memo of a computed field, cached in the [`KeyMap`] of its store.
+pub(crate) struct CachedComputed {
+    memo: Box<dyn Any + Send + Sync>,
+    /// Whether the field the memo is computed from still exists.
+    exists: Box<dyn Fn() -> bool + Send + Sync>,
+}
+
+pub(crate) type ComputedCache =
+    RwLock<HashMap<(StorePath, &'static str), CachedComputed>>;
+
+impl KeyMap {
+    fn with_computed<F, T>(
+        &self,
+        field: F,
+        name: &'static str,
+        fun: impl Fn(F) -> T + Send + Sync + 'static,
+    ) -> Computed<T>
+    where
+        F: DowngradeField + Send + Sync + 'static,
+        T: PartialEq + Send + Sync + 'static,
+    {
+        let key = (field.path().into_iter().collect::<StorePath>(), name);
+        if let Some(cached) = self.computed.read().or_poisoned().get(&key) {
+            if let Some(memo) = cached.memo.downcast_ref::<Computed<T>>() {
+                if (cached.exists)() {
+                    return memo.clone();
+                }
+            }
+        }
+
+        let weak = field.downgrade();
+        let memo = computed_memo(
+            field,
+            fun,
+            Some((Arc::downgrade(&self.computed), key.clone())),
+        );
+        let mut cache = self.computed.write().or_poisoned();
+        // drop the memos of any other fields that no longer exist
+        cache.retain(|_, cached| (cached.exists)());
+        cache.insert(
+            key,
+            CachedComputed {
+                memo: Box::new(memo.clone()),
+                exists: Box::new(move || F::upgrade(&weak).is_some()),
+            },
+        );
+        memo
+    }
+}
+
+/// Creates the memo for a computed field, which only holds the field weakly.
+///
+/// Once the field no longer exists, the memo keeps its last value, and removes itself from
+/// `cache`.
+fn computed_memo<F, T>(
+    field: F,
+    fun: impl Fn(F) -> T + Send + Sync + 'static,
+    cache: Option<(Weak<ComputedCache>, (StorePath, &'static str))>,
+) -> Computed<T>
+where
+    F: DowngradeField + Send + Sync + 'static,
+    T: PartialEq + Send + Sync + 'static,
+{
+    let weak = field.downgrade();
+    // the first value is computed from the field itself, which is released right after
+    let first = Mutex::new(Some(field));
+    let memo = Computed::new_owning(move |prev: Option<T>| {
+        let field = first
+            .lock()
+            .or_poisoned()
+            .take()
+            .or_else(|| F::upgrade(&weak));
+        match (field, prev) {
+            (Some(field), prev) => {
+                let value = fun(field);
+                let changed = prev.as_ref() != Some(&value);
+                (value, changed)
+            }
+            (None, Some(prev)) => {
+                if let Some((cache, key)) = &cache {
+                    if let Some(cache) = cache.upgrade() {
+                        let mut cache = cache.write().or_poisoned();
+                        if cache
+                            .get(key)
+                            .is_some_and(|cached| !(cached.exists)())
+                        {
+                            cache.remove(key);
+                        }
+                    }
+                }
+                (prev, false)
+            }
+            (None, None) => unreachable!(
+                "the first value of a computed field is computed from its field"
+            ),
+        }
+    });
+    memo.with_untracked(|_| {});
+    memo
+}
+
+/// Used by the code generated by `#[store(computed(...))]`, so that computed fields of nested
+/// structs can read their fields reactively, and by the implementations of `JsonField` and
+/// `JsonPatchField`.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "serde")]
+    pub use crate::json_field::{keyed_index, SerializeJson};
+    #[cfg(feature = "serde")]
+    pub use crate::json_patch::{
+        edit_keyed, edit_whole, DeserializeJson, JsonEdit, JsonEditError,
+        JsonEdited,
+    };
+    pub use crate::store_field::{variant_index, DowngradeField};
+    pub use reactive_graph::traits::{IsDisposed, Track};
+    #[cfg(feature = "serde")]
+    pub use serde_json;
+}
+
+/// Returns the cached memo for a computed field, creating it if it does not exist.
+///
+/// This is used by the code generated by `#[store(computed(...))]`.
+#[doc(hidden)]
+#[track_caller]
+pub fn computed_field<F, T>(
+    field: F,
+    name: &'static str,
+    fun: impl Fn(F) -> T + Send + Sync + 'static,
+) -> Computed<T>
+where
+    F: DowngradeField + Send + Sync + 'static,
+    T: PartialEq + Send + Sync + 'static,
+{
+    match field.keys() {
+        Some(keys) => keys.with_computed(field, name, fun),
+        // the store has been disposed, so there is nowhere to cache the memo
+        None => computed_memo(field, fun, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as reactive_stores, ArcStore, Store, StoreField,
+        StoreFieldIterator,
+    };
+    use reactive_graph::traits::{Get, Read, Set, Write};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Store)]
+    #[store(computed(remaining: usize = |todos| {
+        RUNS.fetch_add(1, Ordering::Relaxed);
+        todos
+            .todos()
+            .iter_unkeyed()
+            .filter(|todo| !todo.clone().completed().get())
+            .count()
+    }))]
+    struct Todos {
+        user: String,
+        todos: Vec<Todo>,
+    }
+
+    #[derive(Debug, Store)]
+    #[store(
+        computed(summary: String = |todo| {
+            format!("{} ({})", todo.clone().label().read(), todo.completed().get())
+        }),
+        computed(label_len: usize = |todo| todo.label().read().len())
+    )]
+    struct Todo {
+        label: String,
+        completed: bool,
+    }
+
+    fn data() -> Todos {
+        Todos {
+            user: "Bob".to_string(),
+            todos: vec![
+                Todo {
+                    label: "Create reactive store".to_string(),
+                    completed: true,
+                },
+                Todo {
+                    label: "Add computed fields".to_string(),
+                    completed: false,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn computed_fields_are_cached_and_only_track_what_they_read() {
+        let store = Store::new(data());
+        let before = RUNS.load(Ordering::Relaxed);
+        let remaining = store.remaining();
+
+        assert_eq!(remaining.get(), 1);
+        // the same memo is returned for the same store
+        assert_eq!(store.remaining().get(), 1);
+        assert_eq!(RUNS.load(Ordering::Relaxed), before + 1);
+
+        // fields that are not read do not cause it to recalculate
+        store.user().set("Carol".to_string());
+        assert_eq!(store.remaining().get(), 1);
+        assert_eq!(RUNS.load(Ordering::Relaxed), before + 1);
+
+        store.todos().at_unkeyed(0).completed().set(false);
+        assert_eq!(remaining.get(), 2);
+        assert_eq!(RUNS.load(Ordering::Relaxed), before + 2);
+
+        // each store has its own memo
+        let other = Store::new(data());
+        assert_eq!(other.remaining().get(), 1);
+    }
+
+    #[test]
+    fn computed_fields_of_nested_fields() {
+        let store = Store::new(data());
+        let first = store.todos().at_unkeyed(0);
+        let second = store.todos().at_unkeyed(1);
+        assert_eq!(first.summary().get(), "Create reactive store (true)");
+        assert_eq!(second.summary().get(), "Add computed fields (false)");
+        assert_eq!(second.label_len().get(), 19);
+
+        second.label().set("Done".to_string());
+        assert_eq!(second.summary().get(), "Done (false)");
+        assert_eq!(second.label_len().get(), 4);
+    }
+
+    #[test]
+    fn computed_fields_do_not_keep_an_arc_store_alive() {
+        let store = ArcStore::new(data());
+        let value = Arc::downgrade(&store.value);
+        let remaining = store.clone().remaining();
+        assert_eq!(remaining.get(), 1);
+
+        drop(store);
+        assert!(value.upgrade().is_none());
+        // the memo keeps its last value
+        assert_eq!(remaining.get(), 1);
+    }
+
+    #[test]
+    fn computed_fields_of_removed_items_are_evicted() {
+        let store = Store::new(data());
+        let cached = || store.keys().unwrap().computed.read().unwrap().len();
+        let label_len = store.todos().at_unkeyed(1).label_len();
+        assert_eq!(label_len.get(), 19);
+        assert_eq!(cached(), 1);
+
+        store.todos().write().pop();
+        // caching another memo drops the memo of the removed item
+        assert_eq!(store.todos().at_unkeyed(0).label_len().get(), 21);
+        assert_eq!(cached(), 1);
+        // and the memo keeps its last value
+        assert_eq!(label_len.get(), 19);
+    }
+}
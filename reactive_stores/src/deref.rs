@@ -1,10 +1,10 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    store_field::StoreField,
+    store_field::{record_change, this_trigger, DowngradeField, StoreField},
     KeyMap, StoreFieldTrigger,
 };
 use reactive_graph::{
-    signal::guards::{Mapped, MappedMut},
+    signal::guards::{Mapped, MappedMut, WriteGuard},
     traits::{
         DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
         Write,
@@ -85,6 +85,31 @@ where
     }
 }
 
+impl<S> DowngradeField for DerefedField<S>
+where
+    S: DowngradeField,
+    S::Value: Deref + DerefMut,
+    <S::Value as Deref>::Target: Sized + 'static,
+{
+    type Weak = DerefedField<S::Weak>;
+
+    fn downgrade(&self) -> Self::Weak {
+        DerefedField {
+            inner: self.inner.downgrade(),
+            #[cfg(debug_assertions)]
+            defined_at: self.defined_at,
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        Some(DerefedField {
+            inner: S::upgrade(&weak.inner)?,
+            #[cfg(debug_assertions)]
+            defined_at: weak.defined_at,
+        })
+    }
+}
+
 impl<S> DefinedAt for DerefedField<S>
 where
     S: StoreField,
@@ -117,6 +142,7 @@ where
     <S::Value as Deref>::Target: Sized + 'static,
 {
     fn notify(&self) {
+        record_change(self);
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
@@ -152,7 +178,9 @@ where
     type Value = <S::Value as Deref>::Target;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        record_change(self);
+        let this = this_trigger(self);
+        self.writer().map(|writer| WriteGuard::new(this, writer))
     }
     fn try_write_untracked(
         &self,
@@ -1,11 +1,13 @@
 use crate::{
     arc_field::{StoreFieldReader, StoreFieldWriter},
     path::{StorePath, StorePathSegment},
+    store_field::{record_change, this_trigger, DowngradeField},
     ArcField, ArcStore, AtIndex, AtKeyed, DerefedField, KeyMap, KeyedSubfield,
     Store, StoreField, StoreFieldTrigger, Subfield,
 };
 use reactive_graph::{
     owner::{ArenaItem, Storage, SyncStorage},
+    signal::guards::WriteGuard,
     traits::{
         DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
         Write,
@@ -172,6 +174,40 @@ where
     }
 }
 
+impl<T, S> Field<T, S>
+where
+    S: Storage<ArcField<T>>,
+{
+    /// Returns a field that views this field's value as a different type, through a pair of
+    /// functions that convert to and from it.
+    ///
+    /// See [`ArcField::lens`].
+    ///
+    /// # Panics
+    /// Panics if this field has been disposed.
+    #[track_caller]
+    pub fn lens<U>(
+        &self,
+        get: impl Fn(&T) -> U + Send + Sync + 'static,
+        set: impl Fn(&mut T, U) + Send + Sync + 'static,
+    ) -> Field<U, S>
+    where
+        U: 'static,
+        S: Storage<ArcField<U>>,
+    {
+        let inner = self
+            .inner
+            .try_get_value()
+            .map(|inner| inner.lens(get, set))
+            .unwrap_or_else(unwrap_signal!(self));
+        Field {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner: ArenaItem::new_with_storage(inner),
+        }
+    }
+}
+
 impl<T, S> Clone for Field<T, S> {
     fn clone(&self) -> Self {
         *self
@@ -180,6 +216,22 @@ impl<T, S> Clone for Field<T, S> {
 
 impl<T, S> Copy for Field<T, S> {}
 
+impl<T, S> DowngradeField for Field<T, S>
+where
+    S: Storage<ArcField<T>> + 'static,
+{
+    // the arena already holds the field weakly
+    type Weak = Self;
+
+    fn downgrade(&self) -> Self::Weak {
+        *self
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        (!weak.is_disposed()).then_some(*weak)
+    }
+}
+
 impl<T, S> DefinedAt for Field<T, S> {
     fn defined_at(&self) -> Option<&'static Location<'static>> {
         #[cfg(any(debug_assertions, leptos_debuginfo))]
@@ -232,7 +284,11 @@ impl<T> Write for Field<T> {
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.inner.try_get_value().and_then(|inner| (inner.write)())
+        self.inner.try_get_value().and_then(|inner| {
+            record_change(&inner);
+            let this = this_trigger(&inner);
+            (inner.write)().map(|writer| WriteGuard::new(this, writer))
+        })
     }
 
     fn try_write_untracked(
@@ -0,0 +1,719 @@
+use crate::{
+    path::{StorePath, StorePathSegment},
+    FieldKeys, KeyMap, Store, StoreField,
+};
+use reactive_graph::{
+    computed::Memo,
+    signal::RwSignal,
+    traits::{
+        Get, GetUntracked, Read, ReadUntracked, Set, Track, Update, With,
+        WithUntracked,
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Debug, Formatter},
+    hash::Hash,
+};
+
+/// Describes a type whose value can be checked for errors, reported by the path of each invalid
+/// field.
+///
+/// This is implemented by `#[derive(Store)]` for structs with validated fields, declared with
+/// `#[store(validate = <expr>)]`. The expression should be a function that takes a reference to
+/// the field's value and returns a `Result<(), E>`, where `E` implements `ToString`. Fields whose
+/// type itself implements `Validate`, like nested structs, can be validated with
+/// `#[store(validate)]`.
+///
+/// It can also be implemented by hand, for example to check several fields at once, or with an
+/// empty `impl` for a type without any validation.
+///
+/// Items in a `Vec` are validated with the path of [`at_unkeyed`](crate::StoreFieldIterator::at_unkeyed),
+/// unless it is a keyed field (`#[store(key: ...)]`) validated by a [`Form`], in which case they
+/// are validated with the path of the [`AtKeyed`](crate::AtKeyed) field for their key.
+pub trait Validate {
+    /// Adds an error to `errors` for each invalid field of this value, which is at `path`.
+    fn validate(&self, path: &StorePath, errors: &mut FormErrors) {
+        _ = (path, errors);
+    }
+
+    /// Adds an error to `errors` for each invalid field at or below `field`, a path relative to
+    /// this value, which is at `path`.
+    ///
+    /// This allows a [`Form`] to validate a single field without running every validator. By
+    /// default, the whole value is validated, and only the errors for `field` are kept.
+    fn validate_field(
+        &self,
+        path: &StorePath,
+        field: &[StorePathSegment],
+        errors: &mut FormErrors,
+    ) {
+        let mut all = FormErrors::with_keys(errors.keys.clone());
+        self.validate(path, &mut all);
+        let len = path.as_slice().len();
+        for (error_path, messages) in all.errors {
+            let segments = error_path.as_slice();
+            if segments.len() >= len + field.len()
+                && segments[len..].starts_with(field)
+            {
+                errors
+                    .errors
+                    .entry(error_path)
+                    .or_default()
+                    .extend(messages);
+            }
+        }
+    }
+}
+
+impl<T> Validate for Vec<T>
+where
+    T: Validate,
+{
+    fn validate(&self, path: &StorePath, errors: &mut FormErrors) {
+        let mut path = path.to_owned();
+        path.push(0);
+        for (idx, item) in self.iter().enumerate() {
+            path.replace_last(idx);
+            item.validate(&path, errors);
+        }
+    }
+
+    fn validate_field(
+        &self,
+        path: &StorePath,
+        field: &[StorePathSegment],
+        errors: &mut FormErrors,
+    ) {
+        let Some((segment, field)) = field.split_first() else {
+            return self.validate(path, errors);
+        };
+        if let Some(item) = self.get(segment.0) {
+            let mut path = path.to_owned();
+            path.push(*segment);
+            item.validate_field(&path, field, errors);
+        }
+    }
+}
+
+impl<T> Validate for Option<T>
+where
+    T: Validate,
+{
+    fn validate(&self, path: &StorePath, errors: &mut FormErrors) {
+        if let Some(inner) = self {
+            // matches the path of the field returned by `OptionStoreExt::unwrap`
+            let mut path = path.to_owned();
+            path.push(0);
+            inner.validate(&path, errors);
+        }
+    }
+
+    fn validate_field(
+        &self,
+        path: &StorePath,
+        field: &[StorePathSegment],
+        errors: &mut FormErrors,
+    ) {
+        match (self, field.split_first()) {
+            (_, None) => self.validate(path, errors),
+            (Some(inner), Some((segment, field))) if segment.0 == 0 => {
+                let mut path = path.to_owned();
+                path.push(*segment);
+                inner.validate_field(&path, field, errors);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Runs the validator for a field, adding its error if it fails.
+///
+/// This is used by the code generated by `#[store(validate = ...)]`.
+#[doc(hidden)]
+pub fn validate_field<T, E>(
+    value: &T,
+    path: StorePath,
+    errors: &mut FormErrors,
+    validator: impl FnOnce(&T) -> Result<(), E>,
+) where
+    E: ToString,
+{
+    if let Err(error) = validator(value) {
+        errors.insert(path, error);
+    }
+}
+
+/// Validates the items of a keyed field, with the path of the [`AtKeyed`](crate::AtKeyed) field
+/// for each item, or only the item at the start of `field` if it is not empty.
+///
+/// This is used by the code generated by `#[store(key: ..., validate)]`.
+#[doc(hidden)]
+pub fn validate_keyed<T, I, K>(
+    items: &T,
+    key_fn: fn(<&T as IntoIterator>::Item) -> K,
+    path: &StorePath,
+    field: &[StorePathSegment],
+    errors: &mut FormErrors,
+) where
+    for<'a> &'a T: IntoIterator<Item = &'a I>,
+    I: Validate + 'static,
+    K: Debug + Hash + Eq + Send + Sync + 'static,
+{
+    // the keys of the store are only read here, as validation runs while the errors are being
+    // read: they are created and updated when the field is accessed or written. Without them,
+    // items are validated by their index
+    let segments = errors
+        .keys
+        .as_ref()
+        .and_then(|keys| {
+            keys.read_field_keys(path, |keys: &FieldKeys<K>| {
+                items
+                    .into_iter()
+                    .map(|item| {
+                        keys.get(&key_fn(item)).map(|(segment, _)| segment)
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+        })
+        .flatten()
+        .unwrap_or_else(|| {
+            (0..items.into_iter().count()).map(Into::into).collect()
+        });
+
+    let rest = field.split_first();
+    for (item, segment) in items.into_iter().zip(segments) {
+        let mut item_path = path.to_owned();
+        item_path.push(segment);
+        match rest {
+            None => item.validate(&item_path, errors),
+            Some((field_segment, field)) if *field_segment == segment => {
+                return item.validate_field(&item_path, field, errors);
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+/// The validation errors for each field of a form, by the path of the field.
+#[derive(Clone, Default)]
+pub struct FormErrors {
+    errors: HashMap<StorePath, Vec<String>>,
+    /// The keys of the store being validated, which give the paths of the items of keyed fields.
+    keys: Option<KeyMap>,
+}
+
+impl Debug for FormErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("FormErrors").field(&self.errors).finish()
+    }
+}
+
+impl PartialEq for FormErrors {
+    fn eq(&self, other: &Self) -> bool {
+        self.errors == other.errors
+    }
+}
+
+impl Eq for FormErrors {}
+
+impl FormErrors {
+    fn with_keys(keys: Option<KeyMap>) -> Self {
+        Self {
+            errors: HashMap::new(),
+            keys,
+        }
+    }
+
+    /// Adds an error for the field at the given path.
+    pub fn insert(&mut self, path: StorePath, error: impl ToString) {
+        self.errors.entry(path).or_default().push(error.to_string());
+    }
+
+    /// Returns the errors for the field at the given path.
+    pub fn get(&self, path: &StorePath) -> &[String] {
+        self.errors.get(path).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Returns `true` if there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Iterates over the paths of invalid fields and their errors.
+    pub fn iter(&self) -> impl Iterator<Item = (&StorePath, &[String])> {
+        self.errors
+            .iter()
+            .map(|(path, errors)| (path, errors.as_slice()))
+    }
+}
+
+/// The state of a form, backed by a [`Store`](struct@crate::Store).
+///
+/// A form keeps a baseline copy of its value, which is its initial value until
+/// [`commit`](Form::commit) is called, and tracks which fields are dirty (have a different value
+/// from the baseline), which fields have been touched, and the errors from validating the value
+/// with [`Validate`].
+///
+/// The state of an individual field can be accessed with [`field`](Form::field), which creates
+/// memos that only update when that field changes.
+///
+/// ```rust
+/// use reactive_graph::traits::{Get, Set};
+/// use reactive_stores::{Form, Store};
+///
+/// #[derive(Store, Clone, PartialEq)]
+/// struct SignUp {
+///     #[store(validate = |name: &String| {
+///         if name.is_empty() { Err("Please enter a name.") } else { Ok(()) }
+///     })]
+///     name: String,
+///     newsletter: bool,
+/// }
+///
+/// let form = Form::new(SignUp {
+///     name: String::new(),
+///     newsletter: false,
+/// });
+/// let name = form.field(|form| form.name());
+/// assert!(!form.is_valid());
+/// assert_eq!(name.errors(), vec!["Please enter a name."]);
+///
+/// name.field().set("Alice".to_string());
+/// name.touch();
+/// assert!(form.is_valid());
+/// assert!(name.is_dirty() && name.is_touched());
+///
+/// form.reset();
+/// assert!(!name.is_dirty() && !name.is_touched());
+/// ```
+pub struct Form<T>
+where
+    T: Send + Sync + 'static,
+{
+    store: Store<T>,
+    baseline: Store<T>,
+    touched: RwSignal<HashSet<StorePath>>,
+    errors: Memo<FormErrors>,
+}
+
+impl<T> Clone for Form<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Form<T> where T: Send + Sync + 'static {}
+
+impl<T> Form<T>
+where
+    T: Validate + Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Creates a form with the given value as its initial value and baseline.
+    pub fn new(value: T) -> Self {
+        let store = Store::new(value.clone());
+        let errors = Memo::new(move |_| {
+            let mut errors = FormErrors::with_keys(store.keys());
+            store.read().validate(&StorePath::default(), &mut errors);
+            errors
+        });
+        Self {
+            store,
+            baseline: Store::new(value),
+            touched: RwSignal::new(HashSet::new()),
+            errors,
+        }
+    }
+
+    /// The store that holds the current value of the form.
+    pub fn store(&self) -> Store<T> {
+        self.store
+    }
+
+    /// Returns the state of one field of the form, which is selected by a function that returns a
+    /// field of the store passed to it.
+    ///
+    /// The function is also called with a store holding the baseline, to find the field's
+    /// baseline value, so it should not have any side effects.
+    pub fn field<F>(&self, field: impl Fn(Store<T>) -> F) -> FormField<F>
+    where
+        F: StoreField + Track + Clone + Send + Sync + 'static,
+        F::Value: PartialEq,
+    {
+        let baseline = field(self.baseline);
+        let field = field(self.store);
+        let path = field.path().into_iter().collect::<StorePath>();
+
+        let dirty = Memo::new({
+            let field = field.clone();
+            move |_| {
+                field.track();
+                baseline.track();
+                match (field.reader(), baseline.reader()) {
+                    (Some(current), Some(baseline)) => *current != *baseline,
+                    _ => false,
+                }
+            }
+        });
+        let touched = Memo::new({
+            let touched = self.touched;
+            let path = path.clone();
+            move |_| touched.with(|touched| touched.contains(&path))
+        });
+        // only this field's validators are run, when this field changes
+        let errors = Memo::new({
+            let store = self.store;
+            let field = field.clone();
+            let path = path.clone();
+            move |_| {
+                field.track();
+                let mut errors = FormErrors::with_keys(store.keys());
+                store.with_untracked(|value| {
+                    value.validate_field(
+                        &StorePath::default(),
+                        path.as_slice(),
+                        &mut errors,
+                    )
+                });
+                errors.get(&path).to_vec()
+            }
+        });
+
+        FormField {
+            field,
+            path,
+            dirty,
+            touched,
+            touched_paths: self.touched,
+            errors,
+        }
+    }
+
+    /// Returns `true` if the value of the form is different from its baseline.
+    pub fn is_dirty(&self) -> bool {
+        *self.store.read() != *self.baseline.read()
+    }
+
+    /// Returns `true` if any field of the form has been touched.
+    pub fn is_touched(&self) -> bool {
+        self.touched.with(|touched| !touched.is_empty())
+    }
+
+    /// Returns `true` if there are no validation errors.
+    pub fn is_valid(&self) -> bool {
+        self.errors.with(FormErrors::is_empty)
+    }
+
+    /// Returns the validation errors for every field of the form.
+    pub fn errors(&self) -> FormErrors {
+        self.errors.get()
+    }
+
+    /// Resets the value of the form to its baseline, and marks every field as untouched.
+    pub fn reset(&self) {
+        self.store.set(self.baseline.read_untracked().clone());
+        self.touched.update(HashSet::clear);
+    }
+
+    /// Sets the baseline to the current value of the form, so that no fields are dirty, and marks
+    /// every field as untouched.
+    ///
+    /// This can be called once the value of the form has been saved.
+    pub fn commit(&self) {
+        self.baseline.set(self.store.get_untracked());
+        self.touched.update(HashSet::clear);
+    }
+}
+
+/// The state of one field of a [`Form`].
+#[derive(Debug)]
+pub struct FormField<F> {
+    field: F,
+    path: StorePath,
+    dirty: Memo<bool>,
+    touched: Memo<bool>,
+    touched_paths: RwSignal<HashSet<StorePath>>,
+    errors: Memo<Vec<String>>,
+}
+
+impl<F> Clone for FormField<F>
+where
+    F: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            field: self.field.clone(),
+            path: self.path.clone(),
+            dirty: self.dirty,
+            touched: self.touched,
+            touched_paths: self.touched_paths,
+            errors: self.errors,
+        }
+    }
+}
+
+impl<F> FormField<F>
+where
+    F: Clone,
+{
+    /// The store field, which can be used to read or update the value.
+    pub fn field(&self) -> F {
+        self.field.clone()
+    }
+
+    /// Returns `true` if the field has a different value from its baseline.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.get()
+    }
+
+    /// Returns `true` if the field has been touched, usually because it has lost focus.
+    pub fn is_touched(&self) -> bool {
+        self.touched.get()
+    }
+
+    /// Marks the field as touched.
+    pub fn touch(&self) {
+        let touched = self
+            .touched_paths
+            .with_untracked(|touched| touched.contains(&self.path));
+        if !touched {
+            self.touched_paths.update(|touched| {
+                touched.insert(self.path.clone());
+            });
+        }
+    }
+
+    /// Returns the validation errors for this field.
+    ///
+    /// Only the validators for this field and the fields nested in it are run, when it changes.
+    pub fn errors(&self) -> Vec<String> {
+        self.errors.get()
+    }
+
+    /// Returns `true` if there are no validation errors for this field.
+    pub fn is_valid(&self) -> bool {
+        self.errors.with(Vec::is_empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Form, Validate};
+    use crate::{
+        self as reactive_stores, AtKeyed, Store, StoreField, StorePath,
+    };
+    use reactive_graph::{
+        effect::Effect,
+        traits::{GetUntracked, Set, Write},
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    fn not_empty(value: &str) -> Result<(), &'static str> {
+        if value.trim().is_empty() {
+            Err("required")
+        } else {
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Store, Clone, PartialEq)]
+    struct Profile {
+        #[store(validate = |name| not_empty(name))]
+        name: String,
+        #[store(validate = |age: &u8| if *age < 18 { Err("too young") } else { Ok(()) })]
+        age: u8,
+        #[store(validate)]
+        address: Address,
+        #[store(key: String = |phone| phone.number.clone(), validate)]
+        phones: Vec<Phone>,
+    }
+
+    #[derive(Debug, Store, Clone, PartialEq)]
+    struct Address {
+        #[store(validate = |city| not_empty(city), validate = |city: &String| {
+            if city.len() > 10 { Err("too long") } else { Ok(()) }
+        })]
+        city: String,
+    }
+
+    #[derive(Debug, Store, Clone, PartialEq)]
+    struct Phone {
+        #[store(validate = |number| not_empty(number))]
+        number: String,
+    }
+
+    static NAME_CHECKS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Debug, Store, Clone, PartialEq)]
+    struct Account {
+        #[store(validate = |name| {
+            NAME_CHECKS.fetch_add(1, Ordering::Relaxed);
+            not_empty(name)
+        })]
+        name: String,
+        #[store(validate = |email| not_empty(email))]
+        email: String,
+    }
+
+    fn data() -> Profile {
+        Profile {
+            name: "Alice".to_string(),
+            age: 30,
+            address: Address {
+                city: "Paris".to_string(),
+            },
+            phones: vec![Phone {
+                number: "555".to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn validates_nested_fields_by_path() {
+        let mut value = data();
+        value.name = " ".to_string();
+        value.address.city = "Llanfairpwllgwyngyll".to_string();
+        value.phones.push(Phone {
+            number: String::new(),
+        });
+
+        let mut errors = super::FormErrors::default();
+        value.validate(&StorePath::default(), &mut errors);
+        assert_eq!(errors.get(&vec![0.into()].into()), ["required"]);
+        assert_eq!(errors.get(&vec![1.into()].into()), [] as [String; 0]);
+        assert_eq!(errors.get(&vec![2.into(), 0.into()].into()), ["too long"]);
+        assert_eq!(
+            errors.get(&vec![3.into(), 1.into(), 0.into()].into()),
+            ["required"]
+        );
+        assert_eq!(errors.iter().count(), 3);
+    }
+
+    #[test]
+    fn tracks_dirty_touched_and_errors_of_fields() {
+        let form = Form::new(data());
+        let name = form.field(|profile| profile.name());
+        let city = form.field(|profile| profile.address().city());
+        let age = form.field(|profile| profile.age());
+        assert!(form.is_valid() && !form.is_dirty() && !form.is_touched());
+
+        city.field().set(String::new());
+        city.touch();
+        assert!(city.is_dirty() && city.is_touched());
+        assert!(!name.is_dirty() && !name.is_touched());
+        assert_eq!(city.errors(), vec!["required"]);
+        assert!(name.is_valid() && !city.is_valid() && !form.is_valid());
+
+        // changing a value back to the baseline means it is no longer dirty
+        city.field().set("Paris".to_string());
+        assert!(!city.is_dirty() && city.is_touched() && form.is_valid());
+
+        age.field().set(16);
+        assert_eq!(age.errors(), vec!["too young"]);
+        form.reset();
+        assert!(!age.is_dirty() && age.is_valid() && !form.is_touched());
+
+        // committing sets a new baseline
+        name.field().set("Bob".to_string());
+        assert!(form.is_dirty());
+        form.commit();
+        assert!(!name.is_dirty() && !form.is_dirty());
+        form.reset();
+        assert_eq!(form.store().name().get_untracked(), "Bob");
+    }
+
+    #[tokio::test]
+    async fn dirty_state_only_notifies_when_it_changes() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let form = Form::new(data());
+        let name = form.field(|profile| profile.name());
+        let runs = Arc::new(AtomicUsize::new(0));
+        Effect::new_sync({
+            let runs = Arc::clone(&runs);
+            let name = name.clone();
+            move |_| {
+                name.is_dirty();
+                runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        crate::tests::tick().await;
+
+        // other fields do not affect this field
+        form.store().age().set(40);
+        form.store().phones().write().clear();
+        crate::tests::tick().await;
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        // and it only reruns when the field becomes dirty, not on every change
+        name.field().set("Bob".to_string());
+        crate::tests::tick().await;
+        name.field().set("Carol".to_string());
+        crate::tests::tick().await;
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn validates_keyed_items_by_their_key() {
+        let form = Form::new(data());
+        let phones = form.store().phones();
+        let first = form.field(|profile| {
+            AtKeyed::new(profile.phones(), "555".to_string()).number()
+        });
+        assert!(first.is_valid());
+
+        phones.write().insert(
+            0,
+            Phone {
+                number: " ".to_string(),
+            },
+        );
+        let blank = form.field(|profile| {
+            AtKeyed::new(profile.phones(), " ".to_string()).number()
+        });
+
+        // the new item is at index 0, but has a new path segment for its key
+        let path: StorePath = AtKeyed::new(phones, " ".to_string())
+            .number()
+            .path()
+            .into_iter()
+            .collect();
+        assert_ne!(path, vec![3.into(), 0.into(), 0.into()].into());
+        assert_eq!(blank.errors(), vec!["required"]);
+        assert!(first.is_valid());
+        assert_eq!(form.errors().get(&path), ["required"]);
+        assert_eq!(form.errors().iter().count(), 1);
+
+        first.field().set(String::new());
+        assert_eq!(first.errors(), vec!["required"]);
+    }
+
+    #[test]
+    fn only_runs_the_validators_of_each_field() {
+        let form = Form::new(Account {
+            name: "Alice".to_string(),
+            email: String::new(),
+        });
+        let name = form.field(|account| account.name());
+        let email = form.field(|account| account.email());
+        assert!(name.is_valid());
+        assert_eq!(email.errors(), vec!["required"]);
+        let checks = NAME_CHECKS.load(Ordering::Relaxed);
+
+        for email_value in ["a", "al", "alice@example.com"] {
+            email.field().set(email_value.to_string());
+            assert!(email.is_valid() && name.is_valid());
+        }
+        assert_eq!(NAME_CHECKS.load(Ordering::Relaxed), checks);
+
+        name.field().set(String::new());
+        assert_eq!(name.errors(), vec!["required"]);
+        assert_eq!(NAME_CHECKS.load(Ordering::Relaxed), checks + 1);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    store_field::StoreField,
+    store_field::{record_change, this_trigger, DowngradeField, StoreField},
     KeyMap, StoreFieldTrigger,
 };
 use reactive_graph::{
@@ -110,6 +110,40 @@ where
     }
 }
 
+impl<Inner, Prev> DowngradeField for AtIndex<Inner, Prev>
+where
+    Inner: DowngradeField<Value = Prev>,
+    Prev: IndexMut<usize> + AsRef<[Prev::Output]> + Send + Sync + 'static,
+    Prev::Output: Sized,
+{
+    type Weak = AtIndex<Inner::Weak, Prev>;
+
+    fn downgrade(&self) -> Self::Weak {
+        AtIndex {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            inner: self.inner.downgrade(),
+            index: self.index,
+            ty: self.ty,
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        let inner = Inner::upgrade(&weak.inner)?;
+        // the item is gone once the collection is shorter than its index
+        if weak.index >= inner.reader()?.as_ref().len() {
+            return None;
+        }
+        Some(AtIndex {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: weak.defined_at,
+            inner,
+            index: weak.index,
+            ty: weak.ty,
+        })
+    }
+}
+
 impl<Inner, Prev> DefinedAt for AtIndex<Inner, Prev>
 where
     Inner: StoreField<Value = Prev>,
@@ -142,6 +176,7 @@ where
     Prev::Output: Sized,
 {
     fn notify(&self) {
+        record_change(self);
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
     }
@@ -180,7 +215,9 @@ where
     type Value = Prev::Output;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        record_change(self);
+        let this = this_trigger(self);
+        self.writer().map(|writer| WriteGuard::new(this, writer))
     }
 
     fn try_write_untracked(
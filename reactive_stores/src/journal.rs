@@ -0,0 +1,444 @@
+use crate::{
+    path::{StorePath, StorePathSegment},
+    ArcStore, FieldCursor, JsonField, Store, StoreField,
+};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    owner::Storage,
+    signal::ArcTrigger,
+    traits::{DefinedAt, Notify, Track},
+    unwrap_signal,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    fmt::Display,
+    mem,
+    sync::{Arc, Mutex, RwLock, Weak},
+};
+
+/// A single change to the value of a store, recorded by a [`StoreJournal`].
+///
+/// ```rust
+/// # use reactive_stores::{StoreChange, StorePath};
+/// let change = StoreChange {
+///     path: StorePath::from(vec![1.into(), 3.into(), 1.into()]),
+///     name: "todos[3].completed".to_string(),
+///     value: serde_json::Value::Bool(true),
+/// };
+/// assert_eq!(change.to_string(), "todos[3].completed = true");
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoreChange {
+    /// The path of the field that changed, as used by the store's fields.
+    pub path: StorePath,
+    /// A readable version of the path, like `todos[3].completed`.
+    ///
+    /// This is empty if the whole store changed.
+    pub name: String,
+    /// The value of the field when the change was read.
+    pub value: Value,
+}
+
+impl Display for StoreChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = if self.name.is_empty() {
+            "(store)"
+        } else {
+            &self.name
+        };
+        write!(f, "{name} = {}", self.value)
+    }
+}
+
+/// Records the changes made to a store, as path-level patches with serializable values.
+///
+/// The journal records the path of each field that is written or notified, including the fields
+/// notified by [`Patch`](crate::Patch). Each call to [`changes`](StoreJournal::changes) returns
+/// the fields that have changed since the previous call, with their current values. Because it
+/// also tracks the journal, it can be called in an effect to send each change to a server, log
+/// it, or record it for undo.
+///
+/// The paths are the [`StorePath`]s of the fields that changed, so items of a keyed field are
+/// found by their key. A field is only reported once, and not at all if a field that contains it
+/// has also changed. If a field no longer exists, or is inside a value that is serialized as a
+/// whole (like a map), the nearest field that does exist is reported instead.
+///
+/// This requires the value of the store to implement [`JsonField`], which `#[derive(Store)]`
+/// implements for types that implement [`Serialize`].
+///
+/// ```rust
+/// use reactive_graph::traits::{Set, Write};
+/// use reactive_stores::{Store, StoreFieldIterator};
+/// use serde::Serialize;
+///
+/// #[derive(Store, Serialize)]
+/// struct Todos {
+///     user: String,
+///     todos: Vec<Todo>,
+/// }
+///
+/// #[derive(Store, Serialize)]
+/// struct Todo {
+///     label: String,
+///     completed: bool,
+/// }
+///
+/// let store = Store::new(Todos {
+///     user: "Alice".to_string(),
+///     todos: vec![Todo {
+///         label: "Write docs".to_string(),
+///         completed: false,
+///     }],
+/// });
+/// let mut journal = store.journal();
+///
+/// store.todos().at_unkeyed(0).completed().set(true);
+/// let changes = journal.changes().unwrap();
+/// assert_eq!(changes.len(), 1);
+/// assert_eq!(changes[0].to_string(), "todos[0].completed = true");
+///
+/// store.todos().write().clear();
+/// let changes = journal.changes().unwrap();
+/// assert_eq!(changes[0].to_string(), "todos = []");
+/// ```
+#[derive(Debug)]
+pub struct StoreJournal<T> {
+    store: ArcStore<T>,
+    log: Arc<JournalLog>,
+}
+
+/// The paths recorded by a [`StoreJournal`].
+#[derive(Debug, Default)]
+pub(crate) struct JournalLog {
+    paths: Mutex<Vec<StorePath>>,
+    trigger: ArcTrigger,
+}
+
+/// The journals that are recording the changes made to a store.
+#[derive(Debug, Default)]
+pub(crate) struct Journals(RwLock<Vec<Weak<JournalLog>>>);
+
+impl Journals {
+    fn add(&self, log: Weak<JournalLog>) {
+        self.0.write().or_poisoned().push(log);
+    }
+
+    fn remove(&self, log: &Weak<JournalLog>) {
+        self.0
+            .write()
+            .or_poisoned()
+            .retain(|other| !other.ptr_eq(log));
+    }
+
+    pub(crate) fn record(&self, path: impl FnOnce() -> StorePath) {
+        let journals = self.0.read().or_poisoned();
+        if journals.is_empty() {
+            return;
+        }
+        let path = path();
+        for log in journals.iter().filter_map(Weak::upgrade) {
+            log.paths.lock().or_poisoned().push(path.clone());
+            log.trigger.notify();
+        }
+    }
+}
+
+impl<T> StoreJournal<T>
+where
+    T: JsonField + 'static,
+{
+    /// Creates a journal that records changes made to the store after this call.
+    pub fn new(store: ArcStore<T>) -> Self {
+        let log = Arc::new(JournalLog::default());
+        store.keys.journals.add(Arc::downgrade(&log));
+        Self { store, log }
+    }
+
+    /// Returns the changes made to the store since the last call, and tracks the journal.
+    ///
+    /// Returns an error if the value of a field could not be serialized.
+    pub fn changes(&mut self) -> Result<Vec<StoreChange>, serde_json::Error> {
+        self.log.trigger.track();
+        let paths = mem::take(&mut *self.log.paths.lock().or_poisoned());
+        if paths.is_empty() {
+            return Ok(Vec::new());
+        }
+        let Some(value) = self.store.reader() else {
+            return Ok(Vec::new());
+        };
+
+        let mut changes = Vec::new();
+        for path in outermost(paths, StorePath::as_slice) {
+            let mut cursor = FieldCursor::new(self.store.keys());
+            let value = value.field_json(path.as_slice(), &mut cursor)?;
+            let (path, name) = cursor.into_parts();
+            changes.push(StoreChange { path, name, value });
+        }
+        // two paths may have led to the same value, if it is serialized as a whole
+        Ok(outermost(changes, |change| change.path.as_slice()))
+    }
+}
+
+impl<T> Drop for StoreJournal<T> {
+    fn drop(&mut self) {
+        let log = Arc::downgrade(&self.log);
+        self.store.keys.journals.remove(&log);
+    }
+}
+
+/// Keeps the first of each path, unless a path that contains it is also in `items`.
+fn outermost<T>(
+    items: Vec<T>,
+    path: impl Fn(&T) -> &[StorePathSegment],
+) -> Vec<T> {
+    let mut kept: Vec<T> = Vec::with_capacity(items.len());
+    for item in items {
+        let this = path(&item);
+        if kept.iter().any(|other| this.starts_with(path(other))) {
+            continue;
+        }
+        kept.retain(|other| !path(other).starts_with(this));
+        kept.push(item);
+    }
+    kept
+}
+
+impl<T> ArcStore<T>
+where
+    T: JsonField + 'static,
+{
+    /// Creates a [`StoreJournal`] that records the changes made to this store.
+    pub fn journal(&self) -> StoreJournal<T> {
+        StoreJournal::new(self.clone())
+    }
+}
+
+impl<T, S> Store<T, S>
+where
+    T: JsonField + 'static,
+    S: Storage<ArcStore<T>>,
+{
+    /// Creates a [`StoreJournal`] that records the changes made to this store.
+    ///
+    /// # Panics
+    /// Panics if the store has been disposed.
+    #[track_caller]
+    pub fn journal(&self) -> StoreJournal<T> {
+        self.inner
+            .try_get_value()
+            .map(|store| store.journal())
+            .unwrap_or_else(unwrap_signal!(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as reactive_stores, AtKeyed, OptionStoreExt, Patch, Store,
+        StoreFieldIterator, StorePath,
+    };
+    use reactive_graph::{
+        effect::Effect,
+        traits::{Set, Update, Write},
+    };
+    use serde::Serialize;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Debug, Store, Patch, Serialize, Default)]
+    struct Todos {
+        user: String,
+        todos: Vec<Todo>,
+    }
+
+    #[derive(Debug, Store, Patch, Serialize, Default)]
+    struct Todo {
+        label: String,
+        completed: bool,
+    }
+
+    #[derive(Debug, Store, Serialize)]
+    struct Settings {
+        theme: Option<String>,
+        tags: HashMap<String, u32>,
+    }
+
+    #[derive(Debug, Store, Serialize)]
+    struct Table {
+        #[store(skip)]
+        #[serde(skip)]
+        _cache: u32,
+        #[store(key: usize = |row| row.id)]
+        rows: Vec<Row>,
+    }
+
+    #[derive(Debug, Store, Serialize)]
+    struct Row {
+        id: usize,
+        label: String,
+    }
+
+    fn data() -> Todos {
+        Todos {
+            user: "Bob".to_string(),
+            todos: vec![Todo {
+                label: "Create reactive store".to_string(),
+                completed: true,
+            }],
+        }
+    }
+
+    fn names(changes: &[super::StoreChange]) -> Vec<String> {
+        changes.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn records_paths_of_changed_fields() {
+        let store = Store::new(data());
+        let mut journal = store.journal();
+        assert_eq!(journal.changes().unwrap(), vec![]);
+
+        store.user().set("Carol".to_string());
+        store.todos().at_unkeyed(0).completed().set(false);
+        store.todos().at_unkeyed(0).completed().set(true);
+        let changes = journal.changes().unwrap();
+        assert_eq!(
+            names(&changes),
+            vec!["user = \"Carol\"", "todos[0].completed = true"]
+        );
+        assert_eq!(
+            changes[1].path,
+            StorePath::from(vec![1.into(), 0.into(), 1.into()])
+        );
+
+        // patching records the fields that were notified
+        store.patch(Todos {
+            user: "Carol".to_string(),
+            todos: vec![],
+        });
+        assert_eq!(names(&journal.changes().unwrap()), vec!["todos = []"]);
+    }
+
+    #[test]
+    fn records_containing_fields_once() {
+        let store = Store::new(Settings {
+            theme: None,
+            tags: HashMap::new(),
+        });
+        let mut journal = store.journal();
+
+        store.theme().set(Some("dark".to_string()));
+        store.tags().update(|tags| {
+            tags.insert("a".to_string(), 1);
+        });
+        assert_eq!(
+            names(&journal.changes().unwrap()),
+            vec!["theme = \"dark\"", "tags = {\"a\":1}"]
+        );
+
+        store.theme().unwrap().write().push_str("er");
+        let changes = journal.changes().unwrap();
+        // the inner value of an `Option` has the path of `unwrap()`
+        assert_eq!(changes[0].path, StorePath::from(vec![0.into(), 0.into()]));
+        assert_eq!(names(&changes), vec!["theme = \"darker\""]);
+
+        let store = Store::new(data());
+        let mut journal = store.journal();
+        store
+            .todos()
+            .at_unkeyed(0)
+            .label()
+            .set("Changed".to_string());
+        store.todos().write().push(Todo::default());
+        assert_eq!(
+            names(&journal.changes().unwrap()),
+            vec![
+                "todos = [{\"completed\":true,\"label\":\"Changed\"},\
+                 {\"completed\":false,\"label\":\"\"}]"
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_keyed_fields_by_their_key() {
+        let store = Store::new(Table {
+            _cache: 0,
+            rows: vec![
+                Row {
+                    id: 1,
+                    label: "a".to_string(),
+                },
+                Row {
+                    id: 2,
+                    label: "b".to_string(),
+                },
+            ],
+        });
+        // creates the keys, so that the rows have the segments of their initial indices
+        AtKeyed::new(store.rows(), 1).label().set("a".to_string());
+        let mut journal = store.journal();
+
+        store.rows().write().insert(
+            0,
+            Row {
+                id: 3,
+                label: "c".to_string(),
+            },
+        );
+        assert_eq!(journal.changes().unwrap()[0].name, "rows");
+
+        AtKeyed::new(store.rows(), 2).label().set("b!".to_string());
+        let changes = journal.changes().unwrap();
+        assert_eq!(names(&changes), vec!["rows[2].label = \"b!\""]);
+        // the skipped field has no path, but keeps the index of the fields after it
+        assert_eq!(
+            changes[0].path,
+            StorePath::from(vec![1.into(), 1.into(), 1.into()])
+        );
+
+        // a row that has been removed is reported as a change to the rows
+        let row = AtKeyed::new(store.rows(), 1);
+        row.label().set("gone".to_string());
+        store.rows().write().remove(1);
+        assert_eq!(
+            names(&journal.changes().unwrap()),
+            vec![
+                "rows = [{\"id\":3,\"label\":\"c\"},{\"id\":2,\"label\":\"b!\"}]"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn changes_can_be_observed_in_an_effect() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        Effect::new_sync({
+            let seen = Arc::clone(&seen);
+            let mut journal = store.journal();
+            move |_| {
+                let changes = journal.changes().unwrap();
+                seen.lock().unwrap().extend(names(&changes));
+            }
+        });
+        crate::tests::tick().await;
+
+        store.user().set("Dave".to_string());
+        crate::tests::tick().await;
+        store
+            .todos()
+            .at_unkeyed(0)
+            .label()
+            .set("Ship it".to_string());
+        crate::tests::tick().await;
+
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec!["user = \"Dave\"", "todos[0].label = \"Ship it\""]
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{
+    path::{StorePath, StorePathSegment},
+    KeyMap,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
+        NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64,
+        NonZeroU8, NonZeroUsize,
+    },
+};
+
+/// A value whose fields can be found by their [`StorePath`], and serialized to JSON.
+///
+/// With the `serde` feature, this is implemented by `#[derive(Store)]` for any type that
+/// implements [`Serialize`], as long as the types of its fields implement `JsonField` too. Keyed
+/// fields (`#[store(key: ...)]`) are found by their key, and fields skipped with
+/// `#[store(skip)]` have no path.
+///
+/// It is also implemented for `Option`, `Box`, `Vec` and `VecDeque`, whose paths match their
+/// store fields, and for primitives, strings, maps and sets, which are always serialized as a
+/// whole.
+///
+/// The `M` parameter only allows the derived implementations to depend on the types of their
+/// fields, and should be left as the default.
+pub trait JsonField<M = ()> {
+    /// Serializes the field at `path`, relative to this value, and moves `cursor` to it.
+    ///
+    /// If the path leads into a value that is serialized as a whole, or to a field that no
+    /// longer exists, the nearest value that does exist is serialized, and the cursor stops
+    /// there.
+    fn field_json(
+        &self,
+        path: &[StorePathSegment],
+        cursor: &mut FieldCursor,
+    ) -> Result<Value, serde_json::Error>;
+
+    /// Serializes the field at `path`, relative to the item at `index` in this collection.
+    ///
+    /// `segment` is the path segment of the item, which is its index for unkeyed collections.
+    /// Returns `None` if this is not a collection, or it has no such item.
+    #[doc(hidden)]
+    fn item_json(
+        &self,
+        segment: StorePathSegment,
+        index: usize,
+        path: &[StorePathSegment],
+        cursor: &mut FieldCursor,
+    ) -> Option<Result<Value, serde_json::Error>> {
+        _ = (segment, index, path, cursor);
+        None
+    }
+}
+
+/// The location of the field found by [`JsonField::field_json`].
+pub struct FieldCursor {
+    pub(crate) keys: Option<KeyMap>,
+    path: StorePath,
+    name: String,
+    /// The segment of the next item, if it is an item of a keyed field, or `None` if its key has
+    /// no segment yet.
+    pub(crate) keyed_item: Option<Option<StorePathSegment>>,
+    /// The length of the path that can still be notified, once the cursor has moved to an item
+    /// whose key has no segment yet.
+    detached_at: Option<usize>,
+    changed: Vec<StorePath>,
+}
+
+impl FieldCursor {
+    /// Creates a cursor at the root of a store, which uses the store's keys to find keyed fields.
+    pub fn new(keys: Option<KeyMap>) -> Self {
+        Self::at(StorePath::default(), keys)
+    }
+
+    pub(crate) fn at(path: StorePath, keys: Option<KeyMap>) -> Self {
+        Self {
+            keys,
+            path,
+            name: String::new(),
+            keyed_item: None,
+            detached_at: None,
+            changed: Vec::new(),
+        }
+    }
+
+    /// The path of the field.
+    pub fn path(&self) -> &StorePath {
+        &self.path
+    }
+
+    /// A readable version of the path, like `todos[3].completed`.
+    ///
+    /// This is empty at the root of the store.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn into_parts(self) -> (StorePath, String) {
+        (self.path, self.name)
+    }
+
+    /// Moves to a named field.
+    #[doc(hidden)]
+    pub fn enter_field(&mut self, segment: StorePathSegment, name: &str) {
+        self.path.push(segment);
+        if !self.name.is_empty() {
+            self.name.push('.');
+        }
+        self.name.push_str(name);
+    }
+
+    /// Moves to the item at `index` in a collection.
+    #[doc(hidden)]
+    pub fn enter_item(&mut self, segment: StorePathSegment, index: usize) {
+        self.path.push(segment);
+        self.name.push_str(&format!("[{index}]"));
+    }
+
+    /// Moves to a field that has no name of its own, like the inner value of an `Option`.
+    #[doc(hidden)]
+    pub fn enter(&mut self, segment: StorePathSegment) {
+        self.path.push(segment);
+    }
+
+    /// Moves to the item at `index` in a collection, which may be the items of a keyed field.
+    pub(crate) fn enter_index(&mut self, index: usize) {
+        match self.keyed_item.take() {
+            Some(Some(segment)) => self.enter_item(segment, index),
+            Some(None) => {
+                // nothing can be tracking an item whose key has no segment
+                self.detached_at.get_or_insert(self.path.as_slice().len());
+                self.enter_item(index.into(), index);
+            }
+            None => self.enter_item(index.into(), index),
+        }
+    }
+
+    /// Records that the field at the cursor has been changed, so that it can be notified.
+    #[doc(hidden)]
+    pub fn changed(&mut self) {
+        let path = self.path.as_slice();
+        let len = self.detached_at.unwrap_or(path.len());
+        self.changed.push(path[..len].iter().copied().collect());
+    }
+
+    pub(crate) fn take_changed(&mut self) -> Vec<StorePath> {
+        std::mem::take(&mut self.changed)
+    }
+
+    /// Calls `fun` with the key that has been given `segment` in the keyed field at the cursor.
+    #[doc(hidden)]
+    pub fn with_key<K, T>(
+        &self,
+        segment: StorePathSegment,
+        fun: impl FnOnce(&K) -> T,
+    ) -> Option<T>
+    where
+        K: Hash + Eq + 'static,
+    {
+        self.keys
+            .as_ref()?
+            .read_field_keys(&self.path, |keys| keys.key(segment).map(fun))
+            .flatten()
+    }
+}
+
+/// Returns the index of the item with the given key.
+#[doc(hidden)]
+pub fn keyed_index<T, K>(
+    items: &T,
+    key: &K,
+    key_fn: fn(<&T as IntoIterator>::Item) -> K,
+) -> Option<usize>
+where
+    for<'a> &'a T: IntoIterator,
+    K: PartialEq,
+{
+    items.into_iter().position(|item| key_fn(item) == *key)
+}
+
+/// Serializes a whole value, as the fallback of the derived implementations of [`JsonField`].
+#[doc(hidden)]
+pub trait SerializeJson<M> {
+    fn to_json(&self) -> Result<Value, serde_json::Error>;
+}
+
+impl<T, M> SerializeJson<M> for T
+where
+    T: Serialize + ?Sized,
+{
+    fn to_json(&self) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(self)
+    }
+}
+
+macro_rules! json_leaves {
+    ($($ty:ty),*) => {
+        $(impl JsonField for $ty {
+            fn field_json(
+                &self,
+                _path: &[StorePathSegment],
+                _cursor: &mut FieldCursor,
+            ) -> Result<Value, serde_json::Error> {
+                serde_json::to_value(self)
+            }
+        })*
+    };
+}
+
+json_leaves! {
+    (),
+    &str,
+    String,
+    Cow<'_, str>,
+    usize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    isize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+    char,
+    bool,
+    IpAddr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6,
+    Ipv4Addr,
+    Ipv6Addr,
+    NonZeroI8,
+    NonZeroU8,
+    NonZeroI16,
+    NonZeroU16,
+    NonZeroI32,
+    NonZeroU32,
+    NonZeroI64,
+    NonZeroU64,
+    NonZeroI128,
+    NonZeroU128,
+    NonZeroIsize,
+    NonZeroUsize
+}
+
+macro_rules! json_whole_collections {
+    ($($ty:ident<$($param:ident),*>),*) => {
+        $(impl<$($param),*> JsonField for $ty<$($param),*>
+        where
+            Self: Serialize,
+        {
+            fn field_json(
+                &self,
+                _path: &[StorePathSegment],
+                _cursor: &mut FieldCursor,
+            ) -> Result<Value, serde_json::Error> {
+                serde_json::to_value(self)
+            }
+        })*
+    };
+}
+
+json_whole_collections! {
+    HashMap<K, V, S>,
+    BTreeMap<K, V>,
+    HashSet<T, S>,
+    BTreeSet<T>
+}
+
+impl<T> JsonField for Option<T>
+where
+    T: JsonField + Serialize,
+{
+    fn field_json(
+        &self,
+        path: &[StorePathSegment],
+        cursor: &mut FieldCursor,
+    ) -> Result<Value, serde_json::Error> {
+        // the inner value has the path of `unwrap()`, and the same name as the option
+        match (self, path.split_first()) {
+            (Some(inner), Some((segment, path))) if segment.0 == 0 => {
+                cursor.enter(*segment);
+                inner.field_json(path, cursor)
+            }
+            _ => serde_json::to_value(self),
+        }
+    }
+}
+
+impl<T> JsonField for Box<T>
+where
+    T: JsonField,
+{
+    fn field_json(
+        &self,
+        path: &[StorePathSegment],
+        cursor: &mut FieldCursor,
+    ) -> Result<Value, serde_json::Error> {
+        T::field_json(self, path, cursor)
+    }
+
+    fn item_json(
+        &self,
+        segment: StorePathSegment,
+        index: usize,
+        path: &[StorePathSegment],
+        cursor: &mut FieldCursor,
+    ) -> Option<Result<Value, serde_json::Error>> {
+        T::item_json(self, segment, index, path, cursor)
+    }
+}
+
+macro_rules! json_sequences {
+    ($($ty:ident),*) => {
+        $(impl<T> JsonField for $ty<T>
+        where
+            T: JsonField + Serialize,
+        {
+            fn field_json(
+                &self,
+                path: &[StorePathSegment],
+                cursor: &mut FieldCursor,
+            ) -> Result<Value, serde_json::Error> {
+                path.split_first()
+                    .and_then(|(segment, path)| {
+                        self.item_json(*segment, segment.0, path, cursor)
+                    })
+                    .unwrap_or_else(|| serde_json::to_value(self))
+            }
+
+            fn item_json(
+                &self,
+                segment: StorePathSegment,
+                index: usize,
+                path: &[StorePathSegment],
+                cursor: &mut FieldCursor,
+            ) -> Option<Result<Value, serde_json::Error>> {
+                let item = self.get(index)?;
+                cursor.enter_item(segment, index);
+                Some(item.field_json(path, cursor))
+            }
+        })*
+    };
+}
+
+json_sequences!(Vec, VecDeque);
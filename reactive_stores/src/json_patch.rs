@@ -0,0 +1,1045 @@
+#[cfg(doc)]
+use crate::JsonField;
+use crate::{
+    json_field::SerializeJson, FieldCursor, FieldKeys, KeyMap, StoreField,
+    StorePath,
+};
+use reactive_graph::traits::{Notify, UntrackableGuard};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+    mem,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
+        NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64,
+        NonZeroU8, NonZeroUsize,
+    },
+};
+use thiserror::Error;
+
+/// A JSON Patch document, as defined in [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902).
+///
+/// This can be deserialized directly from the JSON array of operations.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonPatch(pub Vec<JsonPatchOperation>);
+
+/// A single operation in a [`JsonPatch`].
+///
+/// Each path is a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901), like
+/// `/todos/3/completed`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum JsonPatchOperation {
+    /// Adds a value to an object, or inserts it into an array.
+    Add {
+        /// The location at which the value is added.
+        path: String,
+        /// The value to add.
+        value: Value,
+    },
+    /// Removes the value at the given location.
+    Remove {
+        /// The location of the value to remove.
+        path: String,
+    },
+    /// Replaces the value at the given location.
+    Replace {
+        /// The location of the value to replace.
+        path: String,
+        /// The new value.
+        value: Value,
+    },
+    /// Removes the value at one location, and adds it at another.
+    Move {
+        /// The location of the value to move.
+        from: String,
+        /// The location to which it is moved.
+        path: String,
+    },
+    /// Copies the value at one location to another.
+    Copy {
+        /// The location of the value to copy.
+        from: String,
+        /// The location to which it is copied.
+        path: String,
+    },
+    /// Checks that the value at the given location is equal to some value.
+    Test {
+        /// The location of the value to check.
+        path: String,
+        /// The expected value.
+        value: Value,
+    },
+}
+
+/// An error that occurs when applying a [`JsonPatch`] to a store.
+///
+/// If an error is returned, the store has not been changed.
+#[derive(Error, Debug)]
+pub enum JsonPatchError {
+    /// The path is not a valid JSON Pointer.
+    #[error("`{0}` is not a valid JSON Pointer")]
+    InvalidPointer(String),
+    /// There is no value at the path, or at the parent of the path for an `add` operation.
+    #[error("there is no value at `{0}`")]
+    PathNotFound(String),
+    /// A value cannot be moved into one of its own children.
+    #[error("cannot move `{from}` into itself at `{path}`")]
+    MoveIntoSelf {
+        /// The location of the value being moved.
+        from: String,
+        /// The location to which it was being moved.
+        path: String,
+    },
+    /// A `test` operation failed.
+    #[error("the value at `{0}` did not match the test")]
+    TestFailed(String),
+    /// A value in the patch does not match the type of the field it is applied to.
+    #[error("the patched value does not match the type of the field: {0}")]
+    InvalidType(#[source] serde_json::Error),
+    /// The current value of a field could not be serialized.
+    #[error("could not serialize the value of the field: {0}")]
+    Serialize(#[source] serde_json::Error),
+    /// The store has been disposed.
+    #[error("the store has been disposed")]
+    Disposed,
+}
+
+/// A value that can be changed by the operations of a [`JsonPatch`].
+///
+/// With the `serde` feature, this is implemented by `#[derive(Store)]` for any type that
+/// implements [`Serialize`] and [`DeserializeOwned`], as long as the types of its fields implement
+/// `JsonPatchField` too. It is also implemented for the same types as [`JsonField`].
+///
+/// The tokens of a JSON Pointer are matched with the fields of the store, rather than with the
+/// serialized value: struct fields and the fields of the current variant of an enum are found by
+/// their names in Rust, items of a `Vec` (keyed or not) by their index, and the inner value of an
+/// `Option` by the path of the option itself. Maps, sets and primitives are changed as a whole.
+///
+/// The `M` parameter only allows the derived implementations to depend on the types of their
+/// fields, and should be left as the default.
+pub trait JsonPatchField<M = ()> {
+    /// Applies `edit` to the field at `tokens`, relative to this value.
+    #[doc(hidden)]
+    fn edit_json(
+        &mut self,
+        tokens: &[String],
+        edit: JsonEdit,
+        cursor: &mut FieldCursor,
+    ) -> Result<JsonEdited, JsonEditError>;
+}
+
+/// One of the changes from which the operations of a [`JsonPatch`] are made.
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub enum JsonEdit {
+    Get,
+    Replace(Value),
+    /// Adds an item to a collection, or replaces any other field.
+    Add(Value),
+    /// Removes an item from a collection, or sets an `Option` to `None`.
+    Remove,
+}
+
+/// The result of a [`JsonEdit`].
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum JsonEdited {
+    /// The value that was read, replaced, or removed.
+    Value(Value),
+    /// A new item was added to a collection, with this token.
+    Inserted(String),
+}
+
+/// An error while applying a [`JsonEdit`], before the pointer is known.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum JsonEditError {
+    NotFound,
+    InvalidType(serde_json::Error),
+    Serialize(serde_json::Error),
+}
+
+impl JsonEditError {
+    fn with_pointer(self, pointer: &str) -> JsonPatchError {
+        match self {
+            JsonEditError::NotFound => {
+                JsonPatchError::PathNotFound(pointer.to_string())
+            }
+            JsonEditError::InvalidType(e) => JsonPatchError::InvalidType(e),
+            JsonEditError::Serialize(e) => JsonPatchError::Serialize(e),
+        }
+    }
+}
+
+/// Extends store fields with the ability to apply a [`JsonPatch`].
+pub trait JsonPatchStoreExt {
+    /// Applies every operation in the patch to the value of the field.
+    ///
+    /// Each operation changes the field at the [`StorePath`] its pointer refers to (see
+    /// [`JsonPatchField`]), and only the fields that were changed are notified, once the whole
+    /// patch has been applied. Paths are relative to this field.
+    ///
+    /// If any operation fails, the operations before it are undone, an error is returned, and
+    /// nothing is notified.
+    ///
+    /// ```rust
+    /// use reactive_graph::traits::GetUntracked;
+    /// use reactive_stores::{JsonPatch, JsonPatchStoreExt, Store, StoreFieldIterator};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Store, Serialize, Deserialize)]
+    /// struct Todos {
+    ///     todos: Vec<Todo>,
+    /// }
+    ///
+    /// #[derive(Store, Serialize, Deserialize)]
+    /// struct Todo {
+    ///     label: String,
+    ///     completed: bool,
+    /// }
+    ///
+    /// let store = Store::new(Todos { todos: vec![] });
+    /// let patch: JsonPatch = serde_json::from_str(
+    ///     r#"[
+    ///         { "op": "add", "path": "/todos/-", "value": { "label": "Write docs", "completed": false } },
+    ///         { "op": "replace", "path": "/todos/0/completed", "value": true }
+    ///     ]"#,
+    /// )
+    /// .unwrap();
+    /// store.apply_json_patch(&patch).unwrap();
+    /// assert!(store.todos().at_unkeyed(0).completed().get_untracked());
+    /// ```
+    fn apply_json_patch(&self, patch: &JsonPatch)
+        -> Result<(), JsonPatchError>;
+}
+
+impl<S> JsonPatchStoreExt for S
+where
+    S: StoreField,
+    S::Value: JsonPatchField,
+{
+    fn apply_json_patch(
+        &self,
+        patch: &JsonPatch,
+    ) -> Result<(), JsonPatchError> {
+        let path = self.path().into_iter().collect::<StorePath>();
+        let keys = self.keys();
+        let mut writer = self.writer().ok_or(JsonPatchError::Disposed)?;
+        // don't track the writer for the whole store
+        writer.untrack();
+
+        let mut patcher = Patcher {
+            value: &mut *writer,
+            path: &path,
+            keys: &keys,
+            changed: Vec::new(),
+            undo: Vec::new(),
+        };
+        if let Err(e) = patch.0.iter().try_for_each(|op| patcher.apply(op)) {
+            patcher.undo();
+            return Err(e);
+        }
+        let mut changed = patcher.changed;
+        drop(writer);
+
+        changed.dedup();
+        for (idx, path) in changed.iter().enumerate() {
+            if changed[..idx].contains(path) {
+                continue;
+            }
+            if let Some(keys) = &keys {
+                keys.record_change(|| path.clone());
+            }
+            let trigger = self.get_trigger(path.clone());
+            trigger.this.notify();
+            trigger.children.notify();
+        }
+        Ok(())
+    }
+}
+
+/// Applies the operations of a patch to a value, and keeps what is needed to undo them.
+struct Patcher<'a, T> {
+    value: &'a mut T,
+    path: &'a StorePath,
+    keys: &'a Option<KeyMap>,
+    changed: Vec<StorePath>,
+    undo: Vec<(Vec<String>, JsonEdit)>,
+}
+
+impl<T> Patcher<'_, T>
+where
+    T: JsonPatchField,
+{
+    fn edit(
+        &mut self,
+        tokens: &[String],
+        edit: JsonEdit,
+    ) -> Result<JsonEdited, JsonEditError> {
+        let mut cursor = FieldCursor::at(self.path.clone(), self.keys.clone());
+        let edited = self.value.edit_json(tokens, edit, &mut cursor);
+        self.changed.extend(cursor.take_changed());
+        edited
+    }
+
+    fn get(&mut self, pointer: &str) -> Result<Value, JsonPatchError> {
+        match self.edit(&tokens(pointer)?, JsonEdit::Get) {
+            Ok(JsonEdited::Value(value)) => Ok(value),
+            Ok(JsonEdited::Inserted(_)) => unreachable!(),
+            Err(e) => Err(e.with_pointer(pointer)),
+        }
+    }
+
+    /// Applies a change, and records the change that undoes it.
+    fn change(
+        &mut self,
+        pointer: &str,
+        edit: JsonEdit,
+    ) -> Result<Value, JsonPatchError> {
+        let mut tokens = tokens(pointer)?;
+        let removing = matches!(edit, JsonEdit::Remove);
+        let edited = self
+            .edit(&tokens, edit)
+            .map_err(|e| e.with_pointer(pointer))?;
+        Ok(match edited {
+            JsonEdited::Inserted(token) => {
+                tokens.pop();
+                tokens.push(token);
+                self.undo.push((tokens, JsonEdit::Remove));
+                Value::Null
+            }
+            JsonEdited::Value(old) if removing => {
+                self.undo.push((tokens, JsonEdit::Add(old.clone())));
+                old
+            }
+            JsonEdited::Value(old) => {
+                self.undo.push((tokens, JsonEdit::Replace(old.clone())));
+                old
+            }
+        })
+    }
+
+    fn apply(&mut self, op: &JsonPatchOperation) -> Result<(), JsonPatchError> {
+        match op {
+            JsonPatchOperation::Add { path, value } => {
+                self.change(path, JsonEdit::Add(value.clone()))?;
+            }
+            JsonPatchOperation::Remove { path } => {
+                self.change(path, JsonEdit::Remove)?;
+            }
+            JsonPatchOperation::Replace { path, value } => {
+                self.change(path, JsonEdit::Replace(value.clone()))?;
+            }
+            JsonPatchOperation::Move { from, path } => {
+                if path.starts_with(from.as_str())
+                    && path[from.len()..].starts_with('/')
+                {
+                    return Err(JsonPatchError::MoveIntoSelf {
+                        from: from.clone(),
+                        path: path.clone(),
+                    });
+                }
+                let value = self.change(from, JsonEdit::Remove)?;
+                self.change(path, JsonEdit::Add(value))?;
+            }
+            JsonPatchOperation::Copy { from, path } => {
+                let value = self.get(from)?;
+                self.change(path, JsonEdit::Add(value))?;
+            }
+            JsonPatchOperation::Test { path, value } => {
+                if !json_eq(&self.get(path)?, value) {
+                    return Err(JsonPatchError::TestFailed(path.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn undo(&mut self) {
+        for (tokens, edit) in mem::take(&mut self.undo).into_iter().rev() {
+            // this restores values that were just read from the same fields
+            _ = self.edit(&tokens, edit);
+        }
+    }
+}
+
+/// Compares two JSON values as a `test` operation does, so that numbers are equal if they have
+/// the same value, like `1` and `1.0`.
+fn json_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => {
+            match (a.as_i64(), b.as_i64(), a.as_u64(), b.as_u64()) {
+                (Some(a), Some(b), _, _) => a == b,
+                (_, _, Some(a), Some(b)) => a == b,
+                _ => a.as_f64() == b.as_f64(),
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| json_eq(a, b)))
+        }
+        (a, b) => a == b,
+    }
+}
+
+/// Deserializes a whole value, as the fallback of the derived implementations of
+/// [`JsonPatchField`].
+#[doc(hidden)]
+pub trait DeserializeJson<M>: Sized {
+    fn from_json(value: Value) -> Result<Self, serde_json::Error>;
+}
+
+impl<T, M> DeserializeJson<M> for T
+where
+    T: DeserializeOwned,
+{
+    fn from_json(value: Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+}
+
+/// Applies an edit to a value that is changed as a whole.
+#[doc(hidden)]
+pub fn edit_whole<M, T>(
+    value: &mut T,
+    tokens: &[String],
+    edit: JsonEdit,
+    cursor: &mut FieldCursor,
+) -> Result<JsonEdited, JsonEditError>
+where
+    T: SerializeJson<M> + DeserializeJson<M>,
+{
+    let mut json = value.to_json().map_err(JsonEditError::Serialize)?;
+    let changes = !matches!(edit, JsonEdit::Get);
+    let edited = edit_value(&mut json, tokens, edit)?;
+    if changes {
+        *value = T::from_json(json).map_err(JsonEditError::InvalidType)?;
+        cursor.changed();
+    }
+    Ok(edited)
+}
+
+/// Applies an edit to the field of a keyed field, whose items are found by their keys.
+#[doc(hidden)]
+pub fn edit_keyed<M, T, K>(
+    items: &mut T,
+    key_fn: fn(<&T as IntoIterator>::Item) -> K,
+    tokens: &[String],
+    edit: JsonEdit,
+    cursor: &mut FieldCursor,
+) -> Result<JsonEdited, JsonEditError>
+where
+    T: JsonPatchField<M>,
+    for<'a> &'a T: IntoIterator,
+    K: Debug + Hash + Eq + 'static,
+{
+    let path = cursor.path().clone();
+    let keys = cursor.keys.clone();
+    let item_key = tokens
+        .first()
+        .and_then(|token| index(token))
+        .and_then(|idx| (&*items).into_iter().nth(idx))
+        .map(key_fn);
+    if let Some(key) = item_key {
+        cursor.keyed_item = Some(keys.as_ref().and_then(|keys| {
+            keys.read_field_keys(&path, |keys: &FieldKeys<K>| {
+                keys.get(&key).map(|(segment, _)| segment)
+            })
+            .flatten()
+        }));
+    }
+
+    let changes = !matches!(edit, JsonEdit::Get);
+    let edited = items.edit_json(tokens, edit, cursor)?;
+    if changes {
+        if let Some(keys) = keys {
+            keys.with_existing_field_keys(&path, |keys: &mut FieldKeys<K>| {
+                keys.update((&*items).into_iter().map(key_fn))
+            });
+        }
+    }
+    Ok(edited)
+}
+
+fn cannot_remove() -> JsonEditError {
+    JsonEditError::InvalidType(serde::de::Error::custom(
+        "only items of collections and optional fields can be removed",
+    ))
+}
+
+/// Applies an edit to a serialized value.
+fn edit_value(
+    json: &mut Value,
+    tokens: &[String],
+    edit: JsonEdit,
+) -> Result<JsonEdited, JsonEditError> {
+    let Some((last, tokens)) = tokens.split_last() else {
+        return match edit {
+            JsonEdit::Get => Ok(JsonEdited::Value(json.clone())),
+            JsonEdit::Replace(value) | JsonEdit::Add(value) => {
+                Ok(JsonEdited::Value(mem::replace(json, value)))
+            }
+            JsonEdit::Remove => Err(cannot_remove()),
+        };
+    };
+    let parent = resolve(json, tokens).ok_or(JsonEditError::NotFound)?;
+    match (parent, edit) {
+        (Value::Object(map), JsonEdit::Add(value)) => {
+            Some(match map.insert(last.clone(), value) {
+                Some(old) => JsonEdited::Value(old),
+                None => JsonEdited::Inserted(last.clone()),
+            })
+        }
+        (Value::Object(map), JsonEdit::Remove) => {
+            map.remove(last).map(JsonEdited::Value)
+        }
+        (Value::Object(map), edit) => {
+            map.get_mut(last).map(|old| edit_in_place(old, edit))
+        }
+        (Value::Array(items), JsonEdit::Add(value)) => {
+            insert_index(last, items.len()).map(|idx| {
+                items.insert(idx, value);
+                JsonEdited::Inserted(idx.to_string())
+            })
+        }
+        (Value::Array(items), JsonEdit::Remove) => index(last)
+            .filter(|idx| *idx < items.len())
+            .map(|idx| JsonEdited::Value(items.remove(idx))),
+        (Value::Array(items), edit) => index(last)
+            .and_then(|idx| items.get_mut(idx))
+            .map(|old| edit_in_place(old, edit)),
+        _ => None,
+    }
+    .ok_or(JsonEditError::NotFound)
+}
+
+fn edit_in_place(old: &mut Value, edit: JsonEdit) -> JsonEdited {
+    match edit {
+        JsonEdit::Replace(value) | JsonEdit::Add(value) => {
+            JsonEdited::Value(mem::replace(old, value))
+        }
+        _ => JsonEdited::Value(old.clone()),
+    }
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+fn tokens(pointer: &str) -> Result<Vec<String>, JsonPatchError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err(JsonPatchError::InvalidPointer(pointer.to_string()));
+    };
+    Ok(rest
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// Parses an array index, which may not have leading zeros.
+fn index(token: &str) -> Option<usize> {
+    if token.len() > 1 && token.starts_with('0') {
+        None
+    } else {
+        token.parse().ok()
+    }
+}
+
+/// Parses the index at which an item is added to an array of length `len`.
+fn insert_index(token: &str, len: usize) -> Option<usize> {
+    if token == "-" {
+        Some(len)
+    } else {
+        index(token).filter(|idx| *idx <= len)
+    }
+}
+
+/// Finds the value referred to by the tokens of a JSON Pointer.
+fn resolve<'a>(
+    document: &'a mut Value,
+    tokens: &[String],
+) -> Option<&'a mut Value> {
+    tokens
+        .iter()
+        .try_fold(document, |value, token| match value {
+            Value::Object(map) => map.get_mut(token.as_str()),
+            Value::Array(items) => {
+                index(token).and_then(|idx| items.get_mut(idx))
+            }
+            _ => None,
+        })
+}
+
+macro_rules! patch_whole_values {
+    ($($ty:ty),*) => {
+        $(impl JsonPatchField for $ty {
+            fn edit_json(
+                &mut self,
+                tokens: &[String],
+                edit: JsonEdit,
+                cursor: &mut FieldCursor,
+            ) -> Result<JsonEdited, JsonEditError> {
+                edit_whole::<(), _>(self, tokens, edit, cursor)
+            }
+        })*
+    };
+}
+
+patch_whole_values! {
+    (),
+    String,
+    Cow<'_, str>,
+    usize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    isize,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+    char,
+    bool,
+    IpAddr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6,
+    Ipv4Addr,
+    Ipv6Addr,
+    NonZeroI8,
+    NonZeroU8,
+    NonZeroI16,
+    NonZeroU16,
+    NonZeroI32,
+    NonZeroU32,
+    NonZeroI64,
+    NonZeroU64,
+    NonZeroI128,
+    NonZeroU128,
+    NonZeroIsize,
+    NonZeroUsize
+}
+
+macro_rules! patch_whole_collections {
+    ($($ty:ident<$($param:ident),*>),*) => {
+        $(impl<$($param),*> JsonPatchField for $ty<$($param),*>
+        where
+            Self: Serialize + DeserializeOwned,
+        {
+            fn edit_json(
+                &mut self,
+                tokens: &[String],
+                edit: JsonEdit,
+                cursor: &mut FieldCursor,
+            ) -> Result<JsonEdited, JsonEditError> {
+                edit_whole::<(), _>(self, tokens, edit, cursor)
+            }
+        })*
+    };
+}
+
+patch_whole_collections! {
+    HashMap<K, V, S>,
+    BTreeMap<K, V>,
+    HashSet<T, S>,
+    BTreeSet<T>
+}
+
+impl<T> JsonPatchField for Option<T>
+where
+    T: JsonPatchField + Serialize + DeserializeOwned,
+{
+    fn edit_json(
+        &mut self,
+        tokens: &[String],
+        edit: JsonEdit,
+        cursor: &mut FieldCursor,
+    ) -> Result<JsonEdited, JsonEditError> {
+        match (self, tokens.is_empty(), edit) {
+            (this @ Some(_), true, JsonEdit::Remove) => {
+                let old = serde_json::to_value(&*this)
+                    .map_err(JsonEditError::Serialize)?;
+                *this = None;
+                cursor.changed();
+                Ok(JsonEdited::Value(old))
+            }
+            (this, true, edit) => edit_whole::<(), _>(this, &[], edit, cursor),
+            // the inner value has the path of `unwrap()`
+            (Some(inner), false, edit) => {
+                cursor.enter(0.into());
+                inner.edit_json(tokens, edit, cursor)
+            }
+            (None, false, _) => Err(JsonEditError::NotFound),
+        }
+    }
+}
+
+impl<T> JsonPatchField for Box<T>
+where
+    T: JsonPatchField,
+{
+    fn edit_json(
+        &mut self,
+        tokens: &[String],
+        edit: JsonEdit,
+        cursor: &mut FieldCursor,
+    ) -> Result<JsonEdited, JsonEditError> {
+        T::edit_json(self, tokens, edit, cursor)
+    }
+}
+
+macro_rules! patch_sequences {
+    ($($ty:ident),*) => {
+        $(impl<T> JsonPatchField for $ty<T>
+        where
+            T: JsonPatchField + Serialize + DeserializeOwned,
+        {
+            fn edit_json(
+                &mut self,
+                tokens: &[String],
+                edit: JsonEdit,
+                cursor: &mut FieldCursor,
+            ) -> Result<JsonEdited, JsonEditError> {
+                let Some((token, rest)) = tokens.split_first() else {
+                    return edit_whole::<(), _>(self, tokens, edit, cursor);
+                };
+                match (rest.is_empty(), edit) {
+                    (true, JsonEdit::Add(value)) => {
+                        let idx = insert_index(token, self.len())
+                            .ok_or(JsonEditError::NotFound)?;
+                        let item = serde_json::from_value(value)
+                            .map_err(JsonEditError::InvalidType)?;
+                        self.insert(idx, item);
+                        cursor.changed();
+                        Ok(JsonEdited::Inserted(idx.to_string()))
+                    }
+                    (true, JsonEdit::Remove) => {
+                        let idx = index(token)
+                            .filter(|idx| *idx < self.len())
+                            .ok_or(JsonEditError::NotFound)?;
+                        let old = serde_json::to_value(&self[idx])
+                            .map_err(JsonEditError::Serialize)?;
+                        self.remove(idx);
+                        cursor.changed();
+                        Ok(JsonEdited::Value(old))
+                    }
+                    (_, edit) => {
+                        let idx = index(token).ok_or(JsonEditError::NotFound)?;
+                        let item =
+                            self.get_mut(idx).ok_or(JsonEditError::NotFound)?;
+                        cursor.enter_index(idx);
+                        item.edit_json(rest, edit, cursor)
+                    }
+                }
+            }
+        })*
+    };
+}
+
+patch_sequences!(Vec, VecDeque);
+
+#[cfg(test)]
+mod tests {
+    use super::{JsonPatch, JsonPatchError, JsonPatchStoreExt};
+    use crate::{self as reactive_stores, AtKeyed, Store, StoreFieldIterator};
+    use reactive_graph::{
+        effect::Effect,
+        traits::{Get, GetUntracked, Read},
+    };
+    use serde::{Deserialize, Serialize};
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[derive(Debug, Store, Serialize, Deserialize, Default)]
+    struct Todos {
+        user: String,
+        todos: Vec<Todo>,
+    }
+
+    #[derive(Debug, Store, Serialize, Deserialize, Default, Clone)]
+    struct Todo {
+        label: String,
+        completed: bool,
+    }
+
+    #[derive(Debug, Store, Serialize, Deserialize)]
+    struct Table {
+        #[store(key: usize = |row| row.id)]
+        rows: Vec<Row>,
+        total: f64,
+    }
+
+    #[derive(Debug, Store, Serialize, Deserialize)]
+    struct Row {
+        id: usize,
+        label: String,
+    }
+
+    fn table() -> Table {
+        Table {
+            rows: vec![
+                Row {
+                    id: 1,
+                    label: "one".to_string(),
+                },
+                Row {
+                    id: 2,
+                    label: "two".to_string(),
+                },
+            ],
+            total: 3.0,
+        }
+    }
+
+    fn data() -> Todos {
+        Todos {
+            user: "Bob".to_string(),
+            todos: vec![
+                Todo {
+                    label: "Create reactive store".to_string(),
+                    completed: true,
+                },
+                Todo {
+                    label: "Apply patches".to_string(),
+                    completed: false,
+                },
+            ],
+        }
+    }
+
+    fn patch(json: &str) -> JsonPatch {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn applies_each_operation() {
+        let store = Store::new(data());
+        store
+            .apply_json_patch(&patch(
+                r#"[
+                    { "op": "test", "path": "/user", "value": "Bob" },
+                    { "op": "replace", "path": "/user", "value": "Carol" },
+                    { "op": "move", "from": "/todos/1", "path": "/todos/0" },
+                    { "op": "copy", "from": "/todos/1", "path": "/todos/-" },
+                    { "op": "remove", "path": "/todos/1" },
+                    { "op": "add", "path": "/todos/0/completed", "value": true }
+                ]"#,
+            ))
+            .unwrap();
+
+        assert_eq!(store.user().get_untracked(), "Carol");
+        let todos = store
+            .todos()
+            .iter_unkeyed()
+            .map(|todo| {
+                (
+                    todo.label().get_untracked(),
+                    todo.completed().get_untracked(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            todos,
+            vec![
+                ("Apply patches".to_string(), true),
+                ("Create reactive store".to_string(), true)
+            ]
+        );
+    }
+
+    #[test]
+    fn fails_without_changing_the_store() {
+        let store = Store::new(data());
+
+        let err = store
+            .apply_json_patch(&patch(
+                r#"[
+                    { "op": "replace", "path": "/user", "value": "Carol" },
+                    { "op": "remove", "path": "/todos/5" }
+                ]"#,
+            ))
+            .unwrap_err();
+        assert!(
+            matches!(err, JsonPatchError::PathNotFound(path) if path == "/todos/5")
+        );
+
+        let err = store
+            .apply_json_patch(&patch(
+                r#"[{ "op": "replace", "path": "/user", "value": 42 }]"#,
+            ))
+            .unwrap_err();
+        assert!(matches!(err, JsonPatchError::InvalidType(_)));
+
+        let err = store
+            .apply_json_patch(&patch(
+                r#"[{ "op": "test", "path": "/todos/0/completed", "value": false }]"#,
+            ))
+            .unwrap_err();
+        assert!(matches!(err, JsonPatchError::TestFailed(_)));
+
+        let err = store
+            .apply_json_patch(&patch(
+                r#"[{ "op": "remove", "path": "todos" }]"#,
+            ))
+            .unwrap_err();
+        assert!(matches!(err, JsonPatchError::InvalidPointer(_)));
+
+        let err = store
+            .apply_json_patch(&patch(
+                r#"[
+                    { "op": "remove", "path": "/todos/0" },
+                    { "op": "add", "path": "/todos/-", "value": { "label": "New" } }
+                ]"#,
+            ))
+            .unwrap_err();
+        assert!(matches!(err, JsonPatchError::InvalidType(_)));
+
+        assert_eq!(store.user().get_untracked(), "Bob");
+        assert_eq!(
+            store.todos().at_unkeyed(0).label().get_untracked(),
+            "Create reactive store"
+        );
+        assert_eq!(store.todos().iter_unkeyed().count(), 2);
+    }
+
+    #[test]
+    fn tests_numbers_by_value() {
+        let store = Store::new(table());
+        store
+            .apply_json_patch(&patch(
+                r#"[
+                    { "op": "test", "path": "/total", "value": 3 },
+                    { "op": "test", "path": "/rows/0/id", "value": 1.0 },
+                    { "op": "test", "path": "/rows/1", "value": { "id": 2.0, "label": "two" } }
+                ]"#,
+            ))
+            .unwrap();
+
+        let err = store
+            .apply_json_patch(&patch(
+                r#"[{ "op": "test", "path": "/total", "value": 3.5 }]"#,
+            ))
+            .unwrap_err();
+        assert!(matches!(err, JsonPatchError::TestFailed(_)));
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_field() {
+        let store = Store::new(data());
+        store
+            .todos()
+            .at_unkeyed(1)
+            .apply_json_patch(&patch(
+                r#"[{ "op": "replace", "path": "/label", "value": "a~1b" }]"#,
+            ))
+            .unwrap();
+        assert_eq!(store.todos().at_unkeyed(1).label().get_untracked(), "a~1b");
+    }
+
+    #[tokio::test]
+    async fn only_notifies_changed_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let user_runs = Arc::new(AtomicUsize::new(0));
+        let label_runs = Arc::new(AtomicUsize::new(0));
+        let completed_runs = Arc::new(AtomicUsize::new(0));
+
+        Effect::new_sync({
+            let user_runs = Arc::clone(&user_runs);
+            move |_| {
+                store.user().read();
+                user_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let label_runs = Arc::clone(&label_runs);
+            move |_| {
+                store.todos().at_unkeyed(0).label().read();
+                label_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let completed_runs = Arc::clone(&completed_runs);
+            move |_| {
+                store.todos().at_unkeyed(1).completed().get();
+                completed_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        crate::tests::tick().await;
+
+        store
+            .apply_json_patch(&patch(
+                r#"[{ "op": "replace", "path": "/todos/1/completed", "value": true }]"#,
+            ))
+            .unwrap();
+        crate::tests::tick().await;
+
+        assert_eq!(user_runs.load(Ordering::Relaxed), 1);
+        assert_eq!(label_runs.load(Ordering::Relaxed), 1);
+        assert_eq!(completed_runs.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn finds_keyed_items_by_their_index() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(table());
+        let first_runs = Arc::new(AtomicUsize::new(0));
+        let second_runs = Arc::new(AtomicUsize::new(0));
+
+        Effect::new_sync({
+            let first_runs = Arc::clone(&first_runs);
+            move |_| {
+                AtKeyed::new(store.rows(), 1).label().read();
+                first_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let second_runs = Arc::clone(&second_runs);
+            move |_| {
+                AtKeyed::new(store.rows(), 2).label().read();
+                second_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        crate::tests::tick().await;
+
+        store
+            .apply_json_patch(&patch(
+                r#"[{ "op": "replace", "path": "/rows/1/label", "value": "two!" }]"#,
+            ))
+            .unwrap();
+        crate::tests::tick().await;
+
+        assert_eq!(first_runs.load(Ordering::Relaxed), 1);
+        assert_eq!(second_runs.load(Ordering::Relaxed), 2);
+
+        store
+            .apply_json_patch(&patch(
+                r#"[{ "op": "remove", "path": "/rows/0" }]"#,
+            ))
+            .unwrap();
+        assert_eq!(
+            AtKeyed::new(store.rows(), 2).label().get_untracked(),
+            "two!"
+        );
+    }
+}
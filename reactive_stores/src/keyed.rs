@@ -1,6 +1,6 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    store_field::StoreField,
+    store_field::{record_change, this_trigger, DowngradeField, StoreField},
     KeyMap, StoreFieldTrigger,
 };
 use reactive_graph::{
@@ -248,6 +248,42 @@ where
     }
 }
 
+impl<Inner, Prev, K, T> DowngradeField for KeyedSubfield<Inner, Prev, K, T>
+where
+    Self: Clone,
+    for<'a> &'a T: IntoIterator,
+    Inner: DowngradeField<Value = Prev>,
+    Prev: 'static,
+    K: Debug + Send + Sync + PartialEq + Eq + Hash + 'static,
+    T: 'static,
+{
+    type Weak = KeyedSubfield<Inner::Weak, Prev, K, T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        KeyedSubfield {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            path_segment: self.path_segment,
+            inner: self.inner.downgrade(),
+            read: self.read,
+            write: self.write,
+            key_fn: self.key_fn,
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        Some(KeyedSubfield {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: weak.defined_at,
+            path_segment: weak.path_segment,
+            inner: Inner::upgrade(&weak.inner)?,
+            read: weak.read,
+            write: weak.write,
+            key_fn: weak.key_fn,
+        })
+    }
+}
+
 impl<Inner, Prev, K, T> DefinedAt for KeyedSubfield<Inner, Prev, K, T>
 where
     for<'a> &'a T: IntoIterator,
@@ -284,6 +320,7 @@ where
     K: Debug + Send + Sync + PartialEq + Eq + Hash + 'static,
 {
     fn notify(&self) {
+        record_change(self);
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
@@ -331,10 +368,12 @@ where
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        record_change(self);
+        let this = this_trigger(self);
         let guard = self.writer()?;
         Some(KeyedSubfieldWriteGuard {
             inner: self.clone(),
-            guard: Some(guard),
+            guard: Some(WriteGuard::new(this, guard)),
         })
     }
 
@@ -502,6 +541,40 @@ where
     }
 }
 
+impl<Inner, Prev, K, T> DowngradeField for AtKeyed<Inner, Prev, K, T>
+where
+    K: Debug + Clone + Send + Sync + PartialEq + Eq + Hash + 'static,
+    KeyedSubfield<Inner, Prev, K, T>: Clone,
+    for<'a> &'a T: IntoIterator,
+    Inner: DowngradeField<Value = Prev>,
+    Prev: 'static,
+    T: IndexMut<usize> + 'static,
+    T::Output: Sized,
+{
+    type Weak = AtKeyed<Inner::Weak, Prev, K, T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        AtKeyed {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            inner: self.inner.downgrade(),
+            key: self.key.clone(),
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        let field = AtKeyed {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: weak.defined_at,
+            inner: KeyedSubfield::upgrade(&weak.inner)?,
+            key: weak.key.clone(),
+        };
+        // the item is gone once its key has been removed
+        field.reader()?;
+        Some(field)
+    }
+}
+
 impl<Inner, Prev, K, T> DefinedAt for AtKeyed<Inner, Prev, K, T>
 where
     for<'a> &'a T: IntoIterator,
@@ -539,6 +612,7 @@ where
     T::Output: Sized,
 {
     fn notify(&self) {
+        record_change(self);
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
@@ -590,7 +664,9 @@ where
     type Value = T::Output;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        record_change(self);
+        let this = this_trigger(self);
+        self.writer().map(|writer| WriteGuard::new(this, writer))
     }
 
     fn try_write_untracked(
@@ -179,6 +179,11 @@
 //! // Note the use of the accessor method here .second_0()
 //! assert_eq!(choice_two.second_0().unwrap().get(), "hello");
 //! ```
+//! Checking which variant is active only subscribes to writes that replace the enum itself or
+//! its parent, so code that only checks the variant does not re-run when the fields of that
+//! variant change, and each field of each variant has its own path.
+//! Deriving [`Patch`](macro@Patch) for an enum patches the fields in place if the variant has not
+//! changed, and replaces the whole value, notifying everything that depends on it, if it has.
 //! #### Box
 //! [`Box<T>`](std::boxed::Box) also requires some special treatment in how you dereference elements of the Box, especially
 //! when trying to build a recursive data structure.  [DerefField](trait@DerefField) provides a [.deref_value()](DerefField::deref_field) method to access
@@ -261,14 +266,23 @@ use std::{
     hash::Hash,
     ops::DerefMut,
     panic::Location,
-    sync::{Arc, RwLock},
+    sync::{Arc, RwLock, Weak},
 };
 
 mod arc_field;
+mod computed;
 mod deref;
 mod field;
+mod form;
 mod iter;
+#[cfg(feature = "serde")]
+mod journal;
+#[cfg(feature = "serde")]
+mod json_field;
+#[cfg(feature = "serde")]
+mod json_patch;
 mod keyed;
+mod map;
 mod option;
 mod patch;
 mod path;
@@ -276,14 +290,50 @@ mod store_field;
 mod subfield;
 
 pub use arc_field::ArcField;
+use computed::ComputedCache;
+pub use computed::*;
 pub use deref::*;
 pub use field::Field;
+pub use form::*;
 pub use iter::*;
+#[cfg(feature = "serde")]
+pub use journal::*;
+#[cfg(feature = "serde")]
+pub use json_field::{FieldCursor, JsonField};
+#[cfg(feature = "serde")]
+pub use json_patch::{
+    JsonPatch, JsonPatchError, JsonPatchField, JsonPatchOperation,
+    JsonPatchStoreExt,
+};
 pub use keyed::*;
+pub use map::*;
 pub use option::*;
 pub use patch::*;
 pub use path::{StorePath, StorePathSegment};
+use store_field::record_change;
 pub use store_field::StoreField;
+
+/// Expands to its input only if the `serde` feature is enabled.
+///
+/// `#[derive(Store)]` uses this for its implementation of `JsonField`.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_serde {
+    ($($tokens:tt)*) => {
+        $($tokens)*
+    };
+}
+
+/// Expands to its input only if the `serde` feature is enabled.
+///
+/// `#[derive(Store)]` uses this for its implementation of `JsonField`.
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __with_serde {
+    ($($tokens:tt)*) => {};
+}
 pub use subfield::Subfield;
 
 #[derive(Debug, Default)]
@@ -344,7 +394,8 @@ where
 
         Self {
             spare_keys: Vec::new(),
-            current_key: 0,
+            // `next_key` increments before returning, so new keys will not reuse these segments
+            current_key: keys.len().saturating_sub(1),
             keys,
         }
     }
@@ -358,6 +409,13 @@ where
         self.keys.get(key).copied()
     }
 
+    #[cfg(feature = "serde")]
+    fn key(&self, segment: StorePathSegment) -> Option<&K> {
+        self.keys
+            .iter()
+            .find_map(|(key, (this, _))| (*this == segment).then_some(key))
+    }
+
     fn next_key(&mut self) -> StorePathSegment {
         self.spare_keys.pop().unwrap_or_else(|| {
             self.current_key += 1;
@@ -407,11 +465,48 @@ impl<K> Default for FieldKeys<K> {
     }
 }
 
-/// A map of the keys for a keyed subfield.
+type AnyMap<K> = Arc<RwLock<HashMap<K, Box<dyn Any + Send + Sync>>>>;
+
+/// The state that a store keeps alongside its value: the keys for its keyed subfields, the
+/// computed fields that have been cached, and the journals that are recording its changes.
 #[derive(Default, Clone)]
-pub struct KeyMap(Arc<RwLock<HashMap<StorePath, Box<dyn Any + Send + Sync>>>>);
+pub struct KeyMap {
+    keys: AnyMap<StorePath>,
+    computed: Arc<ComputedCache>,
+    #[cfg(feature = "serde")]
+    journals: Arc<journal::Journals>,
+}
+
+/// A [`KeyMap`] that does not keep the keys of its store alive.
+#[derive(Clone)]
+struct WeakKeyMap {
+    keys: Weak<RwLock<HashMap<StorePath, Box<dyn Any + Send + Sync>>>>,
+    computed: Weak<ComputedCache>,
+    #[cfg(feature = "serde")]
+    journals: Weak<journal::Journals>,
+}
+
+impl WeakKeyMap {
+    fn upgrade(&self) -> Option<KeyMap> {
+        Some(KeyMap {
+            keys: self.keys.upgrade()?,
+            computed: self.computed.upgrade()?,
+            #[cfg(feature = "serde")]
+            journals: self.journals.upgrade()?,
+        })
+    }
+}
 
 impl KeyMap {
+    fn downgrade(&self) -> WeakKeyMap {
+        WeakKeyMap {
+            keys: Arc::downgrade(&self.keys),
+            computed: Arc::downgrade(&self.computed),
+            #[cfg(feature = "serde")]
+            journals: Arc::downgrade(&self.journals),
+        }
+    }
+
     fn with_field_keys<K, T>(
         &self,
         path: StorePath,
@@ -428,7 +523,7 @@ impl KeyMap {
         // while inserting the keys on this child.
         //
         // see here https://github.com/leptos-rs/leptos/issues/3086
-        let mut guard = self.0.write().or_poisoned();
+        let mut guard = self.keys.write().or_poisoned();
         if guard.contains_key(&path) {
             let entry = guard.get_mut(&path)?;
             let entry = entry.downcast_mut::<FieldKeys<K>>()?;
@@ -436,12 +531,51 @@ impl KeyMap {
         } else {
             drop(guard);
             let keys = Box::new(FieldKeys::new(initialize()));
-            let mut guard = self.0.write().or_poisoned();
+            let mut guard = self.keys.write().or_poisoned();
             let entry = guard.entry(path).or_insert(keys);
             let entry = entry.downcast_mut::<FieldKeys<K>>()?;
             Some(fun(entry))
         }
     }
+
+    /// Reads the keys for the keyed field at `path`, if they have been created, without creating
+    /// or updating them.
+    fn read_field_keys<K, T>(
+        &self,
+        path: &StorePath,
+        fun: impl FnOnce(&FieldKeys<K>) -> T,
+    ) -> Option<T>
+    where
+        K: 'static,
+    {
+        let guard = self.keys.read().or_poisoned();
+        let entry = guard.get(path)?.downcast_ref::<FieldKeys<K>>()?;
+        Some(fun(entry))
+    }
+
+    /// Updates the keys for the keyed field at `path`, if they have been created.
+    #[cfg(feature = "serde")]
+    fn with_existing_field_keys<K, T>(
+        &self,
+        path: &StorePath,
+        fun: impl FnOnce(&mut FieldKeys<K>) -> T,
+    ) -> Option<T>
+    where
+        K: 'static,
+    {
+        let mut guard = self.keys.write().or_poisoned();
+        let entry = guard.get_mut(path)?.downcast_mut::<FieldKeys<K>>()?;
+        Some(fun(entry))
+    }
+
+    /// Records a change to the field at `path` in each `StoreJournal` of the store.
+    #[inline(always)]
+    pub(crate) fn record_change(&self, path: impl FnOnce() -> StorePath) {
+        #[cfg(feature = "serde")]
+        self.journals.record(path);
+        #[cfg(not(feature = "serde"))]
+        let _ = path;
+    }
 }
 
 /// A reference-counted container for a reactive store.
@@ -559,6 +693,7 @@ impl<T: 'static> Track for ArcStore<T> {
 
 impl<T: 'static> Notify for ArcStore<T> {
     fn notify(&self) {
+        record_change(self);
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
@@ -903,6 +1038,33 @@ mod tests {
         assert_eq!(combined_count.load(Ordering::Relaxed), 3);
     }
 
+    #[tokio::test]
+    async fn setting_a_field_notifies_its_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let combined_count = Arc::new(AtomicUsize::new(0));
+
+        let store = Store::new(data());
+        let todo = store.todos().at_unkeyed(0);
+
+        Effect::new_sync({
+            let combined_count = Arc::clone(&combined_count);
+            move |_| {
+                todo.label().read();
+                combined_count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        todo.set(Todo {
+            label: "Rewrite the label".into(),
+            completed: false,
+        });
+        tick().await;
+        todo.completed().set(true);
+        tick().await;
+        assert_eq!(combined_count.load(Ordering::Relaxed), 2);
+    }
+
     #[tokio::test]
     async fn changes_do_notify_parent() {
         _ = any_spawner::Executor::init_tokio();
@@ -1083,8 +1245,231 @@ mod tests {
         assert_eq!(combined_count.load(Ordering::Relaxed), 3);
     }
 
+    #[tokio::test]
+    async fn lens_fields_share_tracking_with_their_source() {
+        use crate::Field;
+
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let label: Field<String> = store.todos().at_unkeyed(0).label().into();
+        let words = label.lens(
+            |label| label.split(' ').map(String::from).collect::<Vec<_>>(),
+            |label, words| *label = words.join(" "),
+        );
+
+        let source_runs = Arc::new(AtomicUsize::new(0));
+        let lens_runs = Arc::new(AtomicUsize::new(0));
+        Effect::new_sync({
+            let source_runs = Arc::clone(&source_runs);
+            move |_| {
+                label.read();
+                source_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let lens_runs = Arc::clone(&lens_runs);
+            move |_| {
+                words.read();
+                lens_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+
+        words.write().push("today".to_string());
+        tick().await;
+        assert_eq!(
+            store
+                .todos()
+                .at_unkeyed(0)
+                .label()
+                .read_untracked()
+                .as_str(),
+            "Create reactive store today"
+        );
+        assert_eq!(source_runs.load(Ordering::Relaxed), 2);
+        assert_eq!(lens_runs.load(Ordering::Relaxed), 2);
+
+        label.set("Write tests".to_string());
+        tick().await;
+        assert_eq!(*words.read_untracked(), vec!["Write", "tests"]);
+        assert_eq!(lens_runs.load(Ordering::Relaxed), 3);
+
+        // other fields are unaffected
+        store.user().set("Carol".to_string());
+        tick().await;
+        assert_eq!(lens_runs.load(Ordering::Relaxed), 3);
+    }
+
+    #[derive(Debug, Store, Patch)]
+    struct Feed {
+        #[store(key: usize = |item| item.id)]
+        items: Vec<FeedItem>,
+    }
+
+    #[derive(Debug, Store, Patch)]
+    struct FeedItem {
+        id: usize,
+        content: Content,
+    }
+
+    #[derive(Debug, Store, Patch)]
+    enum Content {
+        Text { title: String, body: String },
+        Image(String, u32),
+        Empty,
+    }
+
+    #[tokio::test]
+    async fn keyed_enum_variants_only_notify_their_own_fields() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(Feed {
+            items: vec![
+                FeedItem {
+                    id: 1,
+                    content: Content::Text {
+                        title: "Hello".into(),
+                        body: "World".into(),
+                    },
+                },
+                FeedItem {
+                    id: 2,
+                    content: Content::Image("cat.png".into(), 100),
+                },
+            ],
+        });
+        let mut rows = store.items().into_iter();
+        let item = rows.next().unwrap();
+        let row = item.content();
+        let other = rows.next().unwrap().content();
+
+        let variant_runs = Arc::new(AtomicUsize::new(0));
+        let title_runs = Arc::new(AtomicUsize::new(0));
+        let body_runs = Arc::new(AtomicUsize::new(0));
+        Effect::new_sync({
+            let variant_runs = Arc::clone(&variant_runs);
+            move |_| {
+                _ = (row.text(), row.image(), row.empty());
+                variant_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let title_runs = Arc::clone(&title_runs);
+            move |_| {
+                if let Some(title) = row.text_title() {
+                    title.read();
+                }
+                title_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Effect::new_sync({
+            let body_runs = Arc::clone(&body_runs);
+            move |_| {
+                if let Some(body) = row.text_body() {
+                    body.read();
+                }
+                body_runs.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        tick().await;
+        let runs = || {
+            (
+                variant_runs.load(Ordering::Relaxed),
+                title_runs.load(Ordering::Relaxed),
+                body_runs.load(Ordering::Relaxed),
+            )
+        };
+        assert_eq!(runs(), (1, 1, 1));
+
+        // updating a field of the current variant does not notify its siblings or the variant
+        row.text_title().unwrap().set("Goodbye".into());
+        tick().await;
+        assert_eq!(runs(), (1, 2, 1));
+
+        // patching with the same variant only notifies the fields that changed
+        row.patch(Content::Text {
+            title: "Goodbye".into(),
+            body: "Moon".into(),
+        });
+        tick().await;
+        assert_eq!(runs(), (1, 2, 2));
+
+        // changing the variant notifies the branch
+        row.patch(Content::Empty);
+        tick().await;
+        assert_eq!(runs(), (2, 3, 3));
+        assert!(row.empty() && row.text_title().is_none());
+
+        // so does replacing the value directly
+        row.set(Content::Image("dog.png".into(), 50));
+        tick().await;
+        assert_eq!(runs().0, 3);
+        assert_eq!(*row.image_1().unwrap().read_untracked(), 50);
+
+        // and replacing the item that holds it
+        item.write().content = Content::Empty;
+        tick().await;
+        assert_eq!(runs().0, 4);
+        assert!(row.empty());
+
+        // the other rows are unaffected
+        assert_eq!(*other.image_0().unwrap().read_untracked(), "cat.png");
+    }
+
     #[derive(Debug, Store)]
     pub struct StructWithOption {
         opt_field: Option<Todo>,
     }
+
+    #[cfg(feature = "snapshot")]
+    #[test]
+    fn store_snapshot_and_restore() {
+        use reactive_graph::{snapshot::SnapshotRegistry, traits::Get};
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, Store, Serialize, Deserialize)]
+        struct Settings {
+            theme: String,
+            font_size: u8,
+        }
+
+        let store = Store::new(Settings {
+            theme: "dark".into(),
+            font_size: 14,
+        });
+        let font_size = Store::new(12u8);
+
+        let registry = SnapshotRegistry::new();
+        registry.register("settings", store);
+        registry.register("theme", store.theme());
+        registry.register("font_size", font_size);
+
+        let snapshot = registry.snapshot().unwrap();
+        assert_eq!(
+            serde_json::to_string(&snapshot).unwrap(),
+            r#"{"font_size":12,"settings":{"font_size":14,"theme":"dark"},"theme":"dark"}"#
+        );
+
+        store.theme().set("light".into());
+        font_size.set(16);
+        registry.unregister("theme");
+        registry.restore(&snapshot).unwrap();
+        assert_eq!(store.theme().get(), "dark");
+        assert_eq!(font_size.get(), 12);
+    }
+
+    #[test]
+    fn added_keys_do_not_reuse_initial_segments() {
+        use crate::FieldKeys;
+        use std::collections::HashSet;
+
+        let mut keys = FieldKeys::new(vec!["a", "b", "c"]);
+        keys.update(["a", "b", "c", "d", "e"]);
+        let segments = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|key| keys.get(key).unwrap().0)
+            .collect::<HashSet<_>>();
+        assert_eq!(segments.len(), 5);
+    }
 }
@@ -0,0 +1,716 @@
+use crate::{
+    path::{StorePath, StorePathSegment},
+    store_field::{record_change, this_trigger, DowngradeField, StoreField},
+    KeyMap, StoreFieldTrigger,
+};
+use reactive_graph::{
+    signal::{
+        guards::{MappedMutArc, WriteGuard},
+        ArcTrigger,
+    },
+    traits::{
+        DefinedAt, IsDisposed, Notify, ReadUntracked, Track, UntrackableGuard,
+        Write,
+    },
+};
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fmt::Debug,
+    hash::{BuildHasher, Hash},
+    iter,
+    marker::PhantomData,
+    ops::DerefMut,
+    panic::Location,
+};
+
+/// The path segment, below a map field, of the trigger that tracks which keys the map contains.
+///
+/// The segments for individual entries are allocated from zero, so this will never be used by one.
+const KEY_SET: StorePathSegment = StorePathSegment(usize::MAX);
+
+/// A map whose entries can be accessed as individual store fields.
+///
+/// This is implemented for [`HashMap`] and [`BTreeMap`]. See [`MapStoreExt`].
+pub trait StoreMap {
+    /// The type of the keys in the map.
+    type Key;
+    /// The type of the values in the map.
+    type Value;
+
+    /// Returns a reference to the value for this key.
+    fn get_value(&self, key: &Self::Key) -> Option<&Self::Value>;
+
+    /// Returns a mutable reference to the value for this key.
+    fn get_value_mut(&mut self, key: &Self::Key) -> Option<&mut Self::Value>;
+
+    /// Inserts a value, returning the previous value for this key.
+    fn insert_value(
+        &mut self,
+        key: Self::Key,
+        value: Self::Value,
+    ) -> Option<Self::Value>;
+
+    /// Removes and returns the value for this key.
+    fn remove_value(&mut self, key: &Self::Key) -> Option<Self::Value>;
+
+    /// Returns the keys in the map, in its iteration order.
+    fn key_list(&self) -> Vec<Self::Key>;
+}
+
+impl<K, V, S> StoreMap for HashMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get_value(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn get_value_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    fn insert_value(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(key, value)
+    }
+
+    fn remove_value(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn key_list(&self) -> Vec<K> {
+        self.keys().cloned().collect()
+    }
+}
+
+impl<K, V> StoreMap for BTreeMap<K, V>
+where
+    K: Ord + Clone,
+{
+    type Key = K;
+    type Value = V;
+
+    fn get_value(&self, key: &K) -> Option<&V> {
+        self.get(key)
+    }
+
+    fn get_value_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.get_mut(key)
+    }
+
+    fn insert_value(&mut self, key: K, value: V) -> Option<V> {
+        self.insert(key, value)
+    }
+
+    fn remove_value(&mut self, key: &K) -> Option<V> {
+        self.remove(key)
+    }
+
+    fn key_list(&self) -> Vec<K> {
+        self.keys().cloned().collect()
+    }
+}
+
+/// Extends store fields that contain a map with access to individual entries.
+///
+/// Each entry is tracked separately: updating the value for one key only notifies subscribers to
+/// that entry (and to the map as a whole), and inserting or removing a key only notifies that
+/// entry and subscribers to the set of keys, like [`entries`](MapStoreExt::entries).
+///
+/// ```rust
+/// use reactive_graph::traits::{Get, Set};
+/// use reactive_stores::{MapStoreExt, Store};
+/// use std::collections::HashMap;
+///
+/// #[derive(Store)]
+/// struct Cache {
+///     users: HashMap<u32, String>,
+/// }
+///
+/// let store = Store::new(Cache {
+///     users: HashMap::from([(1, "Alice".to_string())]),
+/// });
+///
+/// let alice = store.users().get_key(1);
+/// assert_eq!(alice.get(), "Alice");
+/// alice.set("Alicia".to_string());
+///
+/// store.users().insert_key(2, "Bob".to_string());
+/// assert_eq!(store.users().get_key(2).get(), "Bob");
+/// assert_eq!(store.users().entries().count(), 2);
+///
+/// // reading a missing key returns `None`
+/// store.users().remove_key(&2);
+/// assert_eq!(store.users().get_key(2).try_get(), None);
+/// ```
+pub trait MapStoreExt<M>
+where
+    Self: StoreField<Value = M>,
+    M: StoreMap,
+{
+    /// Reactive access to the entry for this key.
+    ///
+    /// The entry can be created before the key is in the map. Reading it returns `None` until
+    /// the key is inserted.
+    fn get_key(self, key: M::Key) -> AtKey<Self, M>;
+
+    /// Inserts a value for this key, returning the previous value.
+    fn insert_key(&self, key: M::Key, value: M::Value) -> Option<M::Value>;
+
+    /// Removes the value for this key, and returns it.
+    fn remove_key(&self, key: &M::Key) -> Option<M::Value>;
+
+    /// Returns `true` if the map contains this key, tracking the set of keys.
+    fn contains_key(&self, key: &M::Key) -> bool;
+
+    /// An iterator over the entries of the map, as reactive fields.
+    ///
+    /// This tracks the set of keys, but not the values.
+    fn entries(self) -> MapStoreIter<Self, M>;
+}
+
+impl<Inner, M> MapStoreExt<M> for Inner
+where
+    Inner: StoreField<Value = M> + Clone,
+    M: StoreMap + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+{
+    #[track_caller]
+    fn get_key(self, key: M::Key) -> AtKey<Self, M> {
+        AtKey::new(self, key)
+    }
+
+    fn insert_key(&self, key: M::Key, value: M::Value) -> Option<M::Value> {
+        let prev = self.writer()?.insert_value(key.clone(), value);
+        let entry = AtKey::new(self.clone(), key);
+        entry.refresh_keys();
+        entry.notify();
+        if prev.is_none() {
+            entry.key_set_trigger().this.notify();
+        }
+        prev
+    }
+
+    fn remove_key(&self, key: &M::Key) -> Option<M::Value> {
+        let prev = self.writer()?.remove_value(key)?;
+        // notify the entry while it still has a path, then free its slot
+        let entry = AtKey::new(self.clone(), key.clone());
+        entry.notify();
+        entry.key_set_trigger().this.notify();
+        entry.refresh_keys();
+        Some(prev)
+    }
+
+    fn contains_key(&self, key: &M::Key) -> bool {
+        let path = self.path().into_iter().chain(iter::once(KEY_SET));
+        self.get_trigger(path.collect()).this.track();
+        self.reader()
+            .map(|map| map.get_value(key).is_some())
+            .unwrap_or(false)
+    }
+
+    #[track_caller]
+    fn entries(self) -> MapStoreIter<Self, M> {
+        let path = self.path().into_iter().chain(iter::once(KEY_SET));
+        self.get_trigger(path.collect()).this.track();
+        let keys = self
+            .reader()
+            .map(|map| map.key_list())
+            .unwrap_or_default()
+            .into();
+        MapStoreIter { inner: self, keys }
+    }
+}
+
+/// Gives access to the value for one key in a map.
+#[derive(Debug)]
+pub struct AtKey<Inner, M>
+where
+    M: StoreMap,
+{
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    inner: Inner,
+    key: M::Key,
+    ty: PhantomData<M>,
+}
+
+impl<Inner, M> Clone for AtKey<Inner, M>
+where
+    Inner: Clone,
+    M: StoreMap,
+    M::Key: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            inner: self.inner.clone(),
+            key: self.key.clone(),
+            ty: self.ty,
+        }
+    }
+}
+
+impl<Inner, M> Copy for AtKey<Inner, M>
+where
+    Inner: Copy,
+    M: StoreMap,
+    M::Key: Copy,
+{
+}
+
+impl<Inner, M> AtKey<Inner, M>
+where
+    M: StoreMap,
+{
+    /// Provides access to the entry for this key in the inner map.
+    #[track_caller]
+    pub fn new(inner: Inner, key: M::Key) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            inner,
+            key,
+            ty: PhantomData,
+        }
+    }
+
+    /// The key of this entry.
+    pub fn key(&self) -> &M::Key {
+        &self.key
+    }
+}
+
+impl<Inner, M> AtKey<Inner, M>
+where
+    Inner: StoreField<Value = M>,
+    M: StoreMap + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+{
+    fn latest_keys(&self) -> Vec<M::Key> {
+        self.inner
+            .reader()
+            .map(|map| map.key_list())
+            .unwrap_or_default()
+    }
+
+    /// The path segment for this entry, or `None` if the key is not in the map.
+    fn segment(&self) -> Option<StorePathSegment> {
+        let inner_path = self.inner.path().into_iter().collect::<StorePath>();
+        let keys = self.inner.keys()?;
+        let segment = keys
+            .with_field_keys(
+                inner_path.clone(),
+                |keys| keys.get(&self.key),
+                || self.latest_keys(),
+            )
+            .flatten();
+        match segment {
+            Some((segment, _)) => Some(segment),
+            // the map may have been changed without going through `MapStoreExt`
+            None => {
+                self.reader()?;
+                self.refresh_keys();
+                keys.with_field_keys(
+                    inner_path,
+                    |keys| keys.get(&self.key),
+                    || self.latest_keys(),
+                )
+                .flatten()
+                .map(|(segment, _)| segment)
+            }
+        }
+    }
+
+    fn refresh_keys(&self) {
+        let inner_path = self.inner.path().into_iter().collect();
+        let Some(keys) = self.inner.keys() else {
+            return;
+        };
+        let latest = self.latest_keys();
+        keys.with_field_keys(
+            inner_path,
+            |keys| keys.update(latest),
+            || self.latest_keys(),
+        );
+    }
+
+    fn key_set_trigger(&self) -> StoreFieldTrigger {
+        let path = self.inner.path().into_iter().chain(iter::once(KEY_SET));
+        self.inner.get_trigger(path.collect())
+    }
+}
+
+impl<Inner, M> StoreField for AtKey<Inner, M>
+where
+    Inner: StoreField<Value = M>,
+    M: StoreMap + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+    M::Value: Sized,
+{
+    type Value = M::Value;
+    type Reader = MappedMutArc<Inner::Reader, M::Value>;
+    type Writer = WriteGuard<ArcTrigger, MappedMutArc<Inner::Writer, M::Value>>;
+
+    fn path(&self) -> impl IntoIterator<Item = StorePathSegment> {
+        // a missing entry shares the path of the key set, which is notified when it is inserted
+        let segment = self.segment().unwrap_or(KEY_SET);
+        self.inner.path().into_iter().chain(iter::once(segment))
+    }
+
+    fn get_trigger(&self, path: StorePath) -> StoreFieldTrigger {
+        self.inner.get_trigger(path)
+    }
+
+    fn reader(&self) -> Option<Self::Reader> {
+        let inner = self.inner.reader()?;
+        inner.get_value(&self.key)?;
+        let key = self.key.clone();
+        let key_mut = self.key.clone();
+        Some(MappedMutArc::new(
+            inner,
+            move |map| map.get_value(&key).expect("entry was removed"),
+            move |map| map.get_value_mut(&key_mut).expect("entry was removed"),
+        ))
+    }
+
+    fn writer(&self) -> Option<Self::Writer> {
+        let trigger = self.get_trigger(self.path().into_iter().collect());
+        let inner = self.inner.writer()?;
+        inner.get_value(&self.key)?;
+        let key = self.key.clone();
+        let key_mut = self.key.clone();
+        Some(WriteGuard::new(
+            trigger.children,
+            MappedMutArc::new(
+                inner,
+                move |map| map.get_value(&key).expect("entry was removed"),
+                move |map| {
+                    map.get_value_mut(&key_mut).expect("entry was removed")
+                },
+            ),
+        ))
+    }
+
+    #[inline(always)]
+    fn keys(&self) -> Option<KeyMap> {
+        self.inner.keys()
+    }
+
+    fn track_field(&self) {
+        let inner = self
+            .inner
+            .get_trigger(self.inner.path().into_iter().collect());
+        inner.this.track();
+        let trigger = self.get_trigger(self.path().into_iter().collect());
+        trigger.this.track();
+        trigger.children.track();
+    }
+}
+
+impl<Inner, M> DowngradeField for AtKey<Inner, M>
+where
+    Inner: DowngradeField<Value = M>,
+    M: StoreMap + Send + Sync + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+    M::Value: Sized,
+{
+    type Weak = AtKey<Inner::Weak, M>;
+
+    fn downgrade(&self) -> Self::Weak {
+        AtKey {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            inner: self.inner.downgrade(),
+            key: self.key.clone(),
+            ty: self.ty,
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        let field = AtKey {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: weak.defined_at,
+            inner: Inner::upgrade(&weak.inner)?,
+            key: weak.key.clone(),
+            ty: weak.ty,
+        };
+        // the entry is gone once its key has been removed
+        field.reader()?;
+        Some(field)
+    }
+}
+
+impl<Inner, M> DefinedAt for AtKey<Inner, M>
+where
+    M: StoreMap,
+{
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        {
+            Some(self.defined_at)
+        }
+        #[cfg(not(any(debug_assertions, leptos_debuginfo)))]
+        {
+            None
+        }
+    }
+}
+
+impl<Inner, M> IsDisposed for AtKey<Inner, M>
+where
+    Inner: IsDisposed,
+    M: StoreMap,
+{
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<Inner, M> Notify for AtKey<Inner, M>
+where
+    Inner: StoreField<Value = M>,
+    M: StoreMap + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+{
+    fn notify(&self) {
+        record_change(self);
+        let trigger = self.get_trigger(self.path().into_iter().collect());
+        trigger.this.notify();
+        trigger.children.notify();
+    }
+}
+
+impl<Inner, M> Track for AtKey<Inner, M>
+where
+    Inner: StoreField<Value = M>,
+    M: StoreMap + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+{
+    fn track(&self) {
+        self.track_field();
+    }
+}
+
+impl<Inner, M> ReadUntracked for AtKey<Inner, M>
+where
+    Inner: StoreField<Value = M>,
+    M: StoreMap + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+{
+    type Value = <Self as StoreField>::Reader;
+
+    fn try_read_untracked(&self) -> Option<Self::Value> {
+        self.reader()
+    }
+}
+
+impl<Inner, M> Write for AtKey<Inner, M>
+where
+    Inner: StoreField<Value = M>,
+    M: StoreMap + 'static,
+    M::Key: Debug + Clone + Hash + Eq + Send + Sync + 'static,
+    M::Value: 'static,
+{
+    type Value = M::Value;
+
+    fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
+        record_change(self);
+        let this = this_trigger(self);
+        self.writer().map(|writer| WriteGuard::new(this, writer))
+    }
+
+    fn try_write_untracked(
+        &self,
+    ) -> Option<impl DerefMut<Target = Self::Value>> {
+        self.writer().map(|mut writer| {
+            writer.untrack();
+            writer
+        })
+    }
+}
+
+/// An iterator over the entries of a map, as reactive fields.
+///
+/// See [`MapStoreExt::entries`].
+pub struct MapStoreIter<Inner, M>
+where
+    M: StoreMap,
+{
+    inner: Inner,
+    keys: VecDeque<M::Key>,
+}
+
+impl<Inner, M> Iterator for MapStoreIter<Inner, M>
+where
+    Inner: Clone,
+    M: StoreMap,
+{
+    type Item = AtKey<Inner, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.keys
+            .pop_front()
+            .map(|key| AtKey::new(self.inner.clone(), key))
+    }
+}
+
+impl<Inner, M> DoubleEndedIterator for MapStoreIter<Inner, M>
+where
+    Inner: Clone,
+    M: StoreMap,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.keys
+            .pop_back()
+            .map(|key| AtKey::new(self.inner.clone(), key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as reactive_stores, MapStoreExt, Store};
+    use reactive_graph::{
+        effect::Effect,
+        traits::{Get, GetUntracked, Read, Set, Update},
+    };
+    use std::{
+        collections::{BTreeMap, HashMap},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    pub async fn tick() {
+        tokio::time::sleep(std::time::Duration::from_micros(1)).await;
+    }
+
+    #[derive(Debug, Store, Default)]
+    struct Cache {
+        users: HashMap<u32, String>,
+        sorted: BTreeMap<String, i32>,
+    }
+
+    fn data() -> Cache {
+        Cache {
+            users: HashMap::from([
+                (1, "Alice".to_string()),
+                (2, "Bob".to_string()),
+                (3, "Carol".to_string()),
+            ]),
+            sorted: BTreeMap::from([
+                ("b".to_string(), 2),
+                ("a".to_string(), 1),
+            ]),
+        }
+    }
+
+    fn counting_effect(
+        fun: impl Fn() + Send + Sync + 'static,
+    ) -> Arc<AtomicUsize> {
+        let count = Arc::new(AtomicUsize::new(0));
+        Effect::new_sync({
+            let count = Arc::clone(&count);
+            move |_| {
+                fun();
+                count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        count
+    }
+
+    #[tokio::test]
+    async fn updating_an_entry_only_notifies_that_entry() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let alice = counting_effect(move || {
+            store.users().get_key(1).read();
+        });
+        let bob = counting_effect(move || {
+            store.users().get_key(2).read();
+        });
+        let keys = counting_effect(move || {
+            store.users().entries().count();
+        });
+        tick().await;
+
+        store.users().get_key(1).set("Alicia".to_string());
+        tick().await;
+        store.users().get_key(1).update(|name| name.push('!'));
+        tick().await;
+
+        assert_eq!(store.users().get_key(1).get_untracked(), "Alicia!");
+        assert_eq!(alice.load(Ordering::Relaxed), 3);
+        assert_eq!(bob.load(Ordering::Relaxed), 1);
+        assert_eq!(keys.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn inserting_and_removing_notify_entry_and_key_set() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        let dave = counting_effect(move || {
+            store.users().get_key(4).try_get();
+        });
+        let bob = counting_effect(move || {
+            store.users().get_key(2).read();
+        });
+        let keys = counting_effect(move || {
+            store.users().entries().count();
+        });
+        tick().await;
+
+        assert_eq!(store.users().insert_key(4, "Dave".to_string()), None);
+        tick().await;
+        assert_eq!(store.users().get_key(4).get_untracked(), "Dave");
+        // replacing an existing value does not change the key set
+        store.users().insert_key(4, "David".to_string());
+        tick().await;
+        assert_eq!(store.users().remove_key(&4), Some("David".to_string()));
+        tick().await;
+        assert_eq!(store.users().remove_key(&4), None);
+        tick().await;
+
+        assert_eq!(store.users().get_key(4).try_get(), None);
+        assert_eq!(dave.load(Ordering::Relaxed), 4);
+        assert_eq!(bob.load(Ordering::Relaxed), 1);
+        assert_eq!(keys.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn btree_entries_iterate_in_order() {
+        _ = any_spawner::Executor::init_tokio();
+
+        let store = Store::new(data());
+        store.sorted().insert_key("c".to_string(), 3);
+        assert!(store.sorted().contains_key(&"c".to_string()));
+
+        let entries = store
+            .sorted()
+            .entries()
+            .map(|entry| (entry.key().clone(), entry.get_untracked()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            entries,
+            vec![
+                ("a".to_string(), 1),
+                ("b".to_string(), 2),
+                ("c".to_string(), 3)
+            ]
+        );
+
+        // entries of keys added without `insert_key` can still be accessed
+        store.sorted().update(|map| {
+            map.insert("d".to_string(), 4);
+        });
+        store.sorted().get_key("d".to_string()).set(5);
+        assert_eq!(store.sorted().read().get("d"), Some(&5));
+    }
+}
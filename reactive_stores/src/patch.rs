@@ -31,10 +31,14 @@ where
 
     fn patch(&self, new: Self::Value) {
         let path = self.path().into_iter().collect::<StorePath>();
+        let keys = self.keys();
         if let Some(mut writer) = self.writer() {
             // don't track the writer for the whole store
             writer.untrack();
             let mut notify = |path: &StorePath| {
+                if let Some(keys) = &keys {
+                    keys.record_change(|| path.to_owned());
+                }
                 self.get_trigger(path.to_owned()).this.notify();
                 self.get_trigger(path.to_owned()).children.notify();
             };
@@ -1,5 +1,6 @@
 /// The path of a field within some store.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StorePath(Vec<StorePathSegment>);
 
 impl IntoIterator for StorePath {
@@ -28,6 +29,11 @@ impl StorePath {
         self.0.pop()
     }
 
+    /// Returns the segments of the path.
+    pub fn as_slice(&self) -> &[StorePathSegment] {
+        &self.0
+    }
+
     /// Updates the last segment in the place in place.
     pub fn replace_last(&mut self, segment: impl Into<StorePathSegment>) {
         if let Some(last) = self.0.last_mut() {
@@ -38,6 +44,7 @@ impl StorePath {
 
 /// One segment of a [`StorePath`].
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StorePathSegment(pub(crate) usize);
 
 impl From<usize> for StorePathSegment {
@@ -1,6 +1,6 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    ArcStore, KeyMap, Store, StoreFieldTrigger,
+    ArcStore, KeyMap, Store, StoreFieldTrigger, TriggerMap, WeakKeyMap,
 };
 use or_poisoned::OrPoisoned;
 use reactive_graph::{
@@ -9,10 +9,16 @@ use reactive_graph::{
         guards::{Plain, UntrackedWriteGuard, WriteGuard},
         ArcTrigger,
     },
-    traits::{DefinedAt, Track, UntrackableGuard},
+    traits::{DefinedAt, IsDisposed, Track, UntrackableGuard},
     unwrap_signal,
 };
-use std::{iter, ops::Deref, sync::Arc};
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use std::panic::Location;
+use std::{
+    iter,
+    ops::Deref,
+    sync::{Arc, RwLock, Weak},
+};
 
 /// Describes a type that can be accessed as a reactive store field.
 pub trait StoreField: Sized {
@@ -53,6 +59,65 @@ pub trait StoreField: Sized {
     fn keys(&self) -> Option<KeyMap>;
 }
 
+/// A store field that can be held without keeping its store alive.
+///
+/// This allows a store to cache values that hold its own fields, like the memos of computed
+/// fields, without creating a reference cycle.
+#[doc(hidden)]
+pub trait DowngradeField: StoreField {
+    /// A handle to the field that does not keep its store alive.
+    type Weak: Clone + Send + Sync + 'static;
+
+    /// Returns a handle to the field that does not keep its store alive.
+    fn downgrade(&self) -> Self::Weak;
+
+    /// Returns the field, if its store still exists and the field still has a value.
+    fn upgrade(weak: &Self::Weak) -> Option<Self>;
+}
+
+/// Records a change to `field` in the journals of its store.
+///
+/// This is called before the field is written or notified, so that its path is not read while
+/// the store is locked.
+#[inline(always)]
+pub(crate) fn record_change(field: &impl StoreField) {
+    #[cfg(feature = "serde")]
+    if let Some(keys) = field.keys() {
+        keys.record_change(|| field.path().into_iter().collect());
+    }
+    #[cfg(not(feature = "serde"))]
+    let _ = field;
+}
+
+/// Returns the trigger that is notified when `field` itself is written.
+///
+/// Writes to the fields of `field` only notify its `children` trigger, so that the fields that
+/// track `this` are only notified when their parent is replaced.
+pub(crate) fn this_trigger(field: &impl StoreField) -> ArcTrigger {
+    field.get_trigger(field.path().into_iter().collect()).this
+}
+
+/// Returns the index of the current variant of an enum field.
+///
+/// Unlike [`StoreField::track_field`], this does not track the `children` trigger of the field,
+/// which is notified by writes to the fields of the current variant. Code that only reads which
+/// variant is current is notified when the field itself or its parent is replaced or patched
+/// to a different variant. This is used by the code generated by `#[derive(Store)]` for enums.
+#[doc(hidden)]
+#[track_caller]
+pub fn variant_index<F: StoreField>(
+    field: &F,
+    index: fn(&F::Value) -> usize,
+) -> Option<usize> {
+    let reader = field.reader()?;
+    let mut path: StorePath = field.path().into_iter().collect();
+    field.get_trigger(path.clone()).this.track();
+    if path.pop().is_some() {
+        field.get_trigger(path).this.track();
+    }
+    Some(index(&reader))
+}
+
 impl<T> StoreField for ArcStore<T>
 where
     T: 'static,
@@ -91,6 +156,55 @@ where
     }
 }
 
+/// An [`ArcStore`] that does not keep its value alive.
+#[doc(hidden)]
+pub struct WeakArcStore<T> {
+    #[cfg(any(debug_assertions, leptos_debuginfo))]
+    defined_at: &'static Location<'static>,
+    value: Weak<RwLock<T>>,
+    signals: Weak<RwLock<TriggerMap>>,
+    keys: WeakKeyMap,
+}
+
+impl<T> Clone for WeakArcStore<T> {
+    fn clone(&self) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            value: Weak::clone(&self.value),
+            signals: Weak::clone(&self.signals),
+            keys: self.keys.clone(),
+        }
+    }
+}
+
+impl<T> DowngradeField for ArcStore<T>
+where
+    T: Send + Sync + 'static,
+{
+    type Weak = WeakArcStore<T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        WeakArcStore {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            value: Arc::downgrade(&self.value),
+            signals: Arc::downgrade(&self.signals),
+            keys: self.keys.downgrade(),
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        Some(ArcStore {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: weak.defined_at,
+            value: weak.value.upgrade()?,
+            signals: weak.signals.upgrade()?,
+            keys: weak.keys.upgrade()?,
+        })
+    }
+}
+
 impl<T, S> StoreField for Store<T, S>
 where
     T: 'static,
@@ -131,3 +245,20 @@ where
         self.inner.try_get_value().and_then(|inner| inner.keys())
     }
 }
+
+impl<T, S> DowngradeField for Store<T, S>
+where
+    T: 'static,
+    S: Storage<ArcStore<T>> + 'static,
+{
+    // the arena already holds the store weakly
+    type Weak = Self;
+
+    fn downgrade(&self) -> Self::Weak {
+        *self
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        (!weak.is_disposed()).then_some(*weak)
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     path::{StorePath, StorePathSegment},
-    store_field::StoreField,
+    store_field::{record_change, this_trigger, DowngradeField, StoreField},
     KeyMap, StoreFieldTrigger,
 };
 use reactive_graph::{
@@ -115,6 +115,39 @@ where
     }
 }
 
+impl<Inner, Prev, T> DowngradeField for Subfield<Inner, Prev, T>
+where
+    Inner: DowngradeField<Value = Prev>,
+    Prev: 'static,
+    T: Send + Sync + 'static,
+{
+    type Weak = Subfield<Inner::Weak, Prev, T>;
+
+    fn downgrade(&self) -> Self::Weak {
+        Subfield {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            path_segment: self.path_segment,
+            inner: self.inner.downgrade(),
+            read: self.read,
+            write: self.write,
+            ty: self.ty,
+        }
+    }
+
+    fn upgrade(weak: &Self::Weak) -> Option<Self> {
+        Some(Subfield {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: weak.defined_at,
+            path_segment: weak.path_segment,
+            inner: Inner::upgrade(&weak.inner)?,
+            read: weak.read,
+            write: weak.write,
+            ty: weak.ty,
+        })
+    }
+}
+
 impl<Inner, Prev, T> DefinedAt for Subfield<Inner, Prev, T>
 where
     Inner: StoreField<Value = Prev>,
@@ -147,6 +180,7 @@ where
 {
     #[track_caller]
     fn notify(&self) {
+        record_change(self);
         let trigger = self.get_trigger(self.path().into_iter().collect());
         trigger.this.notify();
         trigger.children.notify();
@@ -186,7 +220,9 @@ where
     type Value = T;
 
     fn try_write(&self) -> Option<impl UntrackableGuard<Target = Self::Value>> {
-        self.writer()
+        record_change(self);
+        let this = this_trigger(self);
+        self.writer().map(|writer| WriteGuard::new(this, writer))
     }
 
     fn try_write_untracked(
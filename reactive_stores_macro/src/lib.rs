@@ -6,8 +6,8 @@ use syn::{
     parse::{Parse, ParseStream, Parser},
     punctuated::Punctuated,
     token::Comma,
-    ExprClosure, Field, Fields, Generics, Ident, Index, Meta, Result, Token,
-    Type, Variant, Visibility, WhereClause,
+    Attribute, Expr, ExprClosure, Field, Fields, Generics, Ident, Index, Meta,
+    Result, Token, Type, Variant, Visibility, WhereClause,
 };
 
 #[proc_macro_error]
@@ -31,6 +31,7 @@ struct Model {
     name: Ident,
     generics: Generics,
     ty: ModelTy,
+    computed: Vec<ComputedField>,
 }
 
 enum ModelTy {
@@ -69,6 +70,7 @@ impl Parse for Model {
         };
 
         Ok(Self {
+            computed: computed_fields(&input.attrs),
             vis: input.vis,
             generics: input.generics,
             name: input.ident,
@@ -77,10 +79,90 @@ impl Parse for Model {
     }
 }
 
+/// A memoized field declared with `#[store(computed(<ident>: <Type> = <expr>))]`.
+struct ComputedField {
+    ident: Ident,
+    ty: Type,
+    fun: Expr,
+}
+
+impl Parse for ComputedField {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mode: Ident = input.parse()?;
+        if mode != "computed" {
+            return Err(syn::Error::new(
+                mode.span(),
+                "expected `computed(<ident>: <Type> = <expr>)`",
+            ));
+        }
+        let content;
+        syn::parenthesized!(content in input);
+        let ident = content.parse()?;
+        let _col: Token!(:) = content.parse()?;
+        let ty = content.parse()?;
+        let _eq: Token!(=) = content.parse()?;
+        let fun = content.parse()?;
+        Ok(ComputedField { ident, ty, fun })
+    }
+}
+
+fn computed_fields(attrs: &[Attribute]) -> Vec<ComputedField> {
+    attrs
+        .iter()
+        .filter(|attr| attr.meta.path().is_ident("store"))
+        .flat_map(|attr| match &attr.meta {
+            Meta::List(list) => {
+                match Punctuated::<ComputedField, Comma>::parse_terminated
+                    .parse2(list.tokens.clone())
+                {
+                    Ok(fields) => fields.into_iter(),
+                    Err(e) => abort!(list, e),
+                }
+            }
+            _ => abort!(
+                attr.meta,
+                "needs to be as `#[store(computed(<ident>: <Type> = <expr>))]`"
+            ),
+        })
+        .collect()
+}
+
+impl ComputedField {
+    fn to_tokens(
+        &self,
+        include_body: bool,
+        library_path: &TokenStream,
+    ) -> TokenStream {
+        let ComputedField { ident, ty, fun } = self;
+        let signature = quote! {
+            fn #ident(self) -> #library_path::Computed<#ty>
+            where
+                Self: Clone
+                    + #library_path::__private::DowngradeField
+                    + Send
+                    + Sync
+                    + #library_path::__private::Track
+                    + #library_path::__private::IsDisposed
+                    + 'static,
+                #ty: PartialEq + Send + Sync + 'static
+        };
+        if include_body {
+            quote! {
+                #signature {
+                    #library_path::computed_field(self, stringify!(#ident), #fun)
+                }
+            }
+        } else {
+            quote! { #signature; }
+        }
+    }
+}
+
 #[derive(Clone)]
 enum SubfieldMode {
     Keyed(ExprClosure, Type),
     Skip,
+    Validate(Option<Expr>),
 }
 
 impl Parse for SubfieldMode {
@@ -94,12 +176,149 @@ impl Parse for SubfieldMode {
             Ok(SubfieldMode::Keyed(ident, ty))
         } else if mode == "skip" {
             Ok(SubfieldMode::Skip)
+        } else if mode == "validate" {
+            if input.peek(Token!(=)) {
+                let _eq: Token!(=) = input.parse()?;
+                Ok(SubfieldMode::Validate(Some(input.parse()?)))
+            } else {
+                Ok(SubfieldMode::Validate(None))
+            }
         } else {
-            Err(input.error("expected `key = <ident>: <Type>`"))
+            Err(input.error(
+                "expected `key = <ident>: <Type>`, `skip`, or `validate = <expr>`",
+            ))
         }
     }
 }
 
+fn field_modes(attrs: &[Attribute]) -> Vec<SubfieldMode> {
+    attrs
+        .iter()
+        .filter(|attr| attr.meta.path().is_ident("store"))
+        .flat_map(|attr| match &attr.meta {
+            Meta::List(list) => {
+                match Punctuated::<SubfieldMode, Comma>::parse_terminated
+                    .parse2(list.tokens.clone())
+                {
+                    Ok(modes) => modes.into_iter().collect(),
+                    Err(e) => abort!(list, e),
+                }
+            }
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// Implements `Validate` for a struct with any fields marked `#[store(validate)]`.
+fn validate_impl(
+    library_path: &TokenStream,
+    name: &Ident,
+    generics: &Generics,
+    fields: &[Field],
+) -> Option<TokenStream> {
+    let segment_ty = quote! { #library_path::StorePathSegment };
+    let (validators, field_validators): (Vec<_>, Vec<_>) = fields
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, field)| {
+            let locator = match &field.ident {
+                Some(ident) => quote! { #ident },
+                None => {
+                    let idx = Index::from(idx);
+                    quote! { #idx }
+                }
+            };
+            let modes = field_modes(&field.attrs);
+            let key = modes.iter().find_map(|mode| match mode {
+                SubfieldMode::Keyed(key_fn, key_ty) => Some((key_fn, key_ty)),
+                _ => None,
+            });
+            let mut validators = Vec::new();
+            let mut nested = false;
+            for mode in &modes {
+                match mode {
+                    SubfieldMode::Validate(Some(fun)) => validators.push(quote! {
+                        #library_path::validate_field(&self.#locator, field_path.clone(), errors, #fun);
+                    }),
+                    SubfieldMode::Validate(None) => nested = true,
+                    _ => {}
+                }
+            }
+            if validators.is_empty() && !nested {
+                return None;
+            }
+
+            // items of keyed fields are validated with the paths of their keys
+            let validate_nested = |field: TokenStream| match (nested, key) {
+                (false, _) => quote! {},
+                (true, Some((key_fn, key_ty))) => quote! {
+                    #library_path::validate_keyed::<_, _, #key_ty>(
+                        &self.#locator,
+                        #key_fn,
+                        &field_path,
+                        #field,
+                        errors,
+                    );
+                },
+                (true, None) => quote! {
+                    #library_path::Validate::validate_field(&self.#locator, &field_path, #field, errors);
+                },
+            };
+            let validate_all = validate_nested(quote! { &[] });
+            let validate_field = validate_nested(quote! { field });
+            Some((
+                quote! {
+                    {
+                        let mut field_path = path.to_owned();
+                        field_path.push(#idx);
+                        #(#validators)*
+                        #validate_all
+                    }
+                },
+                quote! {
+                    if *segment == #segment_ty::from(#idx) {
+                        let mut field_path = path.to_owned();
+                        field_path.push(#idx);
+                        if field.is_empty() {
+                            #(#validators)*
+                        }
+                        #validate_field
+                        return;
+                    }
+                },
+            ))
+        })
+        .unzip();
+    if validators.is_empty() {
+        return None;
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Some(quote! {
+        impl #impl_generics #library_path::Validate for #name #ty_generics #where_clause {
+            fn validate(
+                &self,
+                path: &#library_path::StorePath,
+                errors: &mut #library_path::FormErrors,
+            ) {
+                #(#validators)*
+            }
+
+            fn validate_field(
+                &self,
+                path: &#library_path::StorePath,
+                field: &[#segment_ty],
+                errors: &mut #library_path::FormErrors,
+            ) {
+                let Some((segment, field)) = field.split_first() else {
+                    return #library_path::Validate::validate(self, path, errors);
+                };
+                #(#field_validators)*
+            }
+        }
+    })
+}
+
 impl ToTokens for Model {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let library_path = quote! { reactive_stores };
@@ -108,6 +327,7 @@ impl ToTokens for Model {
             name,
             generics,
             ty,
+            computed,
         } = &self;
         let any_store_field = Ident::new("AnyStoreField", Span::call_site());
         let trait_name = Ident::new(&format!("{name}StoreFields"), name.span());
@@ -135,8 +355,12 @@ impl ToTokens for Model {
 
         // define an extension trait that matches this struct
         // and implement that trait for all StoreFields
-        let (trait_fields, read_fields): (Vec<_>, Vec<_>) =
+        let (mut trait_fields, mut read_fields): (Vec<_>, Vec<_>) =
             ty.to_field_data(&library_path, generics, &any_store_field, name);
+        for field in computed {
+            trait_fields.push(field.to_tokens(false, &library_path));
+            read_fields.push(field.to_tokens(true, &library_path));
+        }
 
         // read access
         tokens.extend(quote! {
@@ -152,6 +376,305 @@ impl ToTokens for Model {
                #(#read_fields)*
             }
         });
+
+        if let ModelTy::Struct { fields } = ty {
+            tokens.extend(validate_impl(&library_path, name, generics, fields));
+        }
+        tokens.extend(json_field_impl(&library_path, name, generics, ty));
+        tokens.extend(json_patch_field_impl(&library_path, name, generics, ty));
+    }
+}
+
+/// Implements `JsonField`, which finds the fields of a value by their store paths.
+///
+/// The implementation only exists if `reactive_stores` has the `serde` feature, and only applies
+/// if the value can be serialized and the types of its fields implement `JsonField`, which is why
+/// it is generic over the otherwise unused `__M`.
+fn json_field_impl(
+    library_path: &TokenStream,
+    name: &Ident,
+    generics: &Generics,
+    ty: &ModelTy,
+) -> TokenStream {
+    let segment_ty = quote! { #library_path::StorePathSegment };
+    let json_field = quote! { #library_path::JsonField::<__M> };
+    let mut field_tys = Vec::new();
+
+    let body = match ty {
+        ModelTy::Struct { fields } => {
+            let fields = fields.iter().enumerate().filter_map(|(idx, field)| {
+                let modes = field_modes(&field.attrs);
+                if modes.iter().any(|mode| matches!(mode, SubfieldMode::Skip)) {
+                    return None;
+                }
+                field_tys.push(&field.ty);
+                let (member, field_name) = match &field.ident {
+                    Some(ident) => (quote! { #ident }, ident.to_string()),
+                    None => {
+                        let member = Index::from(idx);
+                        (quote! { #member }, idx.to_string())
+                    }
+                };
+                let keyed = modes.iter().find_map(|mode| match mode {
+                    SubfieldMode::Keyed(key_fn, key_ty) => Some(quote! {
+                        if let Some((key_segment, path)) = path.split_first() {
+                            let index = cursor
+                                .with_key(*key_segment, |key: &#key_ty| {
+                                    #library_path::__private::keyed_index(
+                                        &self.#member,
+                                        key,
+                                        #key_fn,
+                                    )
+                                })
+                                .flatten();
+                            if let Some(json) = index.and_then(|index| {
+                                #json_field::item_json(
+                                    &self.#member,
+                                    *key_segment,
+                                    index,
+                                    path,
+                                    cursor,
+                                )
+                            }) {
+                                return json;
+                            }
+                        }
+                        return #json_field::field_json(&self.#member, &[], cursor);
+                    }),
+                    _ => None,
+                });
+                let find = keyed.unwrap_or_else(|| {
+                    quote! {
+                        return #json_field::field_json(&self.#member, path, cursor);
+                    }
+                });
+                Some(quote! {
+                    if *segment == #segment_ty::from(#idx) {
+                        cursor.enter_field(*segment, #field_name);
+                        #find
+                    }
+                })
+            });
+            quote! { #(#fields)* }
+        }
+        ModelTy::Enum { variants } => {
+            let arms = variants
+                .iter()
+                .zip(variant_segments(variants))
+                .filter(|(variant, _)| !variant.fields.is_empty())
+                .map(|(variant, first_segment)| {
+                    let ident = &variant.ident;
+                    let (members, finds): (Vec<_>, Vec<_>) = variant
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, field)| {
+                            field_tys.push(&field.ty);
+                            let this = Ident::new(
+                                &format!("this_{idx}"),
+                                Span::call_site(),
+                            );
+                            let (member, field_name) = match &field.ident {
+                                Some(ident) => (quote! { #ident }, ident.to_string()),
+                                None => {
+                                    let member = Index::from(idx);
+                                    (quote! { #member }, idx.to_string())
+                                }
+                            };
+                            let segment = first_segment + idx;
+                            (
+                                quote! { #member: #this },
+                                quote! {
+                                    if *segment == #segment_ty::from(#segment) {
+                                        cursor.enter_field(*segment, #field_name);
+                                        return #json_field::field_json(#this, path, cursor);
+                                    }
+                                },
+                            )
+                        })
+                        .unzip();
+                    quote! {
+                        #name::#ident { #(#members),* } => {
+                            #(#finds)*
+                        }
+                    }
+                });
+            quote! {
+                match self {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => {}
+                }
+            }
+        }
+    };
+
+    let mut impl_generics = generics.clone();
+    impl_generics.params.push(syn::parse_quote!(__M));
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = where_clause.map(|where_clause| &where_clause.predicates);
+
+    quote! {
+        #library_path::__with_serde! {
+            impl #impl_generics #library_path::JsonField<__M> for #name #ty_generics
+            where
+                Self: #library_path::__private::SerializeJson<__M>,
+                #(#field_tys: #library_path::JsonField<__M>,)*
+                #predicates
+            {
+                fn field_json(
+                    &self,
+                    path: &[#segment_ty],
+                    cursor: &mut #library_path::FieldCursor,
+                ) -> ::core::result::Result<
+                    #library_path::__private::serde_json::Value,
+                    #library_path::__private::serde_json::Error,
+                > {
+                    if let Some((segment, path)) = path.split_first() {
+                        #body
+                    }
+                    #library_path::__private::SerializeJson::<__M>::to_json(self)
+                }
+            }
+        }
+    }
+}
+
+fn json_patch_field_impl(
+    library_path: &TokenStream,
+    name: &Ident,
+    generics: &Generics,
+    ty: &ModelTy,
+) -> TokenStream {
+    let segment_ty = quote! { #library_path::StorePathSegment };
+    let private = quote! { #library_path::__private };
+    let patch_field = quote! { #library_path::JsonPatchField::<__M> };
+    let mut field_tys = Vec::new();
+
+    let body = match ty {
+        ModelTy::Struct { fields } => {
+            let fields = fields.iter().enumerate().filter_map(|(idx, field)| {
+                let modes = field_modes(&field.attrs);
+                if modes.iter().any(|mode| matches!(mode, SubfieldMode::Skip)) {
+                    return None;
+                }
+                field_tys.push(&field.ty);
+                let (member, field_name) = match &field.ident {
+                    Some(ident) => (quote! { #ident }, ident.to_string()),
+                    None => {
+                        let member = Index::from(idx);
+                        (quote! { #member }, idx.to_string())
+                    }
+                };
+                let edit = modes
+                    .iter()
+                    .find_map(|mode| match mode {
+                        SubfieldMode::Keyed(key_fn, key_ty) => Some(quote! {
+                            #private::edit_keyed::<__M, _, #key_ty>(
+                                &mut self.#member,
+                                #key_fn,
+                                tokens,
+                                edit,
+                                cursor,
+                            )
+                        }),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| {
+                        quote! {
+                            #patch_field::edit_json(&mut self.#member, tokens, edit, cursor)
+                        }
+                    });
+                Some(quote! {
+                    if token.as_str() == #field_name {
+                        cursor.enter_field(#segment_ty::from(#idx), #field_name);
+                        return #edit;
+                    }
+                })
+            });
+            quote! { #(#fields)* }
+        }
+        ModelTy::Enum { variants } => {
+            let arms = variants
+                .iter()
+                .zip(variant_segments(variants))
+                .filter(|(variant, _)| !variant.fields.is_empty())
+                .map(|(variant, first_segment)| {
+                    let ident = &variant.ident;
+                    let (members, edits): (Vec<_>, Vec<_>) = variant
+                        .fields
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, field)| {
+                            field_tys.push(&field.ty);
+                            let this = Ident::new(
+                                &format!("this_{idx}"),
+                                Span::call_site(),
+                            );
+                            let (member, field_name) = match &field.ident {
+                                Some(ident) => (quote! { #ident }, ident.to_string()),
+                                None => {
+                                    let member = Index::from(idx);
+                                    (quote! { #member }, idx.to_string())
+                                }
+                            };
+                            let segment = first_segment + idx;
+                            (
+                                quote! { #member: #this },
+                                quote! {
+                                    if token.as_str() == #field_name {
+                                        cursor.enter_field(#segment_ty::from(#segment), #field_name);
+                                        return #patch_field::edit_json(#this, tokens, edit, cursor);
+                                    }
+                                },
+                            )
+                        })
+                        .unzip();
+                    quote! {
+                        #name::#ident { #(#members),* } => {
+                            #(#edits)*
+                        }
+                    }
+                });
+            quote! {
+                match self {
+                    #(#arms)*
+                    #[allow(unreachable_patterns)]
+                    _ => {}
+                }
+            }
+        }
+    };
+
+    let mut impl_generics = generics.clone();
+    impl_generics.params.push(syn::parse_quote!(__M));
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = generics.split_for_impl();
+    let predicates = where_clause.map(|where_clause| &where_clause.predicates);
+
+    quote! {
+        #library_path::__with_serde! {
+            impl #impl_generics #library_path::JsonPatchField<__M> for #name #ty_generics
+            where
+                Self: #private::SerializeJson<__M> + #private::DeserializeJson<__M>,
+                #(#field_tys: #library_path::JsonPatchField<__M>,)*
+                #predicates
+            {
+                fn edit_json(
+                    &mut self,
+                    tokens: &[::std::string::String],
+                    edit: #private::JsonEdit,
+                    cursor: &mut #library_path::FieldCursor,
+                ) -> ::core::result::Result<#private::JsonEdited, #private::JsonEditError> {
+                    if let Some((token, tokens)) = tokens.split_first() {
+                        #body
+                        return ::core::result::Result::Err(#private::JsonEditError::NotFound);
+                    }
+                    #private::edit_whole::<__M, _>(self, tokens, edit, cursor)
+                }
+            }
+        }
     }
 }
 
@@ -171,32 +694,14 @@ impl ModelTy {
                     let Field {
                         ident, ty, attrs, ..
                     } = &field;
-                    let modes = attrs
-                        .iter()
-                        .find_map(|attr| {
-                            attr.meta.path().is_ident("store").then(|| {
-                                match &attr.meta {
-                                    Meta::List(list) => {
-                                        match Punctuated::<
-                                                SubfieldMode,
-                                                Comma,
-                                            >::parse_terminated
-                                                .parse2(list.tokens.clone())
-                                            {
-                                                Ok(modes) => Some(
-                                                    modes
-                                                        .iter()
-                                                        .cloned()
-                                                        .collect::<Vec<_>>(),
-                                                ),
-                                                Err(e) => abort!(list, e),
-                                            }
-                                    }
-                                    _ => None,
-                                }
-                            })
+                    // validators are implemented separately, in `Validate`
+                    let modes = field_modes(attrs)
+                        .into_iter()
+                        .filter(|mode| {
+                            !matches!(mode, SubfieldMode::Validate(_))
                         })
-                        .flatten();
+                        .collect::<Vec<_>>();
+                    let modes = (!modes.is_empty()).then_some(modes);
 
                     (
                         field_to_tokens(
@@ -224,33 +729,44 @@ impl ModelTy {
                     )
                 })
                 .unzip(),
-            ModelTy::Enum { variants } => variants
-                .iter()
-                .map(|variant| {
-                    let Variant { ident, fields, .. } = variant;
+            ModelTy::Enum { variants } => {
+                let variant_index = variant_index(name, variants);
+                variants
+                    .iter()
+                    .zip(variant_segments(variants))
+                    .enumerate()
+                    .map(|(variant_idx, (variant, first_segment))| {
+                        let Variant { ident, fields, .. } = variant;
 
-                    (
-                        variant_to_tokens(
-                            false,
-                            library_path,
-                            ident,
-                            generics,
-                            any_store_field,
-                            name,
-                            fields,
-                        ),
-                        variant_to_tokens(
-                            true,
-                            library_path,
-                            ident,
-                            generics,
-                            any_store_field,
-                            name,
-                            fields,
-                        ),
-                    )
-                })
-                .unzip(),
+                        (
+                            variant_to_tokens(
+                                false,
+                                library_path,
+                                ident,
+                                generics,
+                                any_store_field,
+                                name,
+                                fields,
+                                variant_idx,
+                                first_segment,
+                                &variant_index,
+                            ),
+                            variant_to_tokens(
+                                true,
+                                library_path,
+                                ident,
+                                generics,
+                                any_store_field,
+                                name,
+                                fields,
+                                variant_idx,
+                                first_segment,
+                                &variant_index,
+                            ),
+                        )
+                    })
+                    .unzip()
+            }
         }
     }
 }
@@ -305,6 +821,8 @@ fn field_to_tokens(
                     };
                 }
                 SubfieldMode::Skip => return quote! {},
+                // validators are implemented separately, in `Validate`
+                SubfieldMode::Validate(_) => {}
             }
         } else {
             abort!(
@@ -335,6 +853,34 @@ fn field_to_tokens(
     }
 }
 
+/// Returns the first path segment used by the fields of each variant.
+///
+/// Every field of every variant has its own segment, so that the fields of different variants
+/// do not share triggers.
+fn variant_segments(variants: &[Variant]) -> Vec<usize> {
+    variants
+        .iter()
+        .scan(0, |next, variant| {
+            let first = *next;
+            *next += variant.fields.len();
+            Some(first)
+        })
+        .collect()
+}
+
+/// A closure that returns the index of the variant of a value of the enum.
+fn variant_index(name: &Ident, variants: &[Variant]) -> TokenStream {
+    let arms = variants.iter().enumerate().map(|(idx, variant)| {
+        let ident = &variant.ident;
+        quote! { #name::#ident { .. } => #idx, }
+    });
+    quote! {
+        |value| match value {
+            #(#arms)*
+        }
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn variant_to_tokens(
     include_body: bool,
@@ -344,194 +890,89 @@ fn variant_to_tokens(
     any_store_field: &Ident,
     name: &Ident,
     fields: &Fields,
+    variant_idx: usize,
+    first_segment: usize,
+    variant_index: &TokenStream,
 ) -> proc_macro2::TokenStream {
     // the method name will always be the snake_cased ident
     let orig_ident = &ident;
     let ident =
         Ident::new(&ident.to_string().to_case(Case::Snake), ident.span());
 
-    match fields {
-        // For unit enum fields, we will just return a `bool` subfield, which is
-        // true when this field matches
-        Fields::Unit => {
-            // default subfield
-            if include_body {
-                quote! {
-                    fn #ident(self) -> bool {
-                        match #library_path::StoreField::reader(&self) {
-                            Some(reader) => {
-                                #library_path::StoreField::track_field(&self);
-                                matches!(&*reader, #name::#orig_ident)
-                            },
-                            None => false
-                        }
-                    }
-                }
-            } else {
-                quote! {
-                    fn #ident(self) -> bool;
-                }
+    // changes to the fields of the current variant do not notify code that only depends on
+    // which variant it is
+    let matches = quote! {
+        #library_path::__private::variant_index(&self, #variant_index) == Some(#variant_idx)
+    };
+
+    // For every variant, we create a `bool` method, which is true when this variant matches
+    let mut tokens = if include_body {
+        quote! {
+            fn #ident(self) -> bool {
+                #matches
             }
         }
-        // If an enum branch has named fields, we create N + 1 methods:
-        // 1 `bool` subfield, which is true when this field matches
-        // N `Option<T>` subfields for each of the named fields
-        Fields::Named(fields) => {
-            let mut tokens = if include_body {
-                quote! {
-                    fn #ident(self) -> bool {
-                        match #library_path::StoreField::reader(&self) {
-                            Some(reader) => {
-                                #library_path::StoreField::track_field(&self);
-                                matches!(&*reader, #name::#orig_ident { .. })
-                            },
-                            None => false
-                        }
-                    }
-                }
-            } else {
-                quote! {
-                    fn #ident(self) -> bool;
-                }
-            };
+    } else {
+        quote! {
+            fn #ident(self) -> bool;
+        }
+    };
 
-            tokens.extend(fields
-                .named
-                .iter()
-                .map(|field| {
-                    let field_ident = field.ident.as_ref().unwrap();
-                    let field_ty = &field.ty;
-                    let combined_ident = Ident::new(
-                        &format!("{}_{}", ident, field_ident),
-                        field_ident.span(),
-                    );
+    // If an enum branch has fields, we also create N `Option<T>` subfields for each of its
+    // named or unnamed fields
+    tokens.extend(fields.iter().enumerate().map(|(idx, field)| {
+        let field_ty = &field.ty;
+        let (member, combined_ident) = match &field.ident {
+            Some(field_ident) => (
+                quote! { #field_ident },
+                Ident::new(&format!("{ident}_{field_ident}"), field_ident.span()),
+            ),
+            None => {
+                let member = Index::from(idx);
+                (
+                    quote! { #member },
+                    Ident::new(&format!("{ident}_{idx}"), ident.span()),
+                )
+            }
+        };
+        let segment = first_segment + idx;
 
-                    // default subfield
-                    if include_body {
-                        quote! {
-                            fn #combined_ident(self) -> Option<#library_path::Subfield<#any_store_field, #name #generics, #field_ty>> {
-                                #library_path::StoreField::track_field(&self);
-                                let reader = #library_path::StoreField::reader(&self);
-                                let matches = reader
-                                    .map(|reader| matches!(&*reader, #name::#orig_ident { .. }))
-                                    .unwrap_or(false);
-                                if matches {
-                                    Some(#library_path::Subfield::new(
-                                        self,
-                                        0.into(),
-                                        |prev| {
-                                            match prev {
-                                                #name::#orig_ident { #field_ident, .. } => Some(#field_ident),
-                                                _ => None,
-                                            }
-                                            .expect("accessed an enum field that is no longer matched")
-                                        },
-                                        |prev| {
-                                            match prev {
-                                                #name::#orig_ident { #field_ident, .. } => Some(#field_ident),
-                                                _ => None,
-                                            }
-                                            .expect("accessed an enum field that is no longer matched")
-                                        },
-                                    ))
-                                } else {
-                                    None
+        let signature = quote! {
+            fn #combined_ident(self) -> Option<#library_path::Subfield<#any_store_field, #name #generics, #field_ty>>
+        };
+        if include_body {
+            quote! {
+                #signature {
+                    if #matches {
+                        Some(#library_path::Subfield::new(
+                            self,
+                            #segment.into(),
+                            |prev| {
+                                match prev {
+                                    #name::#orig_ident { #member: this, .. } => Some(this),
+                                    _ => None,
                                 }
-                            }
-                        }
-                    } else {
-                        quote! {
-                            fn #combined_ident(self) -> Option<#library_path::Subfield<#any_store_field, #name #generics, #field_ty>>;
-                        }
-                    }
-                }));
-
-            tokens
-        }
-        // If an enum branch has unnamed fields, we create N + 1 methods:
-        // 1 `bool` subfield, which is true when this field matches
-        // N `Option<T>` subfields for each of the unnamed fields
-        Fields::Unnamed(fields) => {
-            let mut tokens = if include_body {
-                quote! {
-                    fn #ident(self) -> bool {
-                        match #library_path::StoreField::reader(&self) {
-                            Some(reader) => {
-                                #library_path::StoreField::track_field(&self);
-                                matches!(&*reader, #name::#orig_ident { .. })
+                                .expect("accessed an enum field that is no longer matched")
                             },
-                            None => false
-                        }
-                    }
-                }
-            } else {
-                quote! {
-                    fn #ident(self) -> bool;
-                }
-            };
-
-            let number_of_fields = fields.unnamed.len();
-
-            tokens.extend(fields
-                .unnamed
-                .iter()
-                .enumerate()
-                .map(|(idx, field)| {
-                    let field_ident = idx;
-                    let field_ty = &field.ty;
-                    let combined_ident = Ident::new(
-                        &format!("{}_{}", ident, field_ident),
-                        ident.span(),
-                    );
-
-                    let ignore_before = (0..idx).map(|_| quote! { _, });
-                    let ignore_before2 = ignore_before.clone();
-                    let ignore_after = (idx..number_of_fields.saturating_sub(1)).map(|_| quote !{_, });
-                    let ignore_after2 = ignore_after.clone();
-
-                    // default subfield
-                    if include_body {
-                        quote! {
-                            fn #combined_ident(self) -> Option<#library_path::Subfield<#any_store_field, #name #generics, #field_ty>> {
-                                #library_path::StoreField::track_field(&self);
-                                let reader = #library_path::StoreField::reader(&self);
-                                let matches = reader
-                                    .map(|reader| matches!(&*reader, #name::#orig_ident(..)))
-                                    .unwrap_or(false);
-                                if matches {
-                                    Some(#library_path::Subfield::new(
-                                        self,
-                                        0.into(),
-                                        |prev| {
-                                            match prev {
-                                                #name::#orig_ident(#(#ignore_before)* this, #(#ignore_after)*) => Some(this),
-                                                _ => None,
-                                            }
-                                            .expect("accessed an enum field that is no longer matched")
-                                        },
-                                        |prev| {
-                                            match prev {
-                                                #name::#orig_ident(#(#ignore_before2)* this, #(#ignore_after2)*) => Some(this),
-                                                _ => None,
-                                            }
-                                            .expect("accessed an enum field that is no longer matched")
-                                        },
-                                    ))
-                                } else {
-                                    None
+                            |prev| {
+                                match prev {
+                                    #name::#orig_ident { #member: this, .. } => Some(this),
+                                    _ => None,
                                 }
-                            }
-                        }
+                                .expect("accessed an enum field that is no longer matched")
+                            },
+                        ))
                     } else {
-                        quote! {
-                            fn #combined_ident(self) -> Option<#library_path::Subfield<#any_store_field, #name #generics, #field_ty>>;
-                        }
+                        None
                     }
-                }));
-
-            tokens
+                }
+            }
+        } else {
+            quote! { #signature; }
         }
-    }
+    }));
+
+    tokens
 }
 
 struct PatchModel {
@@ -541,13 +982,8 @@ struct PatchModel {
 }
 
 enum PatchModelTy {
-    Struct {
-        fields: Vec<Field>,
-    },
-    #[allow(dead_code)]
-    Enum {
-        variants: Vec<Variant>,
-    },
+    Struct { fields: Vec<Field> },
+    Enum { variants: Vec<Variant> },
 }
 
 impl Parse for PatchModel {
@@ -570,14 +1006,9 @@ impl Parse for PatchModel {
 
                 PatchModelTy::Struct { fields }
             }
-            syn::Data::Enum(_e) => {
-                abort_call_site!("only structs can be used with `Patch`");
-
-                // TODO: support enums later on
-                // PatchModelTy::Enum {
-                //     variants: e.variants.into_iter().collect(),
-                // }
-            }
+            syn::Data::Enum(e) => PatchModelTy::Enum {
+                variants: e.variants.into_iter().collect(),
+            },
             _ => {
                 abort_call_site!(
                     "only structs and enums can be used with `Store`"
@@ -600,7 +1031,11 @@ impl ToTokens for PatchModel {
 
         let fields = match ty {
             PatchModelTy::Struct { fields } => {
-                fields.iter().enumerate().map(|(idx, field)| {
+                let new_path = quote! {
+                    let mut new_path = path.clone();
+                    new_path.push(0);
+                };
+                std::iter::once(new_path).chain(fields.iter().enumerate().map(|(idx, field)| {
                     let Field {
                         attrs, ident, ..
                     } = &field;
@@ -659,10 +1094,75 @@ impl ToTokens for PatchModel {
                             new_path.replace_last(#idx + 1);
                         }
                     }
-                }).collect::<Vec<_>>()
+                })).collect::<Vec<_>>()
             }
-            PatchModelTy::Enum { variants: _ } => {
-                unreachable!("not implemented currently")
+            PatchModelTy::Enum { variants } => {
+                let arms = variants.iter().zip(variant_segments(variants)).map(
+                    |(variant, first_segment)| {
+                        let ident = &variant.ident;
+                        let members = variant
+                            .fields
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, field)| match &field.ident {
+                                Some(ident) => quote! { #ident },
+                                None => {
+                                    let idx = Index::from(idx);
+                                    quote! { #idx }
+                                }
+                            })
+                            .collect::<Vec<_>>();
+                        let this = (0..members.len())
+                            .map(|idx| {
+                                Ident::new(
+                                    &format!("this_{idx}"),
+                                    Span::call_site(),
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        let new = (0..members.len())
+                            .map(|idx| {
+                                Ident::new(
+                                    &format!("new_{idx}"),
+                                    Span::call_site(),
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        let segments =
+                            (0..members.len()).map(|idx| first_segment + idx);
+                        quote! {
+                            (
+                                #name::#ident { #(#members: #this),* },
+                                #name::#ident { #(#members: #new),* },
+                            ) => {
+                                let mut new_path = path.clone();
+                                new_path.push(0);
+                                #(
+                                    new_path.replace_last(#segments);
+                                    #library_path::PatchField::patch_field(
+                                        #this,
+                                        #new,
+                                        &new_path,
+                                        notify
+                                    );
+                                )*
+                            }
+                        }
+                    },
+                );
+
+                // if the variant is the same, only the fields that have changed are notified
+                // otherwise, the whole enum is replaced
+                vec![quote! {
+                    match (self, new) {
+                        #(#arms)*
+                        #[allow(unreachable_patterns)]
+                        (this, new) => {
+                            *this = new;
+                            notify(path);
+                        }
+                    }
+                }]
             }
         };
 
@@ -676,8 +1176,6 @@ impl ToTokens for PatchModel {
                     path: &#library_path::StorePath,
                     notify: &mut dyn FnMut(&#library_path::StorePath),
                 ) {
-                    let mut new_path = path.clone();
-                    new_path.push(0);
                     #(#fields)*
                 }
             }
@@ -0,0 +1,325 @@
+use crate::location::Url;
+use any_spawner::Executor;
+use reactive_graph::{
+    owner::ArcStoredValue,
+    traits::{ReadValue, WriteValue},
+};
+use std::{fmt, future::Future, pin::Pin, sync::Arc};
+
+/// Whether a navigation should be blocked. This is returned by a blocker registered with
+/// [`use_blocker`](crate::hooks::use_blocker).
+///
+/// A `bool` can be converted into `Blocking`, where `true` blocks the navigation.
+pub enum Blocking {
+    /// The navigation can continue.
+    Allow,
+    /// The navigation is cancelled.
+    Block,
+    /// The navigation waits until the `Future` resolves, and is cancelled if it resolves to
+    /// `true`.
+    Pending(Pin<Box<dyn Future<Output = bool>>>),
+}
+
+impl Blocking {
+    /// Waits for the given `Future` to decide whether the navigation should be blocked, for
+    /// example while a custom confirmation dialog is shown.
+    pub fn pending(fut: impl Future<Output = bool> + 'static) -> Self {
+        Blocking::Pending(Box::pin(fut))
+    }
+
+    /// Calls `navigate` unless the navigation is blocked.
+    pub(crate) fn unless_blocked(self, navigate: impl FnOnce() + 'static) {
+        match self {
+            Blocking::Allow => navigate(),
+            Blocking::Block => {}
+            Blocking::Pending(fut) => Executor::spawn_local(async move {
+                if !fut.await {
+                    navigate();
+                }
+            }),
+        }
+    }
+}
+
+impl From<bool> for Blocking {
+    fn from(block: bool) -> Self {
+        if block {
+            Blocking::Block
+        } else {
+            Blocking::Allow
+        }
+    }
+}
+
+impl fmt::Debug for Blocking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Blocking::Allow => f.write_str("Allow"),
+            Blocking::Block => f.write_str("Block"),
+            Blocking::Pending(_) => f.write_str("Pending"),
+        }
+    }
+}
+
+type BlockerFn =
+    Arc<dyn Fn(&Url, Option<&Url>) -> Blocking + Send + Sync + 'static>;
+
+/// Removes the `beforeunload` listener that asks the blockers whether the user can leave the app.
+pub(crate) type RemoveUnloadListener = Box<dyn FnOnce() + Send + Sync>;
+
+/// The blockers that are currently registered with [`use_blocker`](crate::hooks::use_blocker).
+#[derive(Clone, Default)]
+pub(crate) struct Blockers {
+    next_id: ArcStoredValue<usize>,
+    blockers: ArcStoredValue<Vec<(usize, BlockerFn)>>,
+    /// The single `beforeunload` listener shared by all of the blockers, while there are any.
+    unload_listener: ArcStoredValue<Option<RemoveUnloadListener>>,
+}
+
+impl Blockers {
+    /// Adds a blocker, returning an ID that can be used to remove it.
+    ///
+    /// If this is the first blocker, `listen` is called to add the `beforeunload` listener, and
+    /// the function it returns is called once the last blocker has been removed.
+    pub fn add(
+        &self,
+        blocker: BlockerFn,
+        listen: impl FnOnce() -> RemoveUnloadListener,
+    ) -> usize {
+        let id = {
+            let mut next_id = self.next_id.write_value();
+            *next_id += 1;
+            *next_id
+        };
+        self.blockers.write_value().push((id, blocker));
+        if self.unload_listener.read_value().is_none() {
+            let remove = listen();
+            *self.unload_listener.write_value() = Some(remove);
+        }
+        id
+    }
+
+    pub fn remove(&self, id: usize) {
+        let is_empty = {
+            let mut blockers = self.blockers.write_value();
+            blockers.retain(|(other, _)| *other != id);
+            blockers.is_empty()
+        };
+        if is_empty {
+            let remove = self.unload_listener.write_value().take();
+            if let Some(remove) = remove {
+                remove();
+            }
+        }
+    }
+
+    /// Asks each blocker whether the navigation from `from` to `to` should be blocked. `to` is
+    /// `None` if the user is leaving the app.
+    ///
+    /// Navigations that do not change the path are never blocked.
+    pub fn check(&self, from: &Url, to: Option<&Url>) -> Blocking {
+        if to.is_some_and(|to| {
+            to.origin() == from.origin() && to.path() == from.path()
+        }) {
+            return Blocking::Allow;
+        }
+
+        // the blockers are cloned out, so that they can add or remove other blockers
+        let blockers = self
+            .blockers
+            .read_value()
+            .iter()
+            .map(|(_, blocker)| Arc::clone(blocker))
+            .collect::<Vec<_>>();
+        let mut pending = Vec::new();
+        for blocker in blockers {
+            match blocker(from, to) {
+                Blocking::Allow => {}
+                Blocking::Block => return Blocking::Block,
+                Blocking::Pending(fut) => pending.push(fut),
+            }
+        }
+
+        if pending.is_empty() {
+            Blocking::Allow
+        } else {
+            // ask one at a time, so that only one confirmation is shown at once
+            Blocking::pending(async move {
+                for fut in pending {
+                    if fut.await {
+                        return true;
+                    }
+                }
+                false
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Blockers, Blocking, RemoveUnloadListener};
+    use crate::location::{RequestUrl, Url};
+    use futures::executor::block_on;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    };
+
+    fn url(path: &str) -> Url {
+        RequestUrl::new(path).parse().unwrap()
+    }
+
+    fn no_listener() -> RemoveUnloadListener {
+        Box::new(|| {})
+    }
+
+    fn is_blocked(blocking: Blocking) -> bool {
+        match blocking {
+            Blocking::Allow => false,
+            Blocking::Block => true,
+            Blocking::Pending(fut) => block_on(fut),
+        }
+    }
+
+    #[test]
+    fn allows_navigations_without_blockers() {
+        let blockers = Blockers::default();
+        assert!(matches!(
+            blockers.check(&url("/form"), Some(&url("/home"))),
+            Blocking::Allow
+        ));
+    }
+
+    #[test]
+    fn never_blocks_navigations_within_the_same_path() {
+        let blockers = Blockers::default();
+        blockers.add(Arc::new(|_, _| Blocking::Block), no_listener);
+        assert!(matches!(
+            blockers.check(&url("/form"), Some(&url("/form#step-2"))),
+            Blocking::Allow
+        ));
+        assert!(matches!(
+            blockers.check(&url("/form"), Some(&url("/home"))),
+            Blocking::Block
+        ));
+    }
+
+    #[test]
+    fn asks_blockers_when_leaving_the_app() {
+        let blockers = Blockers::default();
+        let asked = Arc::new(Mutex::new(Vec::new()));
+        blockers.add(
+            Arc::new({
+                let asked = Arc::clone(&asked);
+                move |from: &Url, to: Option<&Url>| {
+                    asked
+                        .lock()
+                        .unwrap()
+                        .push((from.path().to_string(), to.is_some()));
+                    Blocking::Block
+                }
+            }),
+            no_listener,
+        );
+        assert!(matches!(
+            blockers.check(&url("/form"), None),
+            Blocking::Block
+        ));
+        assert_eq!(*asked.lock().unwrap(), [("/form".to_string(), false)]);
+    }
+
+    #[test]
+    fn removed_blockers_are_not_asked() {
+        let blockers = Blockers::default();
+        let id = blockers.add(Arc::new(|_, _| Blocking::Block), no_listener);
+        blockers.add(Arc::new(|_, _| Blocking::Allow), no_listener);
+        blockers.remove(id);
+        assert!(matches!(
+            blockers.check(&url("/form"), Some(&url("/home"))),
+            Blocking::Allow
+        ));
+    }
+
+    #[test]
+    fn blocking_blockers_win_over_pending_ones() {
+        let blockers = Blockers::default();
+        blockers.add(
+            Arc::new(|_, _| Blocking::pending(async { false })),
+            no_listener,
+        );
+        blockers.add(Arc::new(|_, _| true.into()), no_listener);
+        assert!(matches!(
+            blockers.check(&url("/form"), Some(&url("/home"))),
+            Blocking::Block
+        ));
+    }
+
+    #[test]
+    fn pending_blockers_are_asked_one_at_a_time() {
+        let blockers = Blockers::default();
+        let asked = Arc::new(AtomicUsize::new(0));
+        for block in [false, true, false] {
+            let asked = Arc::clone(&asked);
+            blockers.add(
+                Arc::new(move |_, _| {
+                    let asked = Arc::clone(&asked);
+                    Blocking::pending(async move {
+                        asked.fetch_add(1, Ordering::Relaxed);
+                        block
+                    })
+                }),
+                no_listener,
+            );
+        }
+
+        // the first blocker to block cancels the navigation, without asking the rest
+        let blocking = blockers.check(&url("/form"), Some(&url("/home")));
+        assert!(matches!(blocking, Blocking::Pending(_)));
+        assert!(is_blocked(blocking));
+        assert_eq!(asked.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn pending_blockers_allow_the_navigation_if_none_of_them_block_it() {
+        let blockers = Blockers::default();
+        blockers.add(
+            Arc::new(|_, _| Blocking::pending(async { false })),
+            no_listener,
+        );
+        blockers.add(Arc::new(|_, _| Blocking::Allow), no_listener);
+        blockers.add(
+            Arc::new(|_, _| Blocking::pending(async { false })),
+            no_listener,
+        );
+        let blocking = blockers.check(&url("/form"), Some(&url("/home")));
+        assert!(matches!(blocking, Blocking::Pending(_)));
+        assert!(!is_blocked(blocking));
+    }
+
+    #[test]
+    fn adds_one_unload_listener_while_there_are_blockers() {
+        let blockers = Blockers::default();
+        let listening = Arc::new(AtomicUsize::new(0));
+        let listen = || {
+            listening.fetch_add(1, Ordering::Relaxed);
+            let listening = Arc::clone(&listening);
+            Box::new(move || {
+                listening.fetch_sub(1, Ordering::Relaxed);
+            }) as RemoveUnloadListener
+        };
+
+        let first = blockers.add(Arc::new(|_, _| Blocking::Block), listen);
+        let second = blockers.add(Arc::new(|_, _| Blocking::Block), listen);
+        assert_eq!(listening.load(Ordering::Relaxed), 1);
+
+        blockers.remove(first);
+        assert_eq!(listening.load(Ordering::Relaxed), 1);
+        blockers.remove(second);
+        assert_eq!(listening.load(Ordering::Relaxed), 0);
+
+        // the listener is added again for the next blocker
+        blockers.add(Arc::new(|_, _| Blocking::Block), listen);
+        assert_eq!(listening.load(Ordering::Relaxed), 1);
+    }
+}
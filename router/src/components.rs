@@ -3,6 +3,7 @@ pub use super::{form::*, link::*};
 use crate::location::RequestUrl;
 pub use crate::nested_router::Outlet;
 use crate::{
+    blocker::Blocking,
     flat_router::FlatRoutesView,
     hooks::use_navigate,
     location::{
@@ -10,8 +11,10 @@ use crate::{
     },
     navigate::NavigateOptions,
     nested_router::NestedRoutesView,
+    prefetch::Prefetcher,
     resolve_path::resolve_path,
-    ChooseView, MatchNestedRoutes, NestedRoute, RouteDefs, SsrMode,
+    ChooseView, MatchNestedRoutes, NestedRoute, RouteDefs, RouteLoader,
+    SsrMode,
 };
 use either_of::EitherOf3;
 use leptos::{children, prelude::*};
@@ -112,6 +115,7 @@ where
         set_is_routing,
         query_mutations: Default::default(),
         location_provider,
+        prefetcher: Default::default(),
     });
 
     let children = children.into_inner();
@@ -128,6 +132,7 @@ pub(crate) struct RouterContext {
     pub query_mutations:
         ArcStoredValue<Vec<(Oco<'static, str>, Option<String>)>>,
     pub location_provider: Option<BrowserUrl>,
+    pub prefetcher: Prefetcher,
 }
 
 impl RouterContext {
@@ -177,6 +182,21 @@ impl RouterContext {
             return;
         }
 
+        // ask any blockers registered with `use_blocker` before navigating
+        let blocking = match &self.location_provider {
+            Some(location_provider) => {
+                location_provider.blockers.check(&current, Some(&url))
+            }
+            None => Blocking::Allow,
+        };
+        drop(current);
+        let this = self.clone();
+        blocking.unless_blocked(move || this.navigate_to(url, options));
+    }
+
+    fn navigate_to(&self, url: Url, options: NavigateOptions) {
+        let current = self.current_url.read_untracked();
+
         // update state signal, if necessary
         if options.state != self.state.get_untracked() {
             self.state.set(options.state.clone());
@@ -186,6 +206,9 @@ impl RouterContext {
         let value = url.to_full_path();
         if current != url {
             drop(current);
+            if let Some(location_provider) = &self.location_provider {
+                location_provider.save_scroll_position();
+            }
             self.current_url.set(url);
         }
 
@@ -199,6 +222,19 @@ impl RouterContext {
         }
     }
 
+    /// Prefetches the route matched by the given URL, if it is not the current route.
+    pub fn prefetch(&self, href: &str) {
+        let Ok(url) = BrowserUrl::parse(href) else {
+            return;
+        };
+        let current = self.current_url.read_untracked();
+        if url.origin() != current.origin() || url.path() == current.path() {
+            return;
+        }
+        drop(current);
+        self.prefetcher.prefetch(url);
+    }
+
     pub fn resolve_path<'a>(
         &'a self,
         path: &'a str,
@@ -240,6 +276,7 @@ where
         current_url,
         base,
         set_is_routing,
+        prefetcher,
         ..
     } = use_context()
         .expect("<Routes> should be used inside a <Router> component");
@@ -254,8 +291,10 @@ where
     );
     let outer_owner =
         Owner::current().expect("creating Routes, but no Owner was found");
+    prefetcher.register(routes.clone(), outer_owner.clone());
     move || {
         current_url.track();
+        prefetcher.navigated(current_url.read_untracked().path());
         outer_owner.with(|| {
             current_url.read_untracked().provide_server_action_error()
         });
@@ -293,6 +332,7 @@ where
         current_url,
         base,
         set_is_routing,
+        prefetcher,
         ..
     } = use_context()
         .expect("<FlatRoutes> should be used inside a <Router> component");
@@ -311,9 +351,11 @@ where
 
     let outer_owner =
         Owner::current().expect("creating Router, but no Owner was found");
+    prefetcher.register(routes.clone(), outer_owner.clone());
 
     move || {
         current_url.track();
+        prefetcher.navigated(current_url.read_untracked().path());
         outer_owner.with(|| {
             current_url.read_untracked().provide_server_action_error()
         });
@@ -344,11 +386,19 @@ pub fn Route<Segments, View>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Loads the data for this route, from its params. The data can be accessed with
+    /// [`use_loader_data`](crate::hooks::use_loader_data).
+    #[prop(optional, into)]
+    loader: Option<RouteLoader>,
 ) -> NestedRoute<Segments, (), (), View>
 where
     View: ChooseView,
 {
-    NestedRoute::new(path, view).ssr_mode(ssr)
+    let route = NestedRoute::new(path, view).ssr_mode(ssr);
+    match loader {
+        Some(loader) => route.loader(loader),
+        None => route,
+    }
 }
 
 /// Describes a portion of the nested layout of the app, specifying the route it should match
@@ -368,12 +418,20 @@ pub fn ParentRoute<Segments, View, Children>(
     /// Defaults to out-of-order streaming.
     #[prop(optional)]
     ssr: SsrMode,
+    /// Loads the data for this route, from its params. The data can be accessed with
+    /// [`use_loader_data`](crate::hooks::use_loader_data), in this route or any of its children.
+    #[prop(optional, into)]
+    loader: Option<RouteLoader>,
 ) -> NestedRoute<Segments, Children, (), View>
 where
     View: ChooseView,
 {
     let children = children.into_inner();
-    NestedRoute::new(path, view).ssr_mode(ssr).child(children)
+    let route = NestedRoute::new(path, view).ssr_mode(ssr).child(children);
+    match loader {
+        Some(loader) => route.loader(loader),
+        None => route,
+    }
 }
 
 /// Describes a route that is guarded by a certain condition. This works the same way as
@@ -143,6 +143,7 @@ where
                             provide_context(params_memo);
                             provide_context(url);
                             provide_context(Matched(ArcMemo::from(matched)));
+                            view.load();
                             OwnedView::new(view.choose().await)
                         }
                     })
@@ -295,6 +296,7 @@ where
                             provide_context(Matched(ArcMemo::from(
                                 new_matched,
                             )));
+                            view.load();
                             let view = OwnedView::new(
                                 if let Some(set_is_routing) = set_is_routing {
                                     set_is_routing.set(true);
@@ -399,6 +401,7 @@ where
                             provide_context(url);
                             provide_context(params_memo);
                             provide_context(Matched(ArcMemo::from(matched)));
+                            view.load();
                             view.choose().await
                         })
                     })
@@ -579,6 +582,7 @@ where
                             provide_context(params_memo);
                             provide_context(url);
                             provide_context(Matched(ArcMemo::from(matched)));
+                            view.load();
                             OwnedView::new(view.choose().await)
                         }
                     })
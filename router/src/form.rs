@@ -107,7 +107,7 @@ where
                     return;
                 }
                 let navigate_options = NavigateOptions {
-                    scroll: !noscroll,
+                    scroll: (!noscroll).into(),
                     replace,
                     ..Default::default()
                 };
@@ -1,20 +1,30 @@
 use crate::{
+    blocker::{Blocking, RemoveUnloadListener},
     components::RouterContext,
+    loader::LoaderData,
     location::{Location, Url},
     navigate::NavigateOptions,
     params::{Params, ParamsError, ParamsMap},
 };
-use leptos::{leptos_dom::helpers::request_animation_frame, oco::Oco};
+use leptos::{
+    ev,
+    leptos_dom::helpers::{request_animation_frame, window_event_listener},
+    oco::Oco,
+    server::Resource,
+};
 use reactive_graph::{
     computed::{ArcMemo, Memo},
-    owner::{expect_context, use_context},
+    owner::{expect_context, on_cleanup, use_context},
     signal::{ArcRwSignal, ReadSignal},
     traits::{Get, GetUntracked, ReadUntracked, With, WriteValue},
     wrappers::write::SignalSetter,
 };
 use std::{
     str::FromStr,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 /// See [`query_signal`].
@@ -278,6 +288,78 @@ pub fn use_navigate() -> impl Fn(&str, NavigateOptions) + Clone {
     move |path: &str, options: NavigateOptions| cx.navigate(path, options)
 }
 
+/// Registers a function that is called before each navigation away from the current page, and
+/// can block it, for example if a form has unsaved changes.
+///
+/// The blocker is called with the current URL and the URL being navigated to, and returns either
+/// a `bool` (where `true` blocks the navigation) or a [`Blocking`]. Returning
+/// [`Blocking::pending`] waits for a `Future` to decide, for example while a custom confirmation
+/// dialog is shown.
+///
+/// Blockers are checked for clicks on `<a>` elements, calls to the function returned by
+/// [`use_navigate`] (including the navigations made by [`Form`](crate::components::Form)), and
+/// back/forward navigations. A blocked back/forward navigation is undone by moving back through
+/// the browser history to the current page. Navigations that only change the query or hash are
+/// never blocked.
+///
+/// If the user is leaving the app altogether, by reloading or closing the page or navigating to
+/// another site, the blocker is called with `None` as the new URL. The browser only allows the
+/// page to show its own confirmation dialog in this case, so it is shown unless the blocker
+/// returns `false` or [`Blocking::Allow`].
+///
+/// The blocker is removed when the reactive owner it was registered in is cleaned up.
+///
+/// ```rust
+/// # use leptos::prelude::*;
+/// # use leptos_router::hooks::use_blocker;
+/// # if false { // can't actually navigate, no <Router/>
+/// let (unsaved, set_unsaved) = signal(false);
+/// use_blocker(move |_from, to| {
+///     unsaved.get_untracked()
+///         && to.is_some()
+///         && !window()
+///             .confirm_with_message("Discard unsaved changes?")
+///             .unwrap_or(true)
+/// });
+/// # }
+/// ```
+#[track_caller]
+pub fn use_blocker<R>(
+    blocker: impl Fn(&Url, Option<&Url>) -> R + Send + Sync + 'static,
+) where
+    R: Into<Blocking>,
+{
+    let cx = use_context::<RouterContext>()
+        .expect("You cannot call `use_blocker` outside a <Router>.");
+    // navigations are only blocked in the browser
+    let Some(location_provider) = cx.location_provider else {
+        return;
+    };
+
+    let blockers = location_provider.blockers.clone();
+    // a single `beforeunload` listener asks all of the blockers, while there are any
+    let listen = {
+        let blockers = blockers.clone();
+        let current_url = cx.current_url;
+        move || {
+            let handle = window_event_listener(ev::beforeunload, move |ev| {
+                let blocking =
+                    blockers.check(&current_url.read_untracked(), None);
+                if !matches!(blocking, Blocking::Allow) {
+                    ev.prevent_default();
+                    // older browsers show the dialog only if this is set
+                    ev.set_return_value("");
+                }
+            });
+            Box::new(move || handle.remove()) as RemoveUnloadListener
+        }
+    };
+    let id = blockers
+        .add(Arc::new(move |from, to| blocker(from, to).into()), listen);
+
+    on_cleanup(move || blockers.remove(id));
+}
+
 /// Returns a reactive string that contains the route that was matched for
 /// this [`Route`](crate::components::Route).
 #[track_caller]
@@ -287,3 +369,24 @@ pub fn use_matched() -> Memo<String> {
         .0
         .into()
 }
+
+/// Returns the data loaded by the [`RouteLoader`](crate::RouteLoader) of the current route, or
+/// of the closest parent route with a loader that returns `T`.
+///
+/// The loader starts before the route is rendered, so the resource may already be loading (or
+/// may even have loaded) by the time this is called. Like any other resource, it should be read
+/// inside a [`Suspense`](leptos::suspense::Suspense) or
+/// [`Transition`](leptos::suspense::Transition).
+#[track_caller]
+pub fn use_loader_data<T>() -> Resource<T>
+where
+    T: Send + Sync + 'static,
+{
+    use_context::<LoaderData<T>>()
+        .expect(
+            "use_loader_data called outside a route with a loader that \
+             returns this type",
+        )
+        .0
+        .into()
+}
@@ -124,6 +124,7 @@
 #![cfg_attr(feature = "nightly", feature(auto_traits))]
 #![cfg_attr(feature = "nightly", feature(negative_impls))]
 
+mod blocker;
 /// Components for route definition and for enhanced links and forms.
 pub mod components;
 /// An optimized "flat" router without nested routes.
@@ -133,6 +134,7 @@ mod generate_route_list;
 /// Hooks that can be used to access router state inside your components.
 pub mod hooks;
 mod link;
+mod loader;
 /// Utilities for accessing the current location.
 pub mod location;
 mod matching;
@@ -142,17 +144,23 @@ mod navigate;
 pub mod nested_router;
 /// Support for maps of parameters in the path or in the query.
 pub mod params;
+mod prefetch;
 mod ssr_mode;
 /// Support for static routing.
 pub mod static_routes;
+mod typed_route;
 
+pub use blocker::Blocking;
 pub use generate_route_list::*;
 #[doc(inline)]
-pub use leptos_router_macro::path;
+pub use leptos_router_macro::{path, TypedRoute};
+pub use loader::RouteLoader;
 pub use matching::*;
 pub use method::*;
 pub use navigate::*;
+pub use prefetch::*;
 pub use ssr_mode::*;
+pub use typed_route::{encode_param as __encode_param, TypedRoute};
 
 pub(crate) mod view_transition {
     use js_sys::{Function, Promise, Reflect};
@@ -1,7 +1,15 @@
-use crate::{components::RouterContext, hooks::use_resolved_path};
+use crate::{
+    components::RouterContext,
+    hooks::use_resolved_path,
+    prefetch::{prefetch_in_viewport, Prefetch},
+    ScrollBehavior,
+};
 use leptos::{children::Children, oco::Oco, prelude::*};
 use reactive_graph::{computed::ArcMemo, owner::use_context};
-use std::{borrow::Cow, rc::Rc};
+use std::{borrow::Cow, rc::Rc, time::Duration};
+
+/// How long the pointer has to rest on a link before [`Prefetch::Intent`] prefetches its route.
+const INTENT_DELAY: Duration = Duration::from_millis(80);
 
 /// Describes a value that is either a static or a reactive URL, i.e.,
 /// a [`String`], a [`&str`], or a reactive `Fn() -> String`.
@@ -73,6 +81,12 @@ where
 /// - **`prop:replace`**: If `true`, the link will not add to the browser's history (so, pressing `Back`
 /// will skip this page.)
 ///
+/// The **`data-scroll`** attribute sets the [`ScrollBehavior`] for a link: `data-scroll="restore"`
+/// or `data-scroll="none"`. A `noscroll` or `data-noscroll` attribute is the same as `"none"`.
+///
+/// When the user navigates back or forward, the router restores the scroll position of the window,
+/// and of any element with a `data-scroll-restore="name"` attribute, once the routes have loaded.
+///
 /// Previously, this component took these as component props. Now, they can be added using the
 /// `prop:` syntax, and will be added directly to the DOM. They can work with either `<a>` elements
 /// or the `<A/>` component.
@@ -92,9 +106,16 @@ pub fn A<H>(
     /// a trailing slash.
     #[prop(optional)]
     strict_trailing_slash: bool,
-    /// If `true`, the router will scroll to the top of the window at the end of navigation. Defaults to `true`.
-    #[prop(default = true)]
-    scroll: bool,
+    /// How the router will scroll at the end of navigation. Defaults to [`ScrollBehavior::Top`].
+    ///
+    /// This also accepts a `bool`: `true` scrolls to the top and `false` leaves the scroll
+    /// position unchanged.
+    #[prop(into, optional)]
+    scroll: ScrollBehavior,
+    /// When the route that the link points to should be prefetched. Defaults to
+    /// [`Prefetch::None`].
+    #[prop(optional)]
+    prefetch: Prefetch,
     /// The nodes or elements to be shown inside the link.
     children: Children,
 ) -> impl IntoView
@@ -107,10 +128,67 @@ where
         exact: bool,
         children: Children,
         strict_trailing_slash: bool,
-        scroll: bool,
+        scroll: ScrollBehavior,
+        prefetch: Prefetch,
     ) -> impl IntoView {
-        let RouterContext { current_url, .. } =
-            use_context().expect("tried to use <A/> outside a <Router/>.");
+        let router = use_context::<RouterContext>()
+            .expect("tried to use <A/> outside a <Router/>.");
+        let current_url = router.current_url.clone();
+
+        let prefetch_route = {
+            let href = href.clone();
+            move || {
+                if let Some(href) = href.get_untracked() {
+                    router.prefetch(&href);
+                }
+            }
+        };
+        let intent_timeout = StoredValue::new(None::<TimeoutHandle>);
+        let node_ref = NodeRef::<leptos::html::A>::new();
+        match prefetch {
+            Prefetch::Viewport => {
+                let prefetch_route = prefetch_route.clone();
+                Effect::new(move |_| {
+                    if let Some(el) = node_ref.get() {
+                        prefetch_in_viewport(&el, prefetch_route.clone());
+                    }
+                });
+            }
+            Prefetch::Render => {
+                let href = href.clone();
+                let prefetch_route = prefetch_route.clone();
+                Effect::new(move |_| {
+                    href.track();
+                    prefetch_route();
+                });
+            }
+            _ => {}
+        }
+        let on_mouseenter = {
+            let prefetch_route = prefetch_route.clone();
+            move |_| match prefetch {
+                Prefetch::Hover => prefetch_route(),
+                Prefetch::Intent => {
+                    let handle = set_timeout_with_handle(
+                        prefetch_route.clone(),
+                        INTENT_DELAY,
+                    );
+                    intent_timeout.set_value(handle.ok());
+                }
+                _ => {}
+            }
+        };
+        let on_mouseleave = move |_| {
+            if let Some(handle) = intent_timeout.write_value().take() {
+                handle.clear();
+            }
+        };
+        let on_intent = move || {
+            if prefetch == Prefetch::Intent {
+                prefetch_route();
+            }
+        };
+
         let is_active = {
             let href = href.clone();
             move || {
@@ -133,7 +211,15 @@ where
                 href=move || href.get().unwrap_or_default()
                 target=target
                 aria-current=move || if is_active() { Some("page") } else { None }
-                data-noscroll=!scroll
+                data-scroll=scroll.as_attribute()
+                node_ref=node_ref
+                on:mouseenter=on_mouseenter
+                on:mouseleave=on_mouseleave
+                on:focus={
+                    let on_intent = on_intent.clone();
+                    move |_| on_intent()
+                }
+                on:touchstart=move |_| on_intent()
             >
 
                 {children()}
@@ -142,7 +228,15 @@ where
     }
 
     let href = use_resolved_path(move || href.to_href()());
-    inner(href, target, exact, children, strict_trailing_slash, scroll)
+    inner(
+        href,
+        target,
+        exact,
+        children,
+        strict_trailing_slash,
+        scroll,
+        prefetch,
+    )
 }
 
 // Test if `href` is active for `location`.  Assumes _both_ `href` and `location` begin with a `'/'`.
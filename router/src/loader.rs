@@ -0,0 +1,234 @@
+use crate::{
+    params::ParamsMap,
+    prefetch::{take_prefetched, Prefetching},
+    RouteMatchId,
+};
+use futures::channel::oneshot;
+use leptos::server::ArcResource;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::ArcMemo,
+    owner::{provide_context, use_context},
+    traits::{Get, GetUntracked},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    fmt,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+/// Loads the data for a route, from the params it has matched.
+///
+/// A loader can be created from any function that takes a [`ParamsMap`] and returns a [`Future`],
+/// and is attached to a route with the `loader` prop on [`Route`](crate::components::Route) or
+/// [`ParentRoute`](crate::components::ParentRoute).
+///
+/// The router starts the loaders for all of the matched routes at the same time, as soon as the
+/// URL has been matched, rather than waiting for each parent route to render before its child
+/// route starts loading its data. The data is loaded into a [`Resource`](leptos::prelude::Resource),
+/// which is serialized from the server to the client like any other resource, and is loaded again
+/// whenever the params change. It can be accessed inside the route with
+/// [`use_loader_data`](crate::hooks::use_loader_data).
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{
+///     components::*, hooks::use_loader_data, params::ParamsMap, path,
+/// };
+///
+/// # async fn fetch_contact(id: Option<String>) -> String { String::new() }
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///         <Router>
+///             <Routes fallback=|| "Not found.">
+///                 <Route
+///                     path=path!(":id")
+///                     view=Contact
+///                     loader=|params: ParamsMap| fetch_contact(params.get("id"))
+///                 />
+///             </Routes>
+///         </Router>
+///     }
+/// }
+///
+/// #[component]
+/// fn Contact() -> impl IntoView {
+///     let contact = use_loader_data::<String>();
+///     view! {
+///         <Suspense>
+///             <p>{move || contact.get()}</p>
+///         </Suspense>
+///     }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct RouteLoader(Arc<dyn Fn(RouteMatchId) + Send + Sync>);
+
+impl fmt::Debug for RouteLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteLoader").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RouteLoader {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for RouteLoader {}
+
+impl RouteLoader {
+    /// Starts loading the data for the route.
+    ///
+    /// This should be called with the route's owner and context, before its view is created.
+    pub(crate) fn load(&self, route: RouteMatchId) {
+        (self.0)(route)
+    }
+}
+
+impl<F, Fut, T> From<F> for RouteLoader
+where
+    F: Fn(ParamsMap) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = T> + Send + 'static,
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    fn from(fun: F) -> Self {
+        let fun = Arc::new(fun);
+        RouteLoader(Arc::new(move |route| {
+            let params = use_context::<ArcMemo<ParamsMap>>()
+                .expect("route loaders can only run inside a matched route");
+
+            // if the route is only being prefetched, start loading the data into the cache
+            if let Some(prefetching) = use_context::<Prefetching>() {
+                let params = params.get_untracked();
+                let data = fun(params.clone());
+                prefetching.cache_loading(route, params, data);
+                return;
+            }
+
+            // otherwise, wait for the data that was prefetched for these params, if any, the
+            // first time the resource loads, even if it is still loading
+            let prefetched = take_prefetched::<oneshot::Receiver<T>>(route);
+            let prefetched = Arc::new(Mutex::new(prefetched));
+            let fun = Arc::clone(&fun);
+            let resource = ArcResource::new(
+                move || params.get(),
+                move |params| {
+                    let prefetched = prefetched.lock().or_poisoned().take();
+                    let fun = Arc::clone(&fun);
+                    async move {
+                        match prefetched {
+                            Some(prefetched) => match prefetched.await {
+                                Ok(data) => data,
+                                Err(_) => fun(params).await,
+                            },
+                            None => fun(params).await,
+                        }
+                    }
+                },
+            );
+            provide_context(LoaderData(resource));
+        }))
+    }
+}
+
+/// The data loaded by a route's [`RouteLoader`], provided as context to the route.
+pub(crate) struct LoaderData<T>(pub ArcResource<T>);
+
+impl<T> Clone for LoaderData<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        hooks::use_loader_data, matching::RouteDefs, params::ParamsMap,
+        ChooseView, MatchInterface, MatchParams, NestedRoute, StaticSegment,
+    };
+    use any_spawner::Executor;
+    use reactive_graph::{
+        computed::ArcMemo,
+        owner::{provide_context, Owner},
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::task::LocalSet;
+
+    static ACCOUNT_LOADS: AtomicUsize = AtomicUsize::new(0);
+    static ACCOUNT_VIEWS: AtomicUsize = AtomicUsize::new(0);
+
+    #[tokio::test]
+    async fn loading_a_route_starts_its_loader_before_its_view_is_created() {
+        _ = Executor::init_tokio();
+        LocalSet::new()
+            .run_until(async {
+                let owner = Owner::new();
+                owner.set();
+                let routes = RouteDefs::new(
+                    NestedRoute::new(StaticSegment("account"), || {
+                        ACCOUNT_VIEWS.fetch_add(1, Ordering::Relaxed);
+                    })
+                    .loader(|_: ParamsMap| async {
+                        ACCOUNT_LOADS.fetch_add(1, Ordering::Relaxed) + 1
+                    }),
+                );
+                let matched = routes.match_route("/account").unwrap();
+                let params =
+                    matched.to_params().into_iter().collect::<ParamsMap>();
+                let (view, _) = matched.into_view_and_child();
+
+                let route = owner.child();
+                route.with(|| {
+                    provide_context(ArcMemo::new(move |_| params.clone()));
+                    view.load();
+                });
+                let data = route.with(use_loader_data::<usize>).await;
+                assert_eq!(data, 1);
+                assert_eq!(ACCOUNT_LOADS.load(Ordering::Relaxed), 1);
+                assert_eq!(ACCOUNT_VIEWS.load(Ordering::Relaxed), 0);
+
+                // the view uses the data that has already loaded
+                route.with(|| view.choose()).await;
+                assert_eq!(ACCOUNT_LOADS.load(Ordering::Relaxed), 1);
+                assert_eq!(ACCOUNT_VIEWS.load(Ordering::Relaxed), 1);
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn loader_data_is_available_to_nested_routes() {
+        _ = Executor::init_tokio();
+        LocalSet::new()
+            .run_until(async {
+                let owner = Owner::new();
+                owner.set();
+                let routes = RouteDefs::new(
+                    NestedRoute::new(StaticSegment("team"), || ())
+                        .loader(|_: ParamsMap| async { String::from("team") })
+                        .child(NestedRoute::new(
+                            StaticSegment("members"),
+                            || (),
+                        )),
+                );
+                let matched = routes.match_route("/team/members").unwrap();
+                let (parent_view, child) = matched.into_view_and_child();
+                let (child_view, _) = child.unwrap().into_view_and_child();
+
+                let parent = owner.child();
+                parent.with(|| {
+                    provide_context(ArcMemo::new(|_| ParamsMap::new()));
+                    parent_view.load();
+                });
+                let child = parent.child();
+                child.with(|| child_view.load());
+
+                let data = child.with(use_loader_data::<String>).await;
+                assert_eq!(data, "team");
+            })
+            .await;
+    }
+}
@@ -1,8 +1,14 @@
 use super::{handle_anchor_click, LocationChange, LocationProvider, Url};
-use crate::{hooks::use_navigate, params::ParamsMap};
+use crate::{
+    blocker::{Blockers, Blocking},
+    hooks::use_navigate,
+    params::ParamsMap,
+    ScrollBehavior,
+};
+use any_spawner::Executor;
 use core::fmt;
 use futures::channel::oneshot;
-use js_sys::{try_iter, Array, JsString};
+use js_sys::{try_iter, Array, JsString, Object, Reflect};
 use leptos::prelude::*;
 use or_poisoned::OrPoisoned;
 use reactive_graph::{
@@ -12,12 +18,35 @@ use reactive_graph::{
 use std::{
     borrow::Cow,
     boxed::Box,
+    collections::{HashMap, VecDeque},
+    future::Future,
+    mem,
+    pin::Pin,
     string::String,
     sync::{Arc, Mutex},
 };
 use tachys::dom::{document, window};
 use wasm_bindgen::{closure::Closure, JsCast, JsValue};
-use web_sys::{Event, UrlSearchParams};
+use web_sys::{Element, Event, UrlSearchParams};
+
+/// The attribute that marks an element as a named scroll container, whose scroll position is
+/// saved and restored along with the window's.
+const SCROLL_CONTAINER_ATTR: &str = "data-scroll-restore";
+
+/// The property of `history.state` that holds the key of each history entry.
+const ENTRY_KEY_PROP: &str = "__leptosEntryKey";
+
+/// The property of `history.state` that holds the position of each history entry in the session
+/// history, so that a back/forward navigation that skips several entries can be undone.
+const ENTRY_INDEX_PROP: &str = "__leptosEntryIndex";
+
+/// The property of `history.state` that holds the [`ScrollBehavior`] each history entry was
+/// navigated to with, if it is not the default.
+const ENTRY_SCROLL_PROP: &str = "__leptosScroll";
+
+/// The most scroll positions that are kept at once. The positions of the history entries that
+/// were left longest ago are dropped first.
+const MAX_SCROLL_POSITIONS: usize = 100;
 
 #[derive(Clone)]
 pub struct BrowserUrl {
@@ -25,6 +54,246 @@ pub struct BrowserUrl {
     pub(crate) pending_navigation: Arc<Mutex<Option<oneshot::Sender<()>>>>,
     pub(crate) path_stack: ArcStoredValue<Vec<Url>>,
     pub(crate) is_back: ArcRwSignal<bool>,
+    scroll_positions: ArcStoredValue<ScrollPositions>,
+    /// Where to scroll once the routes for a back/forward navigation have loaded.
+    pending_scroll: ArcStoredValue<Option<ScrollTarget>>,
+    pub(crate) blockers: Blockers,
+    /// Set while a blocked back/forward navigation is being undone, so that the `popstate` event
+    /// this causes is ignored.
+    undoing_traversal: ArcStoredValue<bool>,
+    /// Set while a back/forward navigation is being repeated after its blockers allowed it.
+    allowed_traversal: ArcStoredValue<bool>,
+    /// The position of the current history entry in the session history, if it is known.
+    entry_index: ArcStoredValue<Option<i32>>,
+}
+
+/// The scroll position of the window and of each named scroll container for one history entry.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct ScrollPosition {
+    x: f64,
+    y: f64,
+    containers: HashMap<String, (i32, i32)>,
+}
+
+impl ScrollPosition {
+    fn current() -> Self {
+        let window = window();
+        let containers = scroll_containers()
+            .map(|(name, el)| (name, (el.scroll_left(), el.scroll_top())))
+            .collect();
+        Self {
+            x: window.scroll_x().unwrap_or_default(),
+            y: window.scroll_y().unwrap_or_default(),
+            containers,
+        }
+    }
+
+    fn restore(&self) {
+        window().scroll_to_with_x_and_y(self.x, self.y);
+        for (name, el) in scroll_containers() {
+            if let Some((left, top)) = self.containers.get(&name) {
+                el.set_scroll_left(*left);
+                el.set_scroll_top(*top);
+            }
+        }
+    }
+}
+
+/// The scroll positions saved for each history entry, by the key stored in its `history.state`.
+///
+/// The browser's own scroll restoration is left as it is: the saved position is restored again
+/// once the routes for the entry have loaded.
+#[derive(Debug, Default)]
+struct ScrollPositions {
+    /// Distinguishes the keys created by this page load from those of earlier ones, which may
+    /// still be in `history.state`.
+    session: String,
+    next_key: u64,
+    /// The key of the current history entry, if it has one.
+    current: Option<String>,
+    saved: VecDeque<SavedPosition>,
+}
+
+/// The scroll position saved for one history entry.
+#[derive(Debug)]
+struct SavedPosition {
+    key: String,
+    /// The path of the entry, so that its position can be restored when navigating to the same
+    /// path again with [`ScrollBehavior::Restore`].
+    path: String,
+    position: ScrollPosition,
+}
+
+/// Where to scroll once a navigation has finished, unless the new URL has a hash that matches
+/// the `id` of an element.
+#[derive(Debug, Clone, PartialEq)]
+enum ScrollTarget {
+    Position(ScrollPosition),
+    Top,
+    Unchanged,
+}
+
+impl ScrollPositions {
+    fn new(session: String) -> Self {
+        Self {
+            session,
+            ..Default::default()
+        }
+    }
+
+    /// Returns a key for a new history entry.
+    fn next_key(&mut self) -> String {
+        self.next_key += 1;
+        format!("{}-{}", self.session, self.next_key)
+    }
+
+    /// Saves the scroll position for the current history entry, which is at `path`.
+    fn save(&mut self, path: &str, position: ScrollPosition) {
+        let Some(key) = self.current.clone() else {
+            return;
+        };
+        self.saved.retain(|saved| saved.key != key);
+        self.saved.push_back(SavedPosition {
+            key,
+            path: path.to_string(),
+            position,
+        });
+        while self.saved.len() > MAX_SCROLL_POSITIONS {
+            self.saved.pop_front();
+        }
+    }
+
+    /// Returns the scroll position saved for the current history entry.
+    fn current(&self) -> Option<&ScrollPosition> {
+        let key = self.current.as_ref()?;
+        self.saved
+            .iter()
+            .find(|saved| saved.key == *key)
+            .map(|saved| &saved.position)
+    }
+
+    /// Returns the scroll position saved most recently for any history entry at `path`.
+    fn last_saved_for(&self, path: &str) -> Option<&ScrollPosition> {
+        self.saved
+            .iter()
+            .rev()
+            .find(|saved| saved.path == path)
+            .map(|saved| &saved.position)
+    }
+
+    /// Returns where to scroll after navigating to a new history entry at `path`.
+    fn after_navigation(
+        &self,
+        behavior: ScrollBehavior,
+        path: &str,
+    ) -> ScrollTarget {
+        match behavior {
+            ScrollBehavior::Restore => self
+                .last_saved_for(path)
+                .cloned()
+                .map_or(ScrollTarget::Top, ScrollTarget::Position),
+            ScrollBehavior::Top => ScrollTarget::Top,
+            ScrollBehavior::None => ScrollTarget::Unchanged,
+        }
+    }
+
+    /// Returns where to scroll after a back/forward navigation to the current history entry,
+    /// which was first navigated to with `behavior`.
+    fn after_traversal(&self, behavior: ScrollBehavior) -> ScrollTarget {
+        match behavior {
+            ScrollBehavior::None => ScrollTarget::Unchanged,
+            ScrollBehavior::Restore | ScrollBehavior::Top => self
+                .current()
+                .cloned()
+                .map_or(ScrollTarget::Top, ScrollTarget::Position),
+        }
+    }
+}
+
+/// Returns the key stored in the `history.state` of a history entry.
+fn entry_key(state: &JsValue) -> Option<String> {
+    if !state.is_object() {
+        return None;
+    }
+    Reflect::get(state, &JsValue::from_str(ENTRY_KEY_PROP))
+        .ok()?
+        .as_string()
+}
+
+/// Returns the position in the session history stored in the `history.state` of a history entry.
+fn entry_index(state: &JsValue) -> Option<i32> {
+    if !state.is_object() {
+        return None;
+    }
+    Reflect::get(state, &JsValue::from_str(ENTRY_INDEX_PROP))
+        .ok()?
+        .as_f64()
+        .map(|index| index as i32)
+}
+
+/// Returns the [`ScrollBehavior`] stored in the `history.state` of a history entry.
+fn entry_scroll(state: &JsValue) -> ScrollBehavior {
+    let value = state
+        .is_object()
+        .then(|| Reflect::get(state, &JsValue::from_str(ENTRY_SCROLL_PROP)))
+        .and_then(Result::ok)
+        .and_then(|value| value.as_string());
+    ScrollBehavior::from_attribute(value.as_deref())
+}
+
+/// Returns a copy of the `history.state` of a history entry, with its key, its position in the
+/// session history and the [`ScrollBehavior`] it was navigated to with added.
+///
+/// State that is not a plain object cannot hold a key, so it is returned as it is, and the
+/// scroll position of its entry is not saved.
+fn with_entry_key(
+    state: JsValue,
+    key: &str,
+    index: i32,
+    scroll: ScrollBehavior,
+) -> JsValue {
+    let state = if state.is_undefined() || state.is_null() {
+        Object::new()
+    } else if Array::is_array(&state) {
+        return state;
+    } else if let Some(state) = state.dyn_ref::<Object>() {
+        // the state is cloned when it is added to the history anyway, so copying it does not
+        // change what is stored
+        Object::assign(&Object::new(), state)
+    } else {
+        return state;
+    };
+    _ = Reflect::set(
+        &state,
+        &JsValue::from_str(ENTRY_KEY_PROP),
+        &JsValue::from_str(key),
+    );
+    _ = Reflect::set(
+        &state,
+        &JsValue::from_str(ENTRY_INDEX_PROP),
+        &JsValue::from(index),
+    );
+    if let Some(scroll) = scroll.as_attribute() {
+        _ = Reflect::set(
+            &state,
+            &JsValue::from_str(ENTRY_SCROLL_PROP),
+            &JsValue::from_str(scroll),
+        );
+    }
+    state.into()
+}
+
+/// Iterates over the named scroll containers that are currently in the document.
+fn scroll_containers() -> impl Iterator<Item = (String, Element)> {
+    let els = document()
+        .query_selector_all(&format!("[{SCROLL_CONTAINER_ATTR}]"))
+        .ok();
+    let len = els.as_ref().map(|els| els.length()).unwrap_or_default();
+    (0..len).filter_map(move |i| {
+        let el = els.as_ref()?.item(i)?.dyn_into::<Element>().ok()?;
+        let name = el.get_attribute(SCROLL_CONTAINER_ATTR)?;
+        Some((name, el))
+    })
 }
 
 impl fmt::Debug for BrowserUrl {
@@ -54,6 +323,125 @@ impl BrowserUrl {
             window().scroll_to_with_x_and_y(0.0, 0.0);
         }
     }
+
+    /// Scrolls the window at the end of a navigation.
+    fn scroll_to(target: ScrollTarget) {
+        match target {
+            // wait a frame, so that the new route has been rendered
+            ScrollTarget::Position(position) => {
+                request_animation_frame(move || position.restore())
+            }
+            ScrollTarget::Top => Self::scroll_to_el(true),
+            ScrollTarget::Unchanged => Self::scroll_to_el(false),
+        }
+    }
+
+    /// Updates the URL after a back/forward navigation.
+    fn traverse(&self, new_url: Url, is_navigating_back: bool) {
+        self.is_back.set(is_navigating_back);
+
+        self.save_scroll_position();
+        let state = window().history().and_then(|history| history.state()).ok();
+        self.scroll_positions.write_value().current =
+            state.as_ref().and_then(entry_key);
+        *self.entry_index.write_value() = state.as_ref().and_then(entry_index);
+        let behavior = state.as_ref().map(entry_scroll).unwrap_or_default();
+        let target =
+            self.scroll_positions.read_value().after_traversal(behavior);
+        let same_path = self.url.read_untracked().path() == new_url.path();
+        self.url.set(new_url);
+
+        // if the path has changed, wait until the new route is ready before
+        // restoring the scroll position
+        if same_path {
+            Self::scroll_to(target);
+        } else {
+            *self.pending_scroll.write_value() = Some(target);
+        }
+    }
+
+    /// Returns how many entries a back/forward navigation has moved through the session
+    /// history, which is negative for a back navigation.
+    ///
+    /// This can only be told from the positions stored in the history entries, so it is assumed
+    /// to be a single entry if either of them does not have one.
+    fn traversal_delta(&self, is_navigating_back: bool) -> i32 {
+        let new_index = window()
+            .history()
+            .and_then(|history| history.state())
+            .ok()
+            .and_then(|state| entry_index(&state));
+        match (*self.entry_index.read_value(), new_index) {
+            (Some(current), Some(new)) if current != new => new - current,
+            _ if is_navigating_back => -1,
+            _ => 1,
+        }
+    }
+
+    /// Returns to the history entry that a blocked back/forward navigation left.
+    fn undo_traversal(&self, delta: i32) {
+        let Ok(history) = window().history() else {
+            return;
+        };
+        *self.undoing_traversal.write_value() = true;
+        _ = history.go_with_delta(-delta);
+    }
+
+    /// Repeats a back/forward navigation that was undone while its blockers were pending.
+    fn redo_traversal(&self, delta: i32) {
+        let Ok(history) = window().history() else {
+            return;
+        };
+        _ = history.go_with_delta(delta);
+    }
+
+    /// Updates the URL for a navigation that its blockers have allowed, and completes the
+    /// navigation once the new routes are ready, unless another navigation has started by then.
+    fn start_navigation(
+        &self,
+        new_url: Url,
+        loc: LocationChange,
+    ) -> impl Future<Output = ()> {
+        let same_path = {
+            let curr = self.url.read_untracked();
+            curr.origin() == new_url.origin() && curr.path() == new_url.path()
+        };
+
+        self.save_scroll_position();
+        self.url.set(new_url.clone());
+        if same_path {
+            self.complete_navigation(&loc);
+        }
+        let (tx, rx) = oneshot::channel::<()>();
+        if !same_path {
+            *self.pending_navigation.lock().or_poisoned() = Some(tx);
+        }
+        let this = self.clone();
+        async move {
+            if !same_path {
+                // if it has been canceled, ignore
+                // otherwise, complete navigation -- i.e., set URL in address bar
+                if rx.await.is_ok() {
+                    // only update the URL in the browser if this is still the current URL
+                    // if we've navigated to another page in the meantime, don't update the
+                    // browser URL
+                    let curr = this.url.read_untracked();
+                    if curr == new_url {
+                        this.complete_navigation(&loc);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Saves the scroll position for the current history entry, before navigating away from it.
+    pub(crate) fn save_scroll_position(&self) {
+        let position = ScrollPosition::current();
+        let url = self.url.read_untracked();
+        self.scroll_positions
+            .write_value()
+            .save(url.path(), position);
+    }
 }
 
 impl LocationProvider for BrowserUrl {
@@ -69,6 +457,14 @@ impl LocationProvider for BrowserUrl {
             pending_navigation: Default::default(),
             path_stack,
             is_back: Default::default(),
+            scroll_positions: ArcStoredValue::new(ScrollPositions::new(
+                js_sys::Date::now().to_string(),
+            )),
+            pending_scroll: Default::default(),
+            blockers: Default::default(),
+            undoing_traversal: Default::default(),
+            allowed_traversal: Default::default(),
+            entry_index: Default::default(),
         })
     }
 
@@ -117,41 +513,48 @@ impl LocationProvider for BrowserUrl {
 
     fn init(&self, base: Option<Cow<'static, str>>) {
         let window = window();
+        // give the initial history entry a key, so that its scroll position can be saved
+        if let Ok(history) = window.history() {
+            let state = history.state().unwrap_or(JsValue::UNDEFINED);
+            let state = match entry_key(&state) {
+                Some(_) => state,
+                None => {
+                    let key = self.scroll_positions.write_value().next_key();
+                    let state = with_entry_key(
+                        state,
+                        &key,
+                        0,
+                        ScrollBehavior::default(),
+                    );
+                    _ = history.replace_state(&state, "");
+                    state
+                }
+            };
+            self.scroll_positions.write_value().current = entry_key(&state);
+            *self.entry_index.write_value() = entry_index(&state);
+        }
+
         let navigate = {
-            let url = self.url.clone();
-            let pending = Arc::clone(&self.pending_navigation);
             let this = self.clone();
-            move |new_url: Url, loc| {
-                let same_path = {
-                    let curr = url.read_untracked();
-                    curr.origin() == new_url.origin()
-                        && curr.path() == new_url.path()
-                };
-
-                url.set(new_url.clone());
-                if same_path {
-                    this.complete_navigation(&loc);
-                }
-                let pending = Arc::clone(&pending);
-                let (tx, rx) = oneshot::channel::<()>();
-                if !same_path {
-                    *pending.lock().or_poisoned() = Some(tx);
-                }
-                let url = url.clone();
-                let this = this.clone();
-                async move {
-                    if !same_path {
-                        // if it has been canceled, ignore
-                        // otherwise, complete navigation -- i.e., set URL in address bar
-                        if rx.await.is_ok() {
-                            // only update the URL in the browser if this is still the current URL
-                            // if we've navigated to another page in the meantime, don't update the
-                            // browser URL
-                            let curr = url.read_untracked();
-                            if curr == new_url {
-                                this.complete_navigation(&loc);
+            move |new_url: Url, loc: LocationChange| {
+                // ask the blockers before making any changes, and only wait for them if one of
+                // them has to ask the user first
+                let blocking = this
+                    .blockers
+                    .check(&this.url.read_untracked(), Some(&new_url));
+                match blocking {
+                    Blocking::Allow => {
+                        Box::pin(this.start_navigation(new_url, loc))
+                            as Pin<Box<dyn Future<Output = ()>>>
+                    }
+                    Blocking::Block => Box::pin(async {}),
+                    Blocking::Pending(fut) => {
+                        let this = this.clone();
+                        Box::pin(async move {
+                            if !fut.await {
+                                this.start_navigation(new_url, loc).await;
                             }
-                        }
+                        })
                     }
                 }
             }
@@ -180,25 +583,63 @@ impl LocationProvider for BrowserUrl {
 
         // handle popstate event (forward/back navigation)
         let cb = {
-            let url = self.url.clone();
-            let path_stack = self.path_stack.clone();
-            let is_back = self.is_back.clone();
-            move || match Self::current() {
-                Ok(new_url) => {
-                    let stack = path_stack.read_value();
-                    let is_navigating_back = stack.len() == 1
-                        || (stack.len() >= 2
-                            && stack.get(stack.len() - 2) == Some(&new_url));
-
-                    is_back.set(is_navigating_back);
-
-                    url.set(new_url);
+            let this = self.clone();
+            move || {
+                // this event was caused by undoing a blocked navigation
+                if mem::take(&mut *this.undoing_traversal.write_value()) {
+                    return;
                 }
-                Err(e) => {
-                    #[cfg(feature = "tracing")]
-                    tracing::error!("{e:?}");
-                    #[cfg(not(feature = "tracing"))]
-                    web_sys::console::error_1(&e);
+
+                match Self::current() {
+                    Ok(new_url) => {
+                        let is_navigating_back = {
+                            let stack = this.path_stack.read_value();
+                            stack.len() == 1
+                                || (stack.len() >= 2
+                                    && stack.get(stack.len() - 2)
+                                        == Some(&new_url))
+                        };
+
+                        let blocking = if mem::take(
+                            &mut *this.allowed_traversal.write_value(),
+                        ) {
+                            Blocking::Allow
+                        } else {
+                            this.blockers.check(
+                                &this.url.read_untracked(),
+                                Some(&new_url),
+                            )
+                        };
+                        match blocking {
+                            Blocking::Allow => {
+                                this.traverse(new_url, is_navigating_back)
+                            }
+                            Blocking::Block => this.undo_traversal(
+                                this.traversal_delta(is_navigating_back),
+                            ),
+                            // the browser has already changed the URL, so put it back while
+                            // waiting, and repeat the navigation if it is allowed
+                            Blocking::Pending(fut) => {
+                                let delta =
+                                    this.traversal_delta(is_navigating_back);
+                                this.undo_traversal(delta);
+                                let this = this.clone();
+                                Executor::spawn_local(async move {
+                                    if !fut.await {
+                                        *this.allowed_traversal.write_value() =
+                                            true;
+                                        this.redo_traversal(delta);
+                                    }
+                                });
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::error!("{e:?}");
+                        #[cfg(not(feature = "tracing"))]
+                        web_sys::console::error_1(&e);
+                    }
                 }
             }
         };
@@ -213,29 +654,34 @@ impl LocationProvider for BrowserUrl {
     }
 
     fn ready_to_complete(&self) {
+        let pending_scroll = self.pending_scroll.write_value().take();
         if let Some(tx) = self.pending_navigation.lock().or_poisoned().take() {
             _ = tx.send(());
+        } else if let Some(target) = pending_scroll {
+            Self::scroll_to(target);
         }
     }
 
     fn complete_navigation(&self, loc: &LocationChange) {
         let history = window().history().unwrap();
 
+        let key = self.scroll_positions.write_value().next_key();
+        let index = self.entry_index.read_value().unwrap_or_default()
+            + i32::from(!loc.replace);
+        let state =
+            with_entry_key(loc.state.to_js_value(), &key, index, loc.scroll);
         if loc.replace {
             history
-                .replace_state_with_url(
-                    &loc.state.to_js_value(),
-                    "",
-                    Some(&loc.value),
-                )
+                .replace_state_with_url(&state, "", Some(&loc.value))
                 .unwrap();
         } else {
             // push the "forward direction" marker
-            let state = &loc.state.to_js_value();
             history
-                .push_state_with_url(state, "", Some(&loc.value))
+                .push_state_with_url(&state, "", Some(&loc.value))
                 .unwrap();
         }
+        self.scroll_positions.write_value().current = entry_key(&state);
+        *self.entry_index.write_value() = entry_index(&state);
 
         // add this URL to the "path stack" for detecting back navigations, and
         // unset "navigating back" state
@@ -244,8 +690,13 @@ impl LocationProvider for BrowserUrl {
             self.is_back.set(false);
         }
 
-        // scroll to el
-        Self::scroll_to_el(loc.scroll);
+        let target = {
+            let url = self.url.read_untracked();
+            self.scroll_positions
+                .read_value()
+                .after_navigation(loc.scroll, url.path())
+        };
+        Self::scroll_to(target);
     }
 
     fn redirect(loc: &str) {
@@ -313,3 +764,165 @@ pub(crate) fn resolve_redirect_url(loc: &str) -> Option<web_sys::Url> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ScrollPosition, ScrollPositions, ScrollTarget, MAX_SCROLL_POSITIONS,
+    };
+    use crate::ScrollBehavior;
+
+    fn at(y: f64) -> ScrollPosition {
+        ScrollPosition {
+            y,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn saves_positions_by_history_entry() {
+        let mut positions = ScrollPositions::new("session".into());
+        let first = positions.next_key();
+        let second = positions.next_key();
+        assert_ne!(first, second);
+
+        positions.current = Some(first.clone());
+        positions.save("/", at(100.0));
+        positions.current = Some(second.clone());
+        assert!(positions.current().is_none());
+        positions.save("/", at(200.0));
+
+        positions.current = Some(first);
+        assert_eq!(positions.current().unwrap().y, 100.0);
+        positions.save("/", at(150.0));
+        assert_eq!(positions.current().unwrap().y, 150.0);
+        positions.current = Some(second);
+        assert_eq!(positions.current().unwrap().y, 200.0);
+    }
+
+    #[test]
+    fn does_not_save_entries_without_a_key() {
+        let mut positions = ScrollPositions::new("session".into());
+        positions.save("/", at(100.0));
+        assert!(positions.saved.is_empty());
+        assert!(positions.current().is_none());
+    }
+
+    #[test]
+    fn drops_the_positions_left_longest_ago() {
+        let mut positions = ScrollPositions::new("session".into());
+        let first = positions.next_key();
+        positions.current = Some(first.clone());
+        positions.save("/", at(1.0));
+        let second = positions.next_key();
+        positions.current = Some(second.clone());
+        positions.save("/", at(2.0));
+
+        // leaving the first entry again makes it the most recent
+        positions.current = Some(first.clone());
+        positions.save("/", at(1.0));
+
+        for _ in 0..MAX_SCROLL_POSITIONS - 1 {
+            positions.current = Some(positions.next_key());
+            positions.save("/", at(0.0));
+        }
+        assert_eq!(positions.saved.len(), MAX_SCROLL_POSITIONS);
+
+        positions.current = Some(second);
+        assert!(positions.current().is_none());
+        positions.current = Some(first);
+        assert_eq!(positions.current().unwrap().y, 1.0);
+    }
+
+    #[test]
+    fn scroll_behavior_survives_the_history_state() {
+        for behavior in [
+            ScrollBehavior::Restore,
+            ScrollBehavior::Top,
+            ScrollBehavior::None,
+        ] {
+            assert_eq!(
+                ScrollBehavior::from_attribute(behavior.as_attribute()),
+                behavior
+            );
+        }
+        assert_eq!(ScrollBehavior::from(true), ScrollBehavior::Top);
+        assert_eq!(ScrollBehavior::from(false), ScrollBehavior::None);
+    }
+
+    #[test]
+    fn navigating_with_top_scrolls_to_the_top() {
+        let mut positions = ScrollPositions::new("session".into());
+        positions.current = Some(positions.next_key());
+        positions.save("/list", at(100.0));
+        positions.current = Some(positions.next_key());
+
+        assert_eq!(
+            positions.after_navigation(ScrollBehavior::Top, "/list"),
+            ScrollTarget::Top
+        );
+    }
+
+    #[test]
+    fn navigating_with_restore_uses_the_last_position_for_the_path() {
+        let mut positions = ScrollPositions::new("session".into());
+        positions.current = Some(positions.next_key());
+        positions.save("/list", at(100.0));
+        positions.current = Some(positions.next_key());
+        positions.save("/item", at(50.0));
+        positions.current = Some(positions.next_key());
+        positions.save("/list", at(300.0));
+        positions.current = Some(positions.next_key());
+
+        assert_eq!(
+            positions.after_navigation(ScrollBehavior::Restore, "/list"),
+            ScrollTarget::Position(at(300.0))
+        );
+        assert_eq!(
+            positions.after_navigation(ScrollBehavior::Restore, "/other"),
+            ScrollTarget::Top
+        );
+    }
+
+    #[test]
+    fn navigating_with_none_leaves_the_scroll_position() {
+        let mut positions = ScrollPositions::new("session".into());
+        positions.current = Some(positions.next_key());
+        positions.save("/list", at(100.0));
+        positions.current = Some(positions.next_key());
+
+        assert_eq!(
+            positions.after_navigation(ScrollBehavior::None, "/list"),
+            ScrollTarget::Unchanged
+        );
+    }
+
+    #[test]
+    fn traversals_restore_the_entry_unless_it_was_navigated_with_none() {
+        let mut positions = ScrollPositions::new("session".into());
+        let first = positions.next_key();
+        positions.current = Some(first.clone());
+        positions.save("/list", at(100.0));
+        positions.current = Some(positions.next_key());
+
+        // going back to the first entry
+        positions.current = Some(first);
+        for behavior in [ScrollBehavior::Restore, ScrollBehavior::Top] {
+            assert_eq!(
+                positions.after_traversal(behavior),
+                ScrollTarget::Position(at(100.0))
+            );
+        }
+        assert_eq!(
+            positions.after_traversal(ScrollBehavior::None),
+            ScrollTarget::Unchanged
+        );
+
+        // an entry without a saved position
+        positions.current = Some(positions.next_key());
+        assert_eq!(
+            positions.after_traversal(ScrollBehavior::Top),
+            ScrollTarget::Top
+        );
+    }
+}
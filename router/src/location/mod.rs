@@ -18,7 +18,7 @@ use web_sys::{Event, HtmlAnchorElement, MouseEvent};
 
 mod history;
 mod server;
-use crate::params::ParamsMap;
+use crate::{params::ParamsMap, ScrollBehavior};
 pub use history::*;
 pub use server::*;
 
@@ -133,9 +133,27 @@ impl Url {
             js_sys::decode_uri(s).unwrap().into()
         }
 
+        // like `decodeURI`, this leaves the escapes of reserved characters as they are, so that
+        // an escaped `/` in a param is not taken to be the end of its segment
         #[cfg(feature = "ssr")]
         {
-            Self::unescape(s)
+            const RESERVED: &[u8] = b";/?:@&=+$,#";
+
+            let mut unescaped = String::with_capacity(s.len());
+            let mut rest = 0;
+            for (i, _) in s.match_indices('%') {
+                let reserved = s
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .is_some_and(|byte| RESERVED.contains(&byte));
+                if reserved {
+                    unescaped.push_str(&Self::unescape(&s[rest..i]));
+                    unescaped.push_str(&s[i..i + 3]);
+                    rest = i + 3;
+                }
+            }
+            unescaped.push_str(&Self::unescape(&s[rest..]));
+            unescaped
         }
     }
 }
@@ -186,8 +204,8 @@ pub struct LocationChange {
     /// If true, the new location will replace the current one in the history stack, i.e.,
     /// clicking the "back" button will not return to the current location.
     pub replace: bool,
-    /// If true, the router will scroll to the top of the page at the end of the navigation.
-    pub scroll: bool,
+    /// How the router will scroll at the end of the navigation.
+    pub scroll: ScrollBehavior,
     /// The [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that will be added during navigation.
     pub state: State,
 }
@@ -197,7 +215,7 @@ impl Default for LocationChange {
         Self {
             value: Default::default(),
             replace: true,
-            scroll: true,
+            scroll: ScrollBehavior::Top,
             state: Default::default(),
         }
     }
@@ -356,11 +374,20 @@ where
                 .and_then(|value| value.as_bool())
                 .unwrap_or(false);
 
+            let scroll = if a.has_attribute("noscroll")
+                || a.has_attribute("data-noscroll")
+            {
+                ScrollBehavior::None
+            } else {
+                ScrollBehavior::from_attribute(
+                    a.get_attribute("data-scroll").as_deref(),
+                )
+            };
+
             let change = LocationChange {
                 value: to,
                 replace,
-                scroll: !a.has_attribute("noscroll")
-                    && !a.has_attribute("data-noscroll"),
+                scroll,
                 state: State::new(state),
             };
 
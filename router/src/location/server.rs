@@ -72,4 +72,28 @@ mod tests {
         assert_eq!(url.origin(), "https://www.example.com");
         assert_eq!(url.path(), "/foo/bar");
     }
+
+    #[test]
+    #[cfg(feature = "ssr")]
+    pub fn keeps_escaped_slashes_in_params() {
+        use crate::{
+            location::Url, params::ParamsMap, typed_route::encode_param,
+            ParamSegment, PossibleRouteMatch, StaticSegment,
+        };
+
+        let path = format!("/tags/{}", encode_param("c/c++ & rust"));
+        assert_eq!(path, "/tags/c%2Fc%2B%2B%20%26%20rust");
+        let url = RequestUrl::new(&path).parse().unwrap();
+
+        // only the escapes of unreserved characters are decoded before matching
+        let path = Url::unescape_minimal(url.path());
+        assert_eq!(path, "/tags/c%2Fc%2B%2B %26 rust");
+
+        let matched = (StaticSegment("tags"), ParamSegment("tag"))
+            .test(&path)
+            .unwrap();
+        assert_eq!(matched.remaining(), "");
+        let params = matched.params().into_iter().collect::<ParamsMap>();
+        assert_eq!(params.get("tag").as_deref(), Some("c/c++ & rust"));
+    }
 }
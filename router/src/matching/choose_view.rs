@@ -1,5 +1,19 @@
+use crate::{
+    params::ParamsMap,
+    prefetch::{take_prefetched, LoadingRoute, Prefetching},
+};
 use either_of::*;
-use std::{future::Future, marker::PhantomData};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::ArcMemo,
+    owner::{provide_context, use_context},
+    traits::GetUntracked,
+};
+use std::{
+    future::Future,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 use tachys::view::any_view::{AnyView, IntoAny};
 
 pub trait ChooseView
@@ -9,6 +23,12 @@ where
     fn choose(self) -> impl Future<Output = AnyView>;
 
     fn preload(&self) -> impl Future<Output = ()>;
+
+    /// Starts the [`RouteLoader`](crate::RouteLoader) for this route, if it has one.
+    ///
+    /// This is called with the route's owner and context, before [`preload`](Self::preload), so
+    /// that the data for every matched route starts loading at the same time.
+    fn load(&self) {}
 }
 
 impl<F, View> ChooseView for F
@@ -28,11 +48,42 @@ where
     T: LazyRoute,
 {
     async fn choose(self) -> AnyView {
-        T::data().view().await.into_any()
+        let data = use_context::<LazyData<T>>()
+            .and_then(|data| data.0.lock().or_poisoned().take())
+            .unwrap_or_else(T::data);
+        data.view().await.into_any()
     }
 
     async fn preload(&self) {
-        T::data().view().await;
+        T::preload().await;
+    }
+
+    fn load(&self) {
+        let route = use_context::<LoadingRoute>();
+
+        // if the route is only being prefetched, cache its data until the user navigates to it
+        if let Some(prefetching) = use_context::<Prefetching>() {
+            let params = use_context::<ArcMemo<ParamsMap>>();
+            if let (Some(LoadingRoute(route)), Some(params)) = (route, params) {
+                prefetching.cache(route, params.get_untracked(), T::data());
+            }
+            return;
+        }
+
+        let data = route
+            .and_then(|LoadingRoute(route)| take_prefetched::<T>(route))
+            .unwrap_or_else(T::data);
+        provide_context(LazyData(Arc::new(Mutex::new(Some(data)))));
+    }
+}
+
+/// The data for a lazy route, created when the route starts loading and used when its view is
+/// created.
+struct LazyData<T>(Arc<Mutex<Option<T>>>);
+
+impl<T> Clone for LazyData<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
     }
 }
 
@@ -40,6 +91,14 @@ pub trait LazyRoute: Send + 'static {
     fn data() -> Self;
 
     fn view(self) -> impl Future<Output = AnyView>;
+
+    /// Loads the code for [`view`](Self::view), without running it.
+    ///
+    /// This is called when the route is prefetched, and before the view is created. It does
+    /// nothing by default; `#[lazy_route]` implements it to load the code split out for the view.
+    fn preload() -> impl Future<Output = ()> {
+        async {}
+    }
 }
 
 #[derive(Debug)]
@@ -93,6 +152,13 @@ where
             Either::Right(f) => f.preload().await,
         }
     }
+
+    fn load(&self) {
+        match self {
+            Either::Left(f) => f.load(),
+            Either::Right(f) => f.load(),
+        }
+    }
 }
 
 macro_rules! tuples {
@@ -112,6 +178,12 @@ macro_rules! tuples {
                     $($either::$ty(f) => f.preload().await,)*
                 }
             }
+
+            fn load(&self) {
+                match self {
+                    $($either::$ty(f) => f.load(),)*
+                }
+            }
         }
     };
 }
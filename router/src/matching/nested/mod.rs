@@ -2,14 +2,20 @@ use super::{
     MatchInterface, MatchNestedRoutes, PartialPathMatch, PathSegment,
     PossibleRouteMatch, RouteMatchId,
 };
-use crate::{ChooseView, GeneratedRouteData, MatchParams, Method, SsrMode};
+use crate::{
+    prefetch::LoadingRoute, ChooseView, GeneratedRouteData, MatchParams,
+    Method, RouteLoader, SsrMode,
+};
 use core::{fmt, iter};
 use either_of::Either;
+use reactive_graph::owner::provide_context;
 use std::{
     borrow::Cow,
     collections::HashSet,
+    future::Future,
     sync::atomic::{AtomicU16, Ordering},
 };
+use tachys::view::any_view::AnyView;
 
 mod tuples;
 
@@ -22,6 +28,7 @@ pub struct NestedRoute<Segments, Children, Data, View> {
     children: Option<Children>,
     data: Data,
     view: View,
+    loader: Option<RouteLoader>,
     methods: HashSet<Method>,
     ssr_mode: SsrMode,
 }
@@ -41,6 +48,7 @@ where
             children: self.children.clone(),
             data: self.data.clone(),
             view: self.view.clone(),
+            loader: self.loader.clone(),
             methods: self.methods.clone(),
             ssr_mode: self.ssr_mode.clone(),
         }
@@ -58,6 +66,7 @@ impl<Segments, View> NestedRoute<Segments, (), (), View> {
             children: None,
             data: (),
             view,
+            loader: None,
             methods: [Method::Get].into(),
             ssr_mode: Default::default(),
         }
@@ -74,6 +83,7 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             segments,
             data,
             view,
+            loader,
             ssr_mode,
             methods,
             ..
@@ -84,6 +94,7 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
             children: Some(child),
             data,
             view,
+            loader,
             ssr_mode,
             methods,
         }
@@ -95,6 +106,16 @@ impl<Segments, Data, View> NestedRoute<Segments, (), Data, View> {
     }
 }
 
+impl<Segments, Children, Data, View>
+    NestedRoute<Segments, Children, Data, View>
+{
+    /// Sets the [`RouteLoader`] that loads the data for this route.
+    pub fn loader(mut self, loader: impl Into<RouteLoader>) -> Self {
+        self.loader = Some(loader.into());
+        self
+    }
+}
+
 #[derive(PartialEq, Eq)]
 pub struct NestedMatch<Child, View> {
     id: RouteMatchId,
@@ -105,6 +126,8 @@ pub struct NestedMatch<Child, View> {
     /// The nested route.
     child: Option<Child>,
     view_fn: View,
+    /// The loader for this nested route.
+    loader: Option<RouteLoader>,
 }
 
 impl<Child, View> fmt::Debug for NestedMatch<Child, View>
@@ -143,7 +166,41 @@ where
     }
 
     fn into_view_and_child(self) -> (impl ChooseView, Option<Self::Child>) {
-        (self.view_fn, self.child)
+        let view = RouteView {
+            id: self.id,
+            view: self.view_fn,
+            loader: self.loader,
+        };
+        (view, self.child)
+    }
+}
+
+/// The view for a matched route, along with its loader.
+#[derive(Clone)]
+struct RouteView<View> {
+    id: RouteMatchId,
+    view: View,
+    loader: Option<RouteLoader>,
+}
+
+impl<View> ChooseView for RouteView<View>
+where
+    View: ChooseView,
+{
+    fn choose(self) -> impl Future<Output = AnyView> {
+        self.view.choose()
+    }
+
+    fn preload(&self) -> impl Future<Output = ()> {
+        self.view.preload()
+    }
+
+    fn load(&self) {
+        if let Some(loader) = &self.loader {
+            loader.load(self.id);
+        }
+        provide_context(LoadingRoute(self.id));
+        self.view.load();
     }
 }
 
@@ -199,6 +256,7 @@ where
                                     params,
                                     child: inner,
                                     view_fn: self.view.clone(),
+                                    loader: self.loader.clone(),
                                 },
                             )),
                             remaining,
@@ -8,9 +8,12 @@ pub struct NavigateOptions {
     /// If `true` the new location will replace the current route in the history stack, meaning
     /// the "back" button will skip over the current route. (Defaults to `false`).
     pub replace: bool,
-    /// If `true`, the router will scroll to the top of the window at the end of navigation.
-    /// Defaults to `true`.
-    pub scroll: bool,
+    /// How the router should scroll at the end of navigation. Defaults to
+    /// [`ScrollBehavior::Top`].
+    ///
+    /// A `bool` can be converted into this: `true` scrolls to the top and `false` leaves the
+    /// scroll position unchanged.
+    pub scroll: ScrollBehavior,
     /// [State](https://developer.mozilla.org/en-US/docs/Web/API/History/state) that should be pushed
     /// onto the history stack during navigation.
     pub state: State,
@@ -21,8 +24,61 @@ impl Default for NavigateOptions {
         Self {
             resolve: true,
             replace: false,
-            scroll: true,
+            scroll: ScrollBehavior::Top,
             state: State::new(None),
         }
     }
 }
+
+/// How the router should scroll at the end of a navigation.
+///
+/// Whichever behavior is chosen, if the new URL has a hash that matches the `id` of an element,
+/// the router scrolls that element into view instead.
+///
+/// The router saves the scroll position of the window before each navigation, along with the
+/// positions of any elements marked with a `data-scroll-restore="name"` attribute. When the user
+/// navigates back or forward to a history entry, its saved position is restored once the routes
+/// have finished loading, unless the entry was navigated to with [`ScrollBehavior::None`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ScrollBehavior {
+    /// Restores the position that was last saved for the new URL, or scrolls to the top of the
+    /// window if there is none.
+    Restore,
+    /// Scrolls to the top of the window.
+    #[default]
+    Top,
+    /// Leaves the scroll position unchanged, including when the user navigates back or forward
+    /// to this history entry later.
+    None,
+}
+
+impl ScrollBehavior {
+    /// The value of the `data-scroll` attribute that sets this behavior on an `<a>` element,
+    /// if it is not the default.
+    pub(crate) fn as_attribute(&self) -> Option<&'static str> {
+        match self {
+            ScrollBehavior::Restore => Some("restore"),
+            ScrollBehavior::Top => None,
+            ScrollBehavior::None => Some("none"),
+        }
+    }
+
+    /// Reads a behavior back from the value returned by [`ScrollBehavior::as_attribute`].
+    pub(crate) fn from_attribute(value: Option<&str>) -> Self {
+        match value {
+            Some("restore") => ScrollBehavior::Restore,
+            Some("none") => ScrollBehavior::None,
+            _ => ScrollBehavior::Top,
+        }
+    }
+}
+
+impl From<bool> for ScrollBehavior {
+    fn from(scroll: bool) -> Self {
+        if scroll {
+            ScrollBehavior::Top
+        } else {
+            ScrollBehavior::None
+        }
+    }
+}
@@ -629,6 +629,7 @@ where
                     provide_context(params_including_parents);
                     provide_context(url);
                     provide_context(matched);
+                    view.load();
                     view.preload().await;
                     *view_fn.lock().or_poisoned() = Box::new(move || {
                         let view = view.clone();
@@ -773,6 +774,7 @@ where
                                 provide_context(params_including_parents);
                                 provide_context(url);
                                 provide_context(matched);
+                                view.load();
                                 view.preload().await;
                                 *view_fn.lock().or_poisoned() =
                                     Box::new(move || {
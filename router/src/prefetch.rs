@@ -0,0 +1,580 @@
+use crate::{
+    components::RouterContext, hooks::Matched, location::Url,
+    matching::RouteDefs, params::ParamsMap, ChooseView, MatchInterface,
+    MatchNestedRoutes, MatchParams, RouteMatchId,
+};
+use any_spawner::Executor;
+use futures::{channel::oneshot, future::join_all};
+use js_sys::Array;
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    computed::{ArcMemo, ScopedFuture},
+    owner::{on_cleanup, provide_context, use_context, ArcStoredValue, Owner},
+    signal::ArcRwSignal,
+    traits::{GetUntracked, ReadValue, WriteValue},
+};
+use send_wrapper::SendWrapper;
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    future::Future,
+    mem,
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex, Weak},
+};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{Element, IntersectionObserver, IntersectionObserverEntry};
+
+/// When an [`A`](crate::components::A) link should load the route it links to, before it has
+/// been clicked.
+///
+/// Prefetching a route matches the link's URL against the route definitions, and preloads each
+/// of the matched routes with the params of that URL. This starts the
+/// [`RouteLoader`](crate::RouteLoader) of each route, and the [`data`](crate::LazyRoute::data)
+/// and [`preload`](crate::LazyRoute::preload) of each lazy route, without rendering any views.
+/// The data is cached, and used when the user navigates to that URL. Each URL is only prefetched
+/// once for each page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Prefetch {
+    /// The route is not prefetched.
+    #[default]
+    None,
+    /// The route is prefetched as soon as the pointer enters the link.
+    Hover,
+    /// The route is prefetched when the pointer rests on the link for a moment, or when the link
+    /// is focused or touched.
+    Intent,
+    /// The route is prefetched when the link scrolls into the viewport.
+    Viewport,
+    /// The route is prefetched as soon as the link is rendered.
+    Render,
+}
+
+type PrefetchFn = SendWrapper<Rc<dyn Fn(Url)>>;
+
+type Loader = Pin<Box<dyn Future<Output = ()>>>;
+
+type PrefetchCache = HashMap<(RouteMatchId, TypeId, ParamsMap), Prefetched>;
+
+/// The owners of a prefetched route and of its parents.
+type Owners = Arc<Mutex<Vec<Owner>>>;
+
+/// Data loaded for a route while it was prefetched.
+struct Prefetched {
+    data: Box<dyn Any + Send>,
+    /// Anything created while loading the data belongs to these owners, so they are kept alive
+    /// along with it.
+    owners: Owners,
+}
+
+/// Provided as context to routes that are being prefetched, rather than rendered.
+///
+/// This is held by the owners of the prefetched routes, so it only holds them and the cache
+/// weakly.
+#[derive(Clone)]
+pub(crate) struct Prefetching {
+    cache: Weak<Mutex<PrefetchCache>>,
+    owners: Weak<Mutex<Vec<Owner>>>,
+}
+
+impl Prefetching {
+    /// Caches data loaded for a route while it is prefetched, until the user navigates to it.
+    pub fn cache<T>(&self, route: RouteMatchId, params: ParamsMap, data: T)
+    where
+        T: Send + 'static,
+    {
+        let (Some(cache), Some(owners)) =
+            (self.cache.upgrade(), self.owners.upgrade())
+        else {
+            return;
+        };
+        cache.lock().or_poisoned().insert(
+            (route, TypeId::of::<T>(), params),
+            Prefetched {
+                data: Box::new(data),
+                owners,
+            },
+        );
+    }
+
+    /// Starts loading data for a route, and caches the load while it is still in flight, so
+    /// that a route the user navigates to before its data has loaded waits for it, rather than
+    /// loading it again.
+    ///
+    /// The owners of the route are kept alive until the data has loaded.
+    pub fn cache_loading<T>(
+        &self,
+        route: RouteMatchId,
+        params: ParamsMap,
+        data: impl Future<Output = T> + 'static,
+    ) where
+        T: Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel::<T>();
+        self.cache(route, params, rx);
+        let owners = self.owners.upgrade();
+        Executor::spawn_local(async move {
+            _ = tx.send(data.await);
+            drop(owners);
+        });
+    }
+}
+
+/// The route that is being loaded, provided as context while its data loads.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LoadingRoute(pub RouteMatchId);
+
+/// Prefetches routes for the router.
+///
+/// The routes are only known once they are registered by `<Routes/>` or `<FlatRoutes/>`.
+/// Data loaded while prefetching from one page can only be used by the routes of the next page
+/// the user navigates to, and is dropped once the user navigates again.
+#[derive(Clone, Default)]
+pub(crate) struct Prefetcher {
+    prefetch: ArcStoredValue<Option<PrefetchFn>>,
+    /// The path of the page that the URLs in `prefetched` were prefetched from.
+    path: ArcStoredValue<String>,
+    prefetched: ArcStoredValue<HashSet<String>>,
+    /// Data prefetched from the current page.
+    cache: Arc<Mutex<PrefetchCache>>,
+    /// Data prefetched from the previous page, which the routes of the current page can use.
+    arrived: Arc<Mutex<PrefetchCache>>,
+}
+
+impl Prefetcher {
+    pub fn register<Defs>(&self, routes: RouteDefs<Defs>, owner: Owner)
+    where
+        Defs: MatchNestedRoutes + 'static,
+    {
+        // the prefetcher holds this function, so it only holds the cache weakly
+        let cache = Arc::downgrade(&self.cache);
+        let prefetch =
+            move |url: Url| prefetch_route(&routes, url, &owner, &cache);
+        *self.prefetch.write_value() =
+            Some(SendWrapper::new(Rc::new(prefetch)));
+    }
+
+    /// Called whenever the router matches its routes against the current path.
+    ///
+    /// If the path has changed, the data prefetched from the previous page becomes available to
+    /// the routes that are about to load, and anything prefetched before that is dropped, as it
+    /// may be out of date.
+    pub fn navigated(&self, path: &str) {
+        if *self.path.read_value() == path {
+            return;
+        }
+        *self.path.write_value() = path.to_string();
+        self.prefetched.write_value().clear();
+        let cache = mem::take(&mut *self.cache.lock().or_poisoned());
+        *self.arrived.lock().or_poisoned() = cache;
+    }
+
+    pub fn prefetch(&self, url: Url) {
+        let key = url.to_full_path();
+        if !self.prefetched.write_value().insert(key) {
+            return;
+        }
+        let prefetch =
+            self.prefetch.read_value().as_ref().map(|f| Rc::clone(f));
+        if let Some(prefetch) = prefetch {
+            prefetch(url);
+        }
+    }
+
+    /// Takes the data of type `T` that was prefetched for a route with these params, if any.
+    ///
+    /// The owners that the data was loaded under are kept alive until the current owner is
+    /// cleaned up.
+    pub fn take<T>(&self, route: RouteMatchId, params: &ParamsMap) -> Option<T>
+    where
+        T: 'static,
+    {
+        let key = (route, TypeId::of::<T>(), params.clone());
+        let Prefetched { data, owners } =
+            self.arrived.lock().or_poisoned().remove(&key)?;
+        on_cleanup(move || drop(owners));
+        data.downcast::<T>().ok().map(|data| *data)
+    }
+}
+
+/// Takes the data of type `T` that was prefetched for a route with the current params, if any.
+pub(crate) fn take_prefetched<T>(route: RouteMatchId) -> Option<T>
+where
+    T: 'static,
+{
+    let params = use_context::<ArcMemo<ParamsMap>>()?.get_untracked();
+    let router = use_context::<RouterContext>()?;
+    router.prefetcher.take(route, &params)
+}
+
+fn prefetch_route<Defs>(
+    routes: &RouteDefs<Defs>,
+    url: Url,
+    parent: &Owner,
+    cache: &Weak<Mutex<PrefetchCache>>,
+) where
+    Defs: MatchNestedRoutes,
+{
+    let Some(matched) = routes.match_route(url.path()) else {
+        return;
+    };
+    let owners = Owners::default();
+    let prefetching = Prefetching {
+        cache: Weak::clone(cache),
+        owners: Arc::downgrade(&owners),
+    };
+    let mut loaders = Vec::new();
+    preload_match(
+        matched,
+        &url,
+        &mut Vec::new(),
+        &mut String::new(),
+        parent,
+        &prefetching,
+        &mut loaders,
+    );
+    Executor::spawn_local(async move {
+        join_all(loaders).await;
+        // the owners of nested routes look up context through their parents, so keep all of
+        // them alive until every route has loaded, and after that for as long as any of the
+        // data they loaded is cached
+        drop(owners);
+    });
+}
+
+/// Preloads a matched route with the same context it will have once it is rendered, and then
+/// recursively preloads its child.
+fn preload_match<M>(
+    matched: M,
+    url: &Url,
+    params: &mut Vec<(Cow<'static, str>, String)>,
+    matched_path: &mut String,
+    parent: &Owner,
+    prefetching: &Prefetching,
+    loaders: &mut Vec<Loader>,
+) where
+    M: MatchInterface + MatchParams,
+{
+    params.extend(matched.to_params());
+    matched_path.push_str(matched.as_matched());
+
+    let owner = parent.child();
+    let params_map = params.iter().cloned().collect::<ParamsMap>();
+    let route_params = ArcMemo::new(move |_| params_map.clone());
+    let route_url = ArcRwSignal::new(url.clone());
+    let route_matched = {
+        let matched_path = matched_path.clone();
+        Matched(ArcMemo::new(move |_| matched_path.clone()))
+    };
+
+    let (view, child) = matched.into_view_and_child();
+    loaders.push(Box::pin(owner.with(|| {
+        let prefetching = prefetching.clone();
+        ScopedFuture::new(async move {
+            provide_context(route_params);
+            provide_context(route_url);
+            provide_context(route_matched);
+            provide_context(prefetching);
+            // only the data and code for the route are loaded: its view is not created until
+            // the user navigates to it
+            view.load();
+            view.preload().await;
+        })
+    })));
+    if let Some(owners) = prefetching.owners.upgrade() {
+        owners.lock().or_poisoned().push(owner.clone());
+    }
+
+    if let Some(child) = child {
+        preload_match(
+            child,
+            url,
+            params,
+            matched_path,
+            &owner,
+            prefetching,
+            loaders,
+        );
+    }
+}
+
+/// Calls `prefetch` the first time that the element scrolls into the viewport.
+pub(crate) fn prefetch_in_viewport(
+    el: &Element,
+    prefetch: impl Fn() + 'static,
+) {
+    let callback = Closure::<dyn Fn(Array, IntersectionObserver)>::new(
+        move |entries: Array, observer: IntersectionObserver| {
+            let visible = entries.iter().any(|entry| {
+                entry
+                    .unchecked_into::<IntersectionObserverEntry>()
+                    .is_intersecting()
+            });
+            if visible {
+                observer.disconnect();
+                prefetch();
+            }
+        },
+    )
+    .into_js_value();
+    if let Ok(observer) =
+        IntersectionObserver::new(callback.as_ref().unchecked_ref())
+    {
+        observer.observe(el);
+        let observer = SendWrapper::new(observer);
+        on_cleanup(move || observer.disconnect());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prefetcher;
+    use crate::{
+        components::RouterContext,
+        loader::LoaderData,
+        location::{Location, RequestUrl, State},
+        matching::RouteDefs,
+        params::ParamsMap,
+        ChooseView, Lazy, LazyRoute, MatchInterface, MatchNestedRoutes,
+        MatchParams, NestedRoute, StaticSegment,
+    };
+    use any_spawner::Executor;
+    use reactive_graph::{
+        computed::{ArcMemo, ScopedFuture},
+        owner::{provide_context, use_context, Owner},
+        signal::ArcRwSignal,
+    };
+    use std::{
+        future::Future,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use tachys::view::any_view::{AnyView, IntoAny};
+    use tokio::task::LocalSet;
+
+    /// Runs a test with a router that uses `prefetcher`, inside a local task set.
+    async fn with_router<Fut>(test: impl FnOnce(Prefetcher, Owner) -> Fut)
+    where
+        Fut: Future<Output = ()>,
+    {
+        _ = Executor::init_tokio();
+        LocalSet::new()
+            .run_until(async {
+                let owner = Owner::new();
+                owner.set();
+                let prefetcher = Prefetcher::default();
+                let current_url =
+                    ArcRwSignal::new(RequestUrl::new("/").parse().unwrap());
+                let state = ArcRwSignal::new(State::new(None));
+                provide_context(RouterContext {
+                    base: None,
+                    location: Location::new(
+                        current_url.read_only(),
+                        state.read_only(),
+                    ),
+                    current_url,
+                    state,
+                    set_is_routing: None,
+                    query_mutations: Default::default(),
+                    location_provider: None,
+                    prefetcher: prefetcher.clone(),
+                });
+                prefetcher.navigated("/");
+                test(prefetcher, owner).await;
+            })
+            .await;
+    }
+
+    /// Lets any spawned tasks run to completion.
+    async fn settle() {
+        for _ in 0..10 {
+            tokio::task::yield_now().await;
+        }
+    }
+
+    /// Loads the route matched by `path` and creates its view, as the router does once the user
+    /// has navigated to it, and returns the owner of the route.
+    async fn navigate<Defs>(routes: &RouteDefs<Defs>, path: &str) -> Owner
+    where
+        Defs: MatchNestedRoutes,
+    {
+        use_context::<RouterContext>()
+            .unwrap()
+            .prefetcher
+            .navigated(path);
+        let matched = routes.match_route(path).unwrap();
+        let params = matched.to_params().into_iter().collect::<ParamsMap>();
+        let (view, _) = matched.into_view_and_child();
+        let owner = Owner::current().unwrap().child();
+        owner
+            .with(|| {
+                ScopedFuture::new(async move {
+                    provide_context(ArcMemo::new(move |_| params.clone()));
+                    view.load();
+                    view.preload().await;
+                    view.choose().await;
+                })
+            })
+            .await;
+        owner
+    }
+
+    static CONTACT_DATA: AtomicUsize = AtomicUsize::new(0);
+    static CONTACT_VIEWS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Contact;
+
+    impl LazyRoute for Contact {
+        fn data() -> Self {
+            CONTACT_DATA.fetch_add(1, Ordering::Relaxed);
+            Contact
+        }
+
+        async fn view(self) -> AnyView {
+            CONTACT_VIEWS.fetch_add(1, Ordering::Relaxed);
+            ().into_any()
+        }
+    }
+
+    #[tokio::test]
+    async fn prefetching_a_lazy_route_caches_its_data_but_not_its_view() {
+        with_router(|prefetcher, owner| async move {
+            let routes = RouteDefs::new((
+                NestedRoute::new(
+                    (StaticSegment("contacts"), StaticSegment("alice")),
+                    Lazy::<Contact>::new(),
+                ),
+                NestedRoute::new(
+                    (StaticSegment("contacts"), StaticSegment("bob")),
+                    Lazy::<Contact>::new(),
+                ),
+            ));
+            prefetcher.register(routes.clone(), owner);
+            let runs = || {
+                (
+                    CONTACT_DATA.load(Ordering::Relaxed),
+                    CONTACT_VIEWS.load(Ordering::Relaxed),
+                )
+            };
+
+            prefetcher
+                .prefetch(RequestUrl::new("/contacts/alice").parse().unwrap());
+            settle().await;
+            assert_eq!(runs(), (1, 0));
+
+            // navigating uses the prefetched data
+            _ = navigate(&routes, "/contacts/alice").await;
+            assert_eq!(runs(), (1, 1));
+
+            // but only for the route it was prefetched for
+            _ = navigate(&routes, "/contacts/bob").await;
+            assert_eq!(runs(), (2, 2));
+
+            // and only once
+            _ = navigate(&routes, "/contacts/alice").await;
+            assert_eq!(runs(), (3, 3));
+        })
+        .await;
+    }
+
+    static REPORT_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+    #[tokio::test]
+    async fn prefetching_a_route_caches_the_data_of_its_loader() {
+        with_router(|prefetcher, owner| async move {
+            let routes = RouteDefs::new(
+                NestedRoute::new(StaticSegment("report"), || ()).loader(
+                    |_: ParamsMap| async {
+                        REPORT_LOADS.fetch_add(1, Ordering::Relaxed) + 1
+                    },
+                ),
+            );
+            prefetcher.register(routes.clone(), owner);
+
+            prefetcher.prefetch(RequestUrl::new("/report").parse().unwrap());
+            settle().await;
+            assert_eq!(REPORT_LOADS.load(Ordering::Relaxed), 1);
+
+            let route = navigate(&routes, "/report").await;
+            let data = route
+                .with(use_context::<LoaderData<usize>>)
+                .unwrap()
+                .0
+                .await;
+            assert_eq!(data, 1);
+            assert_eq!(REPORT_LOADS.load(Ordering::Relaxed), 1);
+        })
+        .await;
+    }
+
+    static INVOICE_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+    #[tokio::test]
+    async fn navigating_while_prefetching_waits_for_the_prefetched_data() {
+        with_router(|prefetcher, owner| async move {
+            let routes = RouteDefs::new(
+                NestedRoute::new(StaticSegment("invoice"), || ()).loader(
+                    |_: ParamsMap| {
+                        let loads =
+                            INVOICE_LOADS.fetch_add(1, Ordering::Relaxed) + 1;
+                        async move {
+                            settle().await;
+                            loads
+                        }
+                    },
+                ),
+            );
+            prefetcher.register(routes.clone(), owner);
+
+            prefetcher.prefetch(RequestUrl::new("/invoice").parse().unwrap());
+            tokio::task::yield_now().await;
+            assert_eq!(INVOICE_LOADS.load(Ordering::Relaxed), 1);
+
+            // the user clicks the link before the prefetched data has loaded
+            let route = navigate(&routes, "/invoice").await;
+            let data = route
+                .with(use_context::<LoaderData<usize>>)
+                .unwrap()
+                .0
+                .await;
+            assert_eq!(data, 1);
+            assert_eq!(INVOICE_LOADS.load(Ordering::Relaxed), 1);
+        })
+        .await;
+    }
+
+    static ORDER_LOADS: AtomicUsize = AtomicUsize::new(0);
+
+    #[tokio::test]
+    async fn navigating_elsewhere_drops_the_prefetched_data() {
+        with_router(|prefetcher, owner| async move {
+            let routes = RouteDefs::new((
+                NestedRoute::new(StaticSegment("order"), || ()).loader(
+                    |_: ParamsMap| async {
+                        ORDER_LOADS.fetch_add(1, Ordering::Relaxed) + 1
+                    },
+                ),
+                NestedRoute::new(StaticSegment("home"), || ()),
+            ));
+            prefetcher.register(routes.clone(), owner);
+
+            prefetcher.prefetch(RequestUrl::new("/order").parse().unwrap());
+            settle().await;
+            assert_eq!(ORDER_LOADS.load(Ordering::Relaxed), 1);
+
+            // the data was prefetched from `/`, so it is out of date once the user has
+            // navigated somewhere else
+            _ = navigate(&routes, "/home").await;
+            let route = navigate(&routes, "/order").await;
+            let data = route
+                .with(use_context::<LoaderData<usize>>)
+                .unwrap()
+                .0
+                .await;
+            assert_eq!(data, 2);
+            assert_eq!(ORDER_LOADS.load(Ordering::Relaxed), 2);
+        })
+        .await;
+    }
+}
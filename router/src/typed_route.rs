@@ -0,0 +1,74 @@
+use crate::PossibleRouteMatch;
+
+/// A route whose path and params are declared once, on a struct with a field for each param.
+///
+/// This is usually derived with `#[derive(TypedRoute)]`, which checks at compile time that the
+/// params of the path and the fields of the struct match. The same struct can then be used both
+/// to define the route and to link to it, so that links cannot drift from the route definitions.
+///
+/// ```rust
+/// use leptos::prelude::*;
+/// use leptos_router::{
+///     components::{Route, Router, Routes, A},
+///     hooks::use_navigate,
+///     TypedRoute,
+/// };
+///
+/// #[derive(TypedRoute)]
+/// #[route(path = "/users/:id")]
+/// struct UserRoute {
+///     id: u64,
+/// }
+///
+/// #[component]
+/// fn App() -> impl IntoView {
+///     view! {
+///         <Router>
+///             <A href=UserRoute { id: 42 }>"User 42"</A>
+///             <Routes fallback=|| "Not found.">
+///                 <Route path=UserRoute::path() view=|| "User"/>
+///             </Routes>
+///         </Router>
+///     }
+/// }
+///
+/// # fn navigate() {
+/// let navigate = use_navigate();
+/// navigate(&UserRoute { id: 7 }.to_path(), Default::default());
+/// # }
+/// assert_eq!(UserRoute { id: 42 }.to_path(), "/users/42");
+/// ```
+pub trait TypedRoute {
+    /// The segments matched by the route.
+    type Path: PossibleRouteMatch;
+
+    /// Returns the segments for the route definition, as would be created by
+    /// [`path`](crate::path).
+    fn path() -> Self::Path;
+
+    /// Returns the URL of this route, with each param filled in from the struct.
+    fn to_path(&self) -> String;
+}
+
+/// Percent-encodes a param for the URL of a [`TypedRoute`], in the same way as JavaScript's
+/// `encodeURIComponent`, so that the same URL is generated on the server and in the browser.
+///
+/// A `/` in the param is encoded as `%2F`. The router does not decode the escapes of reserved
+/// characters like this one until the path has been split into segments, so the param still
+/// matches a single segment, and its value contains the `/`.
+///
+/// This is used by the code generated by `#[derive(TypedRoute)]`.
+#[doc(hidden)]
+pub fn encode_param(param: &str) -> String {
+    const UNESCAPED: &[u8] = b"-_.!~*'()";
+
+    let mut encoded = String::with_capacity(param.len());
+    for byte in param.bytes() {
+        if byte.is_ascii_alphanumeric() || UNESCAPED.contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
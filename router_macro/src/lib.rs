@@ -1,4 +1,4 @@
-//! A macro to make path definitions easier with [`leptos_router`].
+//! Macros to make path and route definitions easier with [`leptos_router`].
 //!
 //! [`leptos_router`]: https://docs.rs/leptos_router/latest/leptos_router/components/fn.Route.html
 
@@ -9,7 +9,8 @@ use proc_macro2::Span;
 use proc_macro_error2::{abort, proc_macro_error};
 use quote::{quote, ToTokens};
 use syn::{
-    spanned::Spanned, Block, Ident, ImplItem, ItemImpl, Path, Type, TypePath,
+    spanned::Spanned, Data, DeriveInput, Fields, Ident, ImplItem, ItemImpl,
+    LitStr, Path, Type, TypePath,
 };
 
 const RFC3986_UNRESERVED: [char; 4] = ['-', '.', '_', '~'];
@@ -79,20 +80,7 @@ impl SegmentParser {
             match input {
                 TokenTree::Literal(lit) => {
                     let lit = lit.to_string();
-                    if lit.contains("//") {
-                        abort!(
-                            proc_macro2::Span::call_site(),
-                            "Consecutive '/' is not allowed"
-                        );
-                    }
-                    Self::parse_str(
-                        &mut self.segments,
-                        lit.trim_start_matches(['"', '/'])
-                            .trim_end_matches(['"', '/']),
-                    );
-                    if lit.ends_with(r#"/""#) && lit != r#""/""# {
-                        self.segments.push(Segment::Static("/".to_string()));
-                    }
+                    Self::parse_path(&mut self.segments, lit.trim_matches('"'));
                 }
                 TokenTree::Group(_) => unimplemented!(),
                 TokenTree::Ident(_) => unimplemented!(),
@@ -101,6 +89,22 @@ impl SegmentParser {
         }
     }
 
+    pub fn parse_path(segments: &mut Vec<Segment>, path: &str) {
+        if path.contains("//") {
+            abort!(
+                proc_macro2::Span::call_site(),
+                "Consecutive '/' is not allowed"
+            );
+        }
+        Self::parse_str(
+            segments,
+            path.trim_start_matches('/').trim_end_matches('/'),
+        );
+        if path.ends_with('/') && path != "/" {
+            segments.push(Segment::Static("/".to_string()));
+        }
+    }
+
     pub fn parse_str(segments: &mut Vec<Segment>, current_str: &str) {
         if ["", "*"].contains(&current_str) {
             return;
@@ -198,24 +202,25 @@ impl ToTokens for Segments {
 /// add a [`lazy`] annotation to the `view` method, which will cause the code for the view
 /// to lazy-load concurrently with the `data` being loaded for the route.
 ///
+/// It also implements [`LazyRoute::preload`], which loads the code for the view without running
+/// it, so that prefetching the route fetches its code as well as its data.
+///
 /// [`impl LazyRoute`]: https://docs.rs/leptos_router/latest/leptos_router/trait.LazyRoute.html
+/// [`LazyRoute::preload`]: https://docs.rs/leptos_router/latest/leptos_router/trait.LazyRoute.html#method.preload
 /// [`lazy`]: https://docs.rs/leptos_macro/latest/leptos_macro/macro.lazy.html
 #[proc_macro_attribute]
 #[proc_macro_error]
 pub fn lazy_route(
-    args: proc_macro::TokenStream,
-    s: TokenStream,
-) -> TokenStream {
-    lazy_route_impl(args, s)
-}
-
-fn lazy_route_impl(
     _args: proc_macro::TokenStream,
     s: TokenStream,
 ) -> TokenStream {
-    let mut im = syn::parse::<ItemImpl>(s).unwrap_or_else(|e| {
+    let im = syn::parse::<ItemImpl>(s).unwrap_or_else(|e| {
         abort!(e.span(), "`lazy_route` can only be used on an `impl` block")
     });
+    lazy_route_impl(im).into()
+}
+
+fn lazy_route_impl(mut im: ItemImpl) -> proc_macro2::TokenStream {
     if im.trait_.is_none() {
         abort!(
             im.span(),
@@ -233,6 +238,16 @@ fn lazy_route_impl(
         _ => abort!(self_ty.span(), "only path types are supported"),
     };
     let lazy_view_ident = Ident::new(&ty_name_to_snake, im.self_ty.span());
+    // the split view and the function that loads its code are defined next to the `impl` block,
+    // so that `preload` can call the loader without running the view
+    let view_fn_ident = Ident::new(
+        &format!("__lazy_view_{ty_name_to_snake}"),
+        im.self_ty.span(),
+    );
+    let preload_fn_ident = Ident::new(
+        &format!("__preload_lazy_view_{ty_name_to_snake}"),
+        im.self_ty.span(),
+    );
 
     let item = im.items.iter_mut().find_map(|item| match item {
         ImplItem::Fn(inner) => {
@@ -244,28 +259,368 @@ fn lazy_route_impl(
         }
         _ => None,
     });
-    match item {
+    let body = match item {
         None => abort!(im.span(), "must contain a fn called `view`"),
         Some(fun) => {
             let body = fun.block.clone();
-            let new_block = quote! {{
-                    #[cfg_attr(feature = "split", wasm_split::wasm_split(#lazy_view_ident))]
-                    async fn view(this: #self_ty) -> ::leptos::prelude::AnyView {
-                        #body
-                    }
-
-                    view(self).await
+            fun.block = syn::parse_quote! {{
+                #view_fn_ident(self).await
             }};
-            let block =
-                syn::parse::<Block>(new_block.into()).unwrap_or_else(|e| {
-                    abort!(
-                        e.span(),
-                        "`lazy_route` can only be used on an `impl` block"
-                    )
-                });
-            fun.block = block;
+            body
+        }
+    };
+
+    if im.items.iter().any(|item| {
+        matches!(item, ImplItem::Fn(inner) if inner.sig.ident == "preload")
+    }) {
+        abort!(
+            im.span(),
+            "`lazy_route` implements `preload`, so it cannot be defined \
+             in the `impl` block"
+        )
+    }
+    im.items.push(syn::parse_quote! {
+        async fn preload() {
+            #[cfg(feature = "split")]
+            #preload_fn_ident().await;
         }
+    });
+
+    quote! {
+        #[allow(non_snake_case)]
+        #[doc(hidden)]
+        #[cfg_attr(
+            feature = "split",
+            wasm_split::wasm_split(#lazy_view_ident, preload(#preload_fn_ident))
+        )]
+        async fn #view_fn_ident(this: #self_ty) -> ::leptos::prelude::AnyView {
+            #body
+        }
+
+        #im
     }
+}
+
+/// Derives [`TypedRoute`] for a struct, from a path declared with a `#[route(path = "...")]`
+/// attribute in the same format as the [`path`](path!) macro.
+///
+/// Each param in the path is filled in from the field with the same name, which can be of any
+/// type that implements [`Display`](std::fmt::Display). The field for an optional param must be an
+/// `Option`. Every param must have a field and every field must be used by a param, so changing the
+/// path without changing the struct (or the other way around) is a compile error.
+///
+/// This implements [`ToHref`], so that the struct can be used as the `href` of an [`A`] link.
+///
+/// ```rust
+/// use leptos_router::{ParamSegment, StaticSegment, TypedRoute};
+///
+/// #[derive(TypedRoute)]
+/// #[route(path = "/users/:id/posts/:post?")]
+/// struct UserPosts {
+///     id: u64,
+///     post: Option<String>,
+/// }
+///
+/// assert_eq!(
+///     UserPosts::path(),
+///     (
+///         StaticSegment("users"),
+///         ParamSegment("id"),
+///         StaticSegment("posts"),
+///         leptos_router::OptionalParamSegment("post"),
+///     )
+/// );
+/// assert_eq!(UserPosts { id: 42, post: None }.to_path(), "/users/42/posts");
+/// ```
+///
+/// [`TypedRoute`]: https://docs.rs/leptos_router/latest/leptos_router/trait.TypedRoute.html
+/// [`ToHref`]: https://docs.rs/leptos_router/latest/leptos_router/components/trait.ToHref.html
+/// [`A`]: https://docs.rs/leptos_router/latest/leptos_router/components/fn.A.html
+#[proc_macro_error]
+#[proc_macro_derive(TypedRoute, attributes(route))]
+pub fn derive_typed_route(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    typed_route_impl(input).into()
+}
+
+fn typed_route_impl(input: DeriveInput) -> proc_macro2::TokenStream {
+    let path = route_path(&input);
+    let mut segments = Vec::new();
+    SegmentParser::parse_path(&mut segments, &path.value());
+    let segments = Segments(segments);
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect(),
+            Fields::Unit => Vec::new(),
+            Fields::Unnamed(_) => abort!(
+                input.ident.span(),
+                "`TypedRoute` can only be derived for structs with named \
+                 fields"
+            ),
+        },
+        _ => abort!(
+            input.ident.span(),
+            "`TypedRoute` can only be derived for structs"
+        ),
+    };
 
-    quote! { #im }.into()
+    // every param needs a field, and every field needs a param
+    let mut used = Vec::new();
+    let mut to_path = Vec::new();
+    for segment in &segments.0 {
+        let (name, optional) = match segment {
+            Segment::Static(s) if s == "/" => {
+                to_path.push(quote! { href.push('/'); });
+                continue;
+            }
+            Segment::Static(s) => {
+                let s = format!("/{s}");
+                to_path.push(quote! { href.push_str(#s); });
+                continue;
+            }
+            Segment::Param(name) | Segment::Wildcard(name) => (name, false),
+            Segment::OptionalParam(name) => (name, true),
+        };
+        if name.is_empty() {
+            abort!(
+                path.span(),
+                "wildcard segments in a typed route must be named, like \
+                 `*rest`"
+            );
+        }
+        let Some(field) = fields
+            .iter()
+            .find(|field| field.ident.as_ref().is_some_and(|id| id == name))
+        else {
+            abort!(path.span(), "no field for the param `{}`", name);
+        };
+        if optional != is_option(&field.ty) {
+            if optional {
+                abort!(
+                    field.ty.span(),
+                    "the field for the optional param `{}` must be an \
+                     `Option`",
+                    name
+                );
+            } else {
+                abort!(
+                    field.ty.span(),
+                    "the field for the param `{}` cannot be an `Option`; use \
+                     `:{}?` for an optional param",
+                    name,
+                    name
+                );
+            }
+        }
+        used.push(name.as_str());
+
+        let ident = &field.ident;
+        to_path.push(match segment {
+            Segment::OptionalParam(_) => quote! {
+                if let ::core::option::Option::Some(value) = &self.#ident {
+                    href.push('/');
+                    href.push_str(&::leptos_router::__encode_param(
+                        &::std::string::ToString::to_string(value),
+                    ));
+                }
+            },
+            // a wildcard can match several segments, so only the segments are escaped
+            Segment::Wildcard(_) => quote! {
+                let value = ::std::string::ToString::to_string(&self.#ident);
+                for segment in value.split('/').filter(|s| !s.is_empty()) {
+                    href.push('/');
+                    href.push_str(&::leptos_router::__encode_param(
+                        segment,
+                    ));
+                }
+            },
+            _ => quote! {
+                href.push('/');
+                href.push_str(&::leptos_router::__encode_param(
+                    &::std::string::ToString::to_string(&self.#ident),
+                ));
+            },
+        });
+    }
+    for field in &fields {
+        if let Some(ident) = &field.ident {
+            if !used.contains(&ident.to_string().as_str()) {
+                abort!(
+                    ident.span(),
+                    "the field `{}` is not a param in the route's path",
+                    ident
+                );
+            }
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    let path_ty = segments.to_type_tokens();
+    // an empty body already returns `()`
+    let path_body = if segments.0.is_empty() {
+        quote! {}
+    } else {
+        quote! { #segments }
+    };
+
+    quote! {
+        impl #impl_generics ::leptos_router::TypedRoute for #name #ty_generics
+            #where_clause
+        {
+            type Path = #path_ty;
+
+            fn path() -> Self::Path {
+                #path_body
+            }
+
+            fn to_path(&self) -> ::std::string::String {
+                let mut href = ::std::string::String::new();
+                #(#to_path)*
+                if href.is_empty() {
+                    href.push('/');
+                }
+                href
+            }
+        }
+
+        impl #impl_generics ::leptos_router::components::ToHref for #name #ty_generics
+            #where_clause
+        {
+            fn to_href(
+                &self,
+            ) -> ::std::boxed::Box<dyn Fn() -> ::std::string::String + '_> {
+                let href = ::leptos_router::TypedRoute::to_path(self);
+                ::std::boxed::Box::new(move || href.clone())
+            }
+        }
+    }
+}
+
+/// Reads the path from the `#[route(path = "...")]` attribute.
+fn route_path(input: &DeriveInput) -> LitStr {
+    let mut path = None;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("route"))
+    {
+        let parsed = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("path") {
+                path = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `path = \"...\"`"))
+            }
+        });
+        if let Err(e) = parsed {
+            abort!(e.span(), "{}", e);
+        }
+    }
+    path.unwrap_or_else(|| {
+        abort!(
+            input.ident.span(),
+            "`TypedRoute` needs a path, like `#[route(path = \"/users/:id\")]`"
+        )
+    })
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { qself: None, path }) => path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+impl Segment {
+    fn to_type_tokens(&self) -> proc_macro2::TokenStream {
+        match self {
+            Segment::Static(_) => {
+                quote! { ::leptos_router::StaticSegment<&'static str> }
+            }
+            Segment::Param(_) => quote! { ::leptos_router::ParamSegment },
+            Segment::OptionalParam(_) => {
+                quote! { ::leptos_router::OptionalParamSegment }
+            }
+            Segment::Wildcard(_) => quote! { ::leptos_router::WildcardSegment },
+        }
+    }
+}
+
+impl Segments {
+    fn to_type_tokens(&self) -> proc_macro2::TokenStream {
+        let types = self.0.iter().map(Segment::to_type_tokens);
+        match self.0.as_slice() {
+            [] => quote! { () },
+            [_] => quote! { (#(#types,)*) },
+            _ => quote! { (#(#types),*) },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lazy_route_impl;
+    use quote::{quote, ToTokens};
+    use syn::{ImplItem, Item};
+
+    #[test]
+    fn lazy_route_preloads_the_split_view() {
+        let expanded = lazy_route_impl(syn::parse_quote! {
+            impl LazyRoute for Post {
+                fn data() -> Self {
+                    Post
+                }
+
+                async fn view(self) -> AnyView {
+                    ().into_any()
+                }
+            }
+        });
+        let file = syn::parse2::<syn::File>(expanded).unwrap();
+        let [Item::Fn(view), Item::Impl(im)] = file.items.as_slice() else {
+            panic!("expected the split view and the `impl` block");
+        };
+
+        // the view is split into its own chunk, along with a function that only loads it
+        let split = view
+            .attrs
+            .iter()
+            .map(|attr| attr.meta.to_token_stream().to_string())
+            .find(|attr| attr.contains("wasm_split"))
+            .unwrap();
+        assert_eq!(
+            split,
+            quote! {
+                cfg_attr(
+                    feature = "split",
+                    wasm_split::wasm_split(Post, preload(__preload_lazy_view_Post))
+                )
+            }
+            .to_string()
+        );
+
+        // and preloading the route loads the chunk without running the view
+        let preload = im
+            .items
+            .iter()
+            .find_map(|item| match item {
+                ImplItem::Fn(fun) if fun.sig.ident == "preload" => Some(fun),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(
+            preload.block.to_token_stream().to_string(),
+            quote! {{
+                #[cfg(feature = "split")]
+                __preload_lazy_view_Post().await;
+            }}
+            .to_string()
+        );
+    }
 }
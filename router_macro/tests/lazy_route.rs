@@ -0,0 +1,34 @@
+// the `split` feature that `#[lazy_route]` checks for is only defined by apps
+#![allow(unexpected_cfgs)]
+
+use leptos::prelude::{AnyView, IntoAny};
+use leptos_router::LazyRoute;
+use leptos_router_macro::lazy_route;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static VIEWS: AtomicUsize = AtomicUsize::new(0);
+
+struct Post;
+
+#[lazy_route]
+impl LazyRoute for Post {
+    fn data() -> Self {
+        Post
+    }
+
+    async fn view(self) -> AnyView {
+        VIEWS.fetch_add(1, Ordering::Relaxed);
+        ().into_any()
+    }
+}
+
+#[test]
+fn preloading_a_lazy_route_does_not_run_its_view() {
+    futures::executor::block_on(async {
+        Post::preload().await;
+        assert_eq!(VIEWS.load(Ordering::Relaxed), 0);
+
+        Post::data().view().await;
+        assert_eq!(VIEWS.load(Ordering::Relaxed), 1);
+    });
+}
@@ -0,0 +1,104 @@
+use leptos_router::{
+    components::ToHref, OptionalParamSegment, ParamSegment, PossibleRouteMatch,
+    StaticSegment, TypedRoute, WildcardSegment,
+};
+
+#[derive(TypedRoute)]
+#[route(path = "/")]
+struct Home;
+
+#[derive(TypedRoute)]
+#[route(path = "/users/:id")]
+struct User {
+    id: u64,
+}
+
+#[derive(TypedRoute)]
+#[route(path = "/users/:id/posts/:post?")]
+struct UserPost {
+    id: u64,
+    post: Option<String>,
+}
+
+#[derive(TypedRoute)]
+#[route(path = "/tags/:tag")]
+struct Tag {
+    tag: String,
+}
+
+#[derive(TypedRoute)]
+#[route(path = "/files/*path")]
+struct File {
+    path: String,
+}
+
+#[derive(TypedRoute)]
+#[route(path = "/about/")]
+struct About {}
+
+#[test]
+fn derives_path_segments() {
+    assert_eq!(Home::path(), ());
+    assert_eq!(User::path(), (StaticSegment("users"), ParamSegment("id")));
+    assert_eq!(
+        UserPost::path(),
+        (
+            StaticSegment("users"),
+            ParamSegment("id"),
+            StaticSegment("posts"),
+            OptionalParamSegment("post")
+        )
+    );
+    assert_eq!(
+        File::path(),
+        (StaticSegment("files"), WildcardSegment("path"))
+    );
+    assert_eq!(About::path(), (StaticSegment("about"), StaticSegment("/")));
+}
+
+#[test]
+fn fills_in_params() {
+    assert_eq!(Home.to_path(), "/");
+    assert_eq!(User { id: 42 }.to_path(), "/users/42");
+    assert_eq!(
+        UserPost {
+            id: 42,
+            post: Some("hello world".to_string())
+        }
+        .to_path(),
+        "/users/42/posts/hello%20world"
+    );
+    assert_eq!(UserPost { id: 42, post: None }.to_path(), "/users/42/posts");
+    assert_eq!(
+        File {
+            path: "docs/readme.md".to_string()
+        }
+        .to_path(),
+        "/files/docs/readme.md"
+    );
+    assert_eq!(About {}.to_path(), "/about/");
+}
+
+#[test]
+fn href_matches_route() {
+    let user = User { id: 42 };
+    assert_eq!(user.to_href()(), "/users/42");
+
+    let path = user.to_path();
+    let matched = User::path().test(&path).unwrap();
+    assert_eq!(matched.remaining(), "");
+    assert_eq!(matched.params()[0].1, "42");
+}
+
+#[test]
+fn params_with_slashes_match_a_single_segment() {
+    let tag = Tag {
+        tag: "c/c++".to_string(),
+    };
+    let path = tag.to_path();
+    assert_eq!(path, "/tags/c%2Fc%2B%2B");
+
+    let matched = Tag::path().test(&path).unwrap();
+    assert_eq!(matched.remaining(), "");
+    assert_eq!(matched.params()[0].1, "c%2Fc%2B%2B");
+}
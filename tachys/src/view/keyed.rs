@@ -13,8 +13,54 @@ use indexmap::IndexSet;
 use rustc_hash::FxHasher;
 use std::hash::{BuildHasherDefault, Hash};
 
+#[cfg(feature = "reactive_graph")]
+mod diffs;
+#[cfg(feature = "reactive_graph")]
+pub use diffs::*;
+
 type FxIndexSet<T> = IndexSet<T, BuildHasherDefault<FxHasher>>;
 
+/// The items rendered by a keyed list.
+///
+/// This is implemented for every [`IntoIterator`]: whenever the list is rebuilt, each item is
+/// keyed and the new keys are compared with the previous ones. With the `reactive_graph` feature,
+/// it is also implemented for [`VecChanges`], which records exactly how an
+/// [`ArcSignalVec`](reactive_graph::collections::ArcSignalVec) has changed, so that those changes
+/// can be applied without keying and comparing the whole list.
+pub trait KeyedItems: Sized {
+    /// The type of each item.
+    type Item;
+    /// An iterator over all of the items.
+    type IntoIter: Iterator<Item = Self::Item>;
+
+    /// Returns all of the items.
+    fn into_items(self) -> Self::IntoIter;
+
+    /// Returns the changes made to the items since the list was last rendered, if they are
+    /// known. Otherwise, returns all of the items.
+    #[cfg(feature = "reactive_graph")]
+    fn into_diffs(
+        self,
+    ) -> Result<
+        Vec<reactive_graph::collections::VecDiff<Self::Item>>,
+        Self::IntoIter,
+    > {
+        Err(self.into_items())
+    }
+}
+
+impl<I> KeyedItems for I
+where
+    I: IntoIterator,
+{
+    type Item = I::Item;
+    type IntoIter = I::IntoIter;
+
+    fn into_items(self) -> Self::IntoIter {
+        self.into_iter()
+    }
+}
+
 /// Creates a keyed list of views.
 pub fn keyed<T, I, K, KF, VF, VFS, V>(
     items: I,
@@ -22,7 +68,7 @@ pub fn keyed<T, I, K, KF, VF, VFS, V>(
     view_fn: VF,
 ) -> Keyed<T, I, K, KF, VF, VFS, V>
 where
-    I: IntoIterator<Item = T>,
+    I: KeyedItems<Item = T>,
     K: Eq + Hash + 'static,
     KF: Fn(&T) -> K,
     V: Render,
@@ -39,7 +85,7 @@ where
 /// A keyed list of views.
 pub struct Keyed<T, I, K, KF, VF, VFS, V>
 where
-    I: IntoIterator<Item = T>,
+    I: KeyedItems<Item = T>,
     K: Eq + Hash + 'static,
     KF: Fn(&T) -> K,
     VF: Fn(usize, T) -> (VFS, V),
@@ -65,7 +111,7 @@ where
 
 impl<T, I, K, KF, VF, VFS, V> Render for Keyed<T, I, K, KF, VF, VFS, V>
 where
-    I: IntoIterator<Item = T>,
+    I: KeyedItems<Item = T>,
     K: Eq + Hash + 'static,
     KF: Fn(&T) -> K,
     V: Render,
@@ -76,7 +122,7 @@ where
     // TODO fallible state and try_build()/try_rebuild() here
 
     fn build(self) -> Self::State {
-        let items = self.items.into_iter();
+        let items = self.items.into_items();
         let (capacity, _) = items.size_hint();
         let mut hashed_items =
             FxIndexSet::with_capacity_and_hasher(capacity, Default::default());
@@ -95,13 +141,22 @@ where
     }
 
     fn rebuild(self, state: &mut Self::State) {
+        #[cfg(feature = "reactive_graph")]
+        let new_items = match self.items.into_diffs() {
+            Ok(diffs) => {
+                state.apply_vec_diffs(diffs, &self.key_fn, &self.view_fn);
+                return;
+            }
+            Err(items) => items,
+        };
+        #[cfg(not(feature = "reactive_graph"))]
+        let new_items = self.items.into_items();
         let KeyedState {
             parent,
             marker,
             hashed_items,
             ref mut rendered_items,
         } = state;
-        let new_items = self.items.into_iter();
         let (capacity, _) = new_items.size_hint();
         let mut new_hashed_items =
             FxIndexSet::with_capacity_and_hasher(capacity, Default::default());
@@ -131,7 +186,7 @@ where
 
 impl<T, I, K, KF, VF, VFS, V> AddAnyAttr for Keyed<T, I, K, KF, VF, VFS, V>
 where
-    I: IntoIterator<Item = T> + Send,
+    I: KeyedItems<Item = T> + Send,
     K: Eq + Hash + 'static,
     KF: Fn(&T) -> K + Send,
     V: RenderHtml,
@@ -184,7 +239,7 @@ where
 
 impl<T, I, K, KF, VF, VFS, V> RenderHtml for Keyed<T, I, K, KF, VF, VFS, V>
 where
-    I: IntoIterator<Item = T> + Send,
+    I: KeyedItems<Item = T> + Send,
     K: Eq + Hash + 'static,
     KF: Fn(&T) -> K + Send,
     V: RenderHtml + 'static,
@@ -201,7 +256,7 @@ where
     }
 
     async fn resolve(self) -> Self::AsyncOutput {
-        futures::future::join_all(self.items.into_iter().enumerate().map(
+        futures::future::join_all(self.items.into_items().enumerate().map(
             |(index, item)| {
                 let (_, view) = (self.view_fn)(index, item);
                 view.resolve()
@@ -219,7 +274,7 @@ where
         escape: bool,
         mark_branches: bool,
     ) {
-        for (index, item) in self.items.into_iter().enumerate() {
+        for (index, item) in self.items.into_items().enumerate() {
             let (_, item) = (self.view_fn)(index, item);
             item.to_html_with_buf(buf, position, escape, mark_branches);
             *position = Position::NextChild;
@@ -234,7 +289,7 @@ where
         escape: bool,
         mark_branches: bool,
     ) {
-        for (index, item) in self.items.into_iter().enumerate() {
+        for (index, item) in self.items.into_items().enumerate() {
             let (_, item) = (self.view_fn)(index, item);
             item.to_html_async_with_buf::<OUT_OF_ORDER>(
                 buf,
@@ -264,7 +319,7 @@ where
             .expect("parent of keyed list should be an element");
 
         // build list
-        let items = self.items.into_iter();
+        let items = self.items.into_items();
         let (capacity, _) = items.size_hint();
         let mut hashed_items =
             FxIndexSet::with_capacity_and_hasher(capacity, Default::default());
@@ -0,0 +1,346 @@
+use super::{FxIndexSet, KeyedItems, KeyedState};
+use crate::view::{Mountable, Render};
+use or_poisoned::OrPoisoned;
+use reactive_graph::{
+    collections::{ArcSignalVec, Changes, VecDiff},
+    traits::WithUntracked,
+};
+use std::{hash::Hash, sync::Mutex};
+
+/// Returns a function that can be used for the items of a keyed list (like the `each` prop of
+/// `<For/>`), so that the changes made to `items` are applied to the list directly.
+///
+/// Normally, a keyed list keys every item and compares the new keys with the previous ones each
+/// time the list changes. Instead, each insertion, removal, or move made to `items` is applied to
+/// the rendered rows as it was made. An updated item only replaces its row if its key has changed,
+/// just as it would if the whole list had been compared.
+pub fn vec_changes<T>(
+    items: ArcSignalVec<T>,
+) -> impl Fn() -> VecChanges<T> + Send + Sync + 'static
+where
+    T: Clone + Send + Sync + 'static,
+{
+    let cursor = Mutex::new(items.cursor());
+    move || VecChanges {
+        changes: cursor.lock().or_poisoned().changes(),
+        items: items.clone(),
+    }
+}
+
+/// The changes made to an [`ArcSignalVec`] since a keyed list last rendered it.
+///
+/// See [`vec_changes`].
+pub struct VecChanges<T> {
+    changes: Changes<VecDiff<T>, Vec<T>>,
+    items: ArcSignalVec<T>,
+}
+
+impl<T> KeyedItems for VecChanges<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    // the list is always first rendered from its current value, because the cursor may already
+    // have been read (for example, while resolving the view on the server)
+    fn into_items(self) -> Self::IntoIter {
+        self.items.with_untracked(Vec::clone).into_iter()
+    }
+
+    fn into_diffs(self) -> Result<Vec<VecDiff<T>>, Self::IntoIter> {
+        match self.changes {
+            Changes::Diffs(diffs) => Ok(diffs),
+            Changes::Reset(items) => Err(items.into_iter()),
+        }
+    }
+}
+
+impl<K, VFS, V> KeyedState<K, VFS, V>
+where
+    K: Eq + Hash + 'static,
+    VFS: Fn(usize),
+    V: Render,
+{
+    pub(super) fn apply_vec_diffs<T>(
+        &mut self,
+        diffs: Vec<VecDiff<T>>,
+        key_fn: impl Fn(&T) -> K,
+        view_fn: impl Fn(usize, T) -> (VFS, V),
+    ) {
+        let mut rows = DomRows {
+            parent: self
+                .parent
+                .as_ref()
+                .expect("Keyed list rebuilt before being mounted."),
+            marker: &self.marker,
+            rendered_items: &mut self.rendered_items,
+            view_fn,
+        };
+        let first_moved =
+            apply_diffs(&mut self.hashed_items, &mut rows, diffs, key_fn);
+        if let Some(first_moved) = first_moved {
+            for (index, item) in
+                self.rendered_items.iter().enumerate().skip(first_moved)
+            {
+                if let Some((set_index, _)) = item {
+                    set_index(index);
+                }
+            }
+        }
+    }
+}
+
+/// The rows of a keyed list, which are changed to match its keys.
+trait Rows<T> {
+    fn insert(&mut self, index: usize, value: T);
+
+    fn remove(&mut self, index: usize);
+
+    fn move_row(&mut self, from: usize, to: usize);
+
+    fn clear(&mut self);
+}
+
+/// Applies `diffs` to the keys of a list, and makes the same changes to its rows.
+///
+/// Returns the index of the first row whose index may have changed.
+fn apply_diffs<T, K>(
+    keys: &mut FxIndexSet<K>,
+    rows: &mut impl Rows<T>,
+    diffs: Vec<VecDiff<T>>,
+    key_fn: impl Fn(&T) -> K,
+) -> Option<usize>
+where
+    K: Eq + Hash,
+{
+    let mut first_moved: Option<usize> = None;
+    let mut moved = |index: usize| {
+        first_moved = Some(first_moved.map_or(index, |first| first.min(index)));
+    };
+    for diff in diffs {
+        match diff {
+            VecDiff::Insert { index, value } => {
+                keys.shift_insert(index, key_fn(&value));
+                rows.insert(index, value);
+                moved(index + 1);
+            }
+            VecDiff::Remove { index } => {
+                keys.shift_remove_index(index);
+                rows.remove(index);
+                moved(index);
+            }
+            VecDiff::Move { from, to } => {
+                keys.move_index(from, to);
+                rows.move_row(from, to);
+                moved(from.min(to));
+            }
+            VecDiff::Update { index, value } => {
+                // as with a keyed diff, the row is only replaced if its key has changed
+                let key = key_fn(&value);
+                if keys.get_index(index) != Some(&key) {
+                    keys.shift_remove_index(index);
+                    keys.shift_insert(index, key);
+                    rows.remove(index);
+                    rows.insert(index, value);
+                }
+            }
+            VecDiff::Clear => {
+                keys.clear();
+                rows.clear();
+            }
+        }
+    }
+    first_moved
+}
+
+/// The rendered rows of a [`KeyedState`].
+struct DomRows<'a, VFS, V, VF>
+where
+    V: Render,
+{
+    parent: &'a crate::renderer::types::Element,
+    marker: &'a crate::renderer::types::Placeholder,
+    rendered_items: &'a mut Vec<Option<(VFS, V::State)>>,
+    view_fn: VF,
+}
+
+impl<VFS, V, VF> DomRows<'_, VFS, V, VF>
+where
+    V: Render,
+{
+    /// Mounts `item` so that it will be at `index` once it is inserted into the rows.
+    fn mount_at(&self, index: usize, item: &mut dyn Mountable) {
+        match self.rendered_items.get(index) {
+            Some(Some((_, next))) => next.insert_before_this_or_marker(
+                self.parent,
+                item,
+                Some(self.marker.as_ref()),
+            ),
+            _ => item.mount(self.parent, Some(self.marker.as_ref())),
+        }
+    }
+}
+
+impl<T, VFS, V, VF> Rows<T> for DomRows<'_, VFS, V, VF>
+where
+    V: Render,
+    VF: Fn(usize, T) -> (VFS, V),
+{
+    fn insert(&mut self, index: usize, value: T) {
+        let (set_index, view) = (self.view_fn)(index, value);
+        let mut item = view.build();
+        self.mount_at(index, &mut item);
+        self.rendered_items.insert(index, Some((set_index, item)));
+    }
+
+    fn remove(&mut self, index: usize) {
+        if let Some((_, mut item)) = self.rendered_items.remove(index) {
+            item.unmount();
+        }
+    }
+
+    fn move_row(&mut self, from: usize, to: usize) {
+        let mut row = self.rendered_items.remove(from);
+        if let Some((_, item)) = &mut row {
+            self.mount_at(to, item);
+        }
+        self.rendered_items.insert(to, row);
+    }
+
+    fn clear(&mut self) {
+        for (_, mut item) in self.rendered_items.drain(..).flatten() {
+            item.unmount();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_diffs, vec_changes, Rows};
+    use crate::view::keyed::{FxIndexSet, KeyedItems};
+    use reactive_graph::collections::{ArcSignalVec, VecDiff};
+
+    /// Rows that record which values they were created from.
+    #[derive(Default)]
+    struct TestRows {
+        rows: Vec<(u32, &'static str)>,
+        created: usize,
+    }
+
+    impl Rows<(u32, &'static str)> for TestRows {
+        fn insert(&mut self, index: usize, value: (u32, &'static str)) {
+            self.created += 1;
+            self.rows.insert(index, value);
+        }
+
+        fn remove(&mut self, index: usize) {
+            self.rows.remove(index);
+        }
+
+        fn move_row(&mut self, from: usize, to: usize) {
+            let row = self.rows.remove(from);
+            self.rows.insert(to, row);
+        }
+
+        fn clear(&mut self) {
+            self.rows.clear();
+        }
+    }
+
+    fn setup(items: &[(u32, &'static str)]) -> (FxIndexSet<u32>, TestRows) {
+        let keys = items.iter().map(|(key, _)| *key).collect();
+        let rows = TestRows {
+            rows: items.to_vec(),
+            created: 0,
+        };
+        (keys, rows)
+    }
+
+    #[test]
+    fn diffs_are_applied_to_keys_and_rows() {
+        let (mut keys, mut rows) = setup(&[(1, "a"), (2, "b"), (3, "c")]);
+        let first_moved = apply_diffs(
+            &mut keys,
+            &mut rows,
+            vec![
+                VecDiff::Insert {
+                    index: 1,
+                    value: (4, "d"),
+                },
+                VecDiff::Remove { index: 3 },
+                VecDiff::Move { from: 2, to: 0 },
+            ],
+            |(key, _)| *key,
+        );
+        assert_eq!(rows.rows, [(2, "b"), (1, "a"), (4, "d")]);
+        assert_eq!(keys.into_iter().collect::<Vec<_>>(), [2, 1, 4]);
+        assert_eq!(rows.created, 1);
+        assert_eq!(first_moved, Some(0));
+    }
+
+    #[test]
+    fn updates_only_replace_rows_whose_key_changed() {
+        let (mut keys, mut rows) = setup(&[(1, "a"), (2, "b")]);
+        let first_moved = apply_diffs(
+            &mut keys,
+            &mut rows,
+            vec![
+                VecDiff::Update {
+                    index: 0,
+                    value: (1, "changed"),
+                },
+                VecDiff::Update {
+                    index: 1,
+                    value: (3, "c"),
+                },
+            ],
+            |(key, _)| *key,
+        );
+        // the row for key 1 is kept, just as it would be by a keyed diff
+        assert_eq!(rows.rows, [(1, "a"), (3, "c")]);
+        assert_eq!(keys.into_iter().collect::<Vec<_>>(), [1, 3]);
+        assert_eq!(rows.created, 1);
+        assert_eq!(first_moved, None);
+    }
+
+    #[test]
+    fn clear_removes_every_row() {
+        let (mut keys, mut rows) = setup(&[(1, "a"), (2, "b")]);
+        apply_diffs(
+            &mut keys,
+            &mut rows,
+            vec![
+                VecDiff::Clear,
+                VecDiff::Insert {
+                    index: 0,
+                    value: (5, "e"),
+                },
+            ],
+            |(key, _)| *key,
+        );
+        assert_eq!(rows.rows, [(5, "e")]);
+        assert_eq!(keys.into_iter().collect::<Vec<_>>(), [5]);
+    }
+
+    #[test]
+    fn vec_changes_returns_diffs_after_the_first_render() {
+        let list = ArcSignalVec::new(vec![1, 2]);
+        let each = vec_changes(list.clone());
+
+        // the first render uses every item
+        assert_eq!(
+            each().into_diffs().unwrap_err().collect::<Vec<_>>(),
+            [1, 2]
+        );
+
+        list.push(3);
+        assert_eq!(
+            each().into_diffs().ok(),
+            Some(vec![VecDiff::Insert { index: 2, value: 3 }])
+        );
+
+        // rendering from scratch always uses the current items
+        assert_eq!(each().into_items().collect::<Vec<_>>(), [1, 2, 3]);
+    }
+}